@@ -15,16 +15,26 @@ impl crate::Cancelable for Error {}
 
 pub enum Command {
     List,
+    Watch,
+    Tui,
     Update,
     Register(std::iter::Skip<std::env::Args>),
     Dump,
     Plot(std::iter::Skip<std::env::Args>),
+    Spend(std::iter::Skip<std::env::Args>),
+    Settings(std::iter::Skip<std::env::Args>),
+    Undo,
+    Redo,
+    Sync,
 }
 
 fn help<W: std::io::Write>(mut out: W) {
     let cache = constant::path::cache()
         .and_then(|p| p.to_str())
         .unwrap_or("??");
+    let queue = constant::path::queue()
+        .and_then(|p| p.to_str())
+        .unwrap_or("??");
     let host = constant::path::host()
         .and_then(|p| p.to_str())
         .unwrap_or("??");
@@ -37,10 +47,17 @@ A command-line manager for the skull back end
 
 Commands:
   l list               List occurrences for the last two days
+  w watch              Keep the list up to date as changes arrive
+  t tui                Open an interactive dashboard
   u update             Update the cache
   r register [args...] Register new occurrences
   d dump               Dump the occurrences in CSV format
-  p plot     [args...] Plot an average of the occurrences
+  p plot     [args...] Plot the occurrences (modes: sliding, cumulative, daily, heatmap)
+  s spend    [range]   Show how much was spent per skull
+    settings [args...] Show or change the settings (timezone, day-start, window, quick-count, currency)
+    undo               Undo the last change
+    redo               Redo the last undone change
+    sync               Send the occurrences queued while offline
   h help               Show this help message
 
 Environment variables:
@@ -51,15 +68,26 @@ Environment variables:
 
 Paths:
   Cache                {cache}
+  Queue                {queue}
   Host                 {host}
 
 Examples:
   skull r bla 1 now                            Register a `bla` occurrence for now
   skull r bla 1 2023-04-30T11:11:11Z           Register a `bla` occurrence for the timestamp given
   skull r bla 1 -1h, ble 2.5 now               Register a `bla` occurrence for one hour ago and 2.5 `ble` for now
+  skull r bla 1 now '#with friends'            Register a `bla` occurrence for now with a note
+  skull r bla 1 now '#tea\\, milk', ble 1 now   Commas separate occurrences, unless escaped to keep them in a note
   skull p bla,ble 1d/6h ..                     Plot all `bla` and `ble` in a one day sliding window over six hours steps
   skull p bla,ble 1d/6h -1w..                  Plot `bla` and `ble` since one week ago in a one day sliding window over six hours steps
-  skull p bla 1d/6h 2023-01-01T00:00:00Z..-1d  Plot `bla`s since the timestamp given until one day ago in a one day sliding window over six hours steps",
+  skull p bla 1d/6h 2023-01-01T00:00:00Z..-1d  Plot `bla`s since the timestamp given until one day ago in a one day sliding window over six hours steps
+  skull p caffeine 1d/6h -1w..                 Plot all skulls tagged `caffeine` since one week ago in a one day sliding window over six hours steps
+  skull p bla 6h -4w.. -m cumulative           Plot the running total of `bla`s since four weeks ago sampled every six hours
+  skull p bla,ble -2w.. -m daily               Plot the daily sums of `bla` and `ble` since two weeks ago as bars
+  skull p caffeine -1y.. -m heatmap            Plot a calendar heatmap per skull tagged `caffeine` since one year ago
+  skull p bla 1d/6h -4w.. -o chart.svg         Save the plot of `bla`s since four weeks ago as an SVG file (or PNG with a `.png` extension)
+  skull s -4w..                                Show the spending of the last four weeks
+  skull settings timezone -03:00               Set the timezone used to lay out the days
+  skull settings day-start 5                   Start the days at five in the morning",
         constant::ENV_SYSTEM_USER,
         constant::ENV_USER,
         constant::ENV_PASSWORD,
@@ -73,9 +101,16 @@ pub fn parse() -> Result<Command, Error> {
     match args.next().as_deref() {
         None | Some("r" | "register") => Ok(Command::Register(args)),
         Some("l" | "list") => Ok(Command::List),
+        Some("w" | "watch") => Ok(Command::Watch),
+        Some("t" | "tui") => Ok(Command::Tui),
         Some("u" | "update") => Ok(Command::Update),
         Some("d" | "dump") => Ok(Command::Dump),
         Some("p" | "plot") => Ok(Command::Plot(args)),
+        Some("s" | "spend") => Ok(Command::Spend(args)),
+        Some("settings") => Ok(Command::Settings(args)),
+        Some("undo") => Ok(Command::Undo),
+        Some("redo") => Ok(Command::Redo),
+        Some("sync") => Ok(Command::Sync),
         Some("h" | "-h" | "help" | "--help") => {
             help(std::io::stdout());
             std::process::exit(0);
@@ -0,0 +1,195 @@
+use super::{Canvas, Result, into_rgb};
+
+/// The amounts of each skull summed per day, as laid out by the settings of the user
+pub(super) type Days =
+    std::collections::HashMap<types::SkullId, std::collections::BTreeMap<chrono::NaiveDate, f32>>;
+
+const BAR_WIDTH: u16 = 3;
+const EMPTY: (u8, u8, u8) = (40, 40, 40);
+
+pub(super) fn aggregate(occurrences: &[types::Occurrence], settings: &types::Settings) -> Days {
+    let mut days = Days::new();
+    for occurrence in occurrences {
+        let date = settings.day(&chrono::DateTime::<chrono::Utc>::from(occurrence.millis));
+        *days
+            .entry(occurrence.skull)
+            .or_default()
+            .entry(date)
+            .or_default() += occurrence.amount;
+    }
+    days
+}
+
+pub(super) fn bars(skulls: &[types::Skull], days: &Days) -> Result<()> {
+    let plotted = plotted(skulls, days);
+    let dates = dates(days);
+
+    Canvas::new()?.show(|f| {
+        // Only the most recent days that fit are shown
+        let skull_count = u16::try_from(plotted.len()).unwrap_or(u16::MAX);
+        let group_width = skull_count.saturating_mul(BAR_WIDTH + 1) + 1;
+        let fit = usize::from(f.area().width / group_width.max(1));
+
+        let groups = dates
+            .iter()
+            .skip(dates.len().saturating_sub(fit))
+            .map(|date| {
+                let bars = plotted
+                    .iter()
+                    .map(|(skull, amounts)| {
+                        let amount = amounts.get(date).copied().unwrap_or_default();
+                        ratatui::widgets::Bar::new(scale(amount))
+                            .text_value(amount.to_string())
+                            .style(ratatui::style::Style::default().fg(color(skull.color)))
+                    })
+                    .collect::<Vec<_>>();
+                ratatui::widgets::BarGroup::new(bars).label(date.format("%d-%b").to_string())
+            })
+            .collect::<Vec<_>>();
+
+        let legend = plotted
+            .iter()
+            .flat_map(|(skull, _)| {
+                [
+                    ratatui::text::Span::styled(
+                        "● ",
+                        ratatui::style::Style::default().fg(color(skull.color)),
+                    ),
+                    ratatui::text::Span::raw(format!("{}  ", skull.name)),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let [legend_area, chart_area] = ratatui::layout::Layout::vertical([
+            ratatui::layout::Constraint::Length(1),
+            ratatui::layout::Constraint::Min(0),
+        ])
+        .areas(f.area());
+
+        f.render_widget(ratatui::text::Line::from(legend), legend_area);
+        f.render_widget(
+            ratatui::widgets::BarChart::grouped(groups)
+                .bar_width(BAR_WIDTH)
+                .bar_gap(1)
+                .group_gap(1),
+            chart_area,
+        );
+    })
+}
+
+pub(super) fn heatmap(skulls: &[types::Skull], days: &Days) -> Result<()> {
+    use chrono::Datelike;
+
+    let plotted = plotted(skulls, days);
+    let dates = dates(days);
+
+    // Weeks start on Monday, like the rows of the grid
+    let first = dates.first().map(|date| {
+        *date - chrono::Duration::days(i64::from(date.weekday().num_days_from_monday()))
+    });
+
+    Canvas::new()?.show(|f| {
+        let areas = ratatui::layout::Layout::vertical(
+            plotted
+                .iter()
+                .map(|_| ratatui::layout::Constraint::Length(9)),
+        )
+        .split(f.area());
+
+        for ((skull, amounts), area) in plotted.iter().zip(areas.iter()) {
+            let max = amounts.values().copied().fold(0., f32::max);
+            let total = amounts.values().sum::<f32>();
+            let block = ratatui::widgets::Block::bordered()
+                .title(format!(" {} ({total}) ", skull.name))
+                .border_style(ratatui::style::Style::default().fg(color(skull.color)));
+
+            // Only the most recent weeks that fit are shown
+            let fit = usize::from(block.inner(*area).width.saturating_sub(4) / 2);
+            let weeks = first.map_or(0, |first| {
+                dates.last().map_or(0, |last| {
+                    usize::try_from((*last - first).num_days() / 7 + 1).unwrap_or_default()
+                })
+            });
+            let skipped = weeks.saturating_sub(fit);
+
+            let lines = ["Mon", "", "Wed", "", "Fri", "", "Sun"]
+                .into_iter()
+                .enumerate()
+                .map(|(weekday, label)| {
+                    let mut spans = vec![ratatui::text::Span::raw(format!("{label:<4}"))];
+                    spans.extend((skipped..weeks).filter_map(|week| {
+                        let date = first?
+                            + chrono::Duration::days(i64::try_from(week * 7 + weekday).ok()?);
+                        let amount = amounts.get(&date).copied().unwrap_or_default();
+                        let ratio = if max > 0. { amount / max } else { 0. };
+                        Some(ratatui::text::Span::styled(
+                            "■ ",
+                            ratatui::style::Style::default().fg(shade(skull.color, ratio)),
+                        ))
+                    }));
+                    ratatui::text::Line::from(spans)
+                })
+                .collect::<Vec<_>>();
+
+            f.render_widget(ratatui::widgets::Paragraph::new(lines).block(block), *area);
+        }
+    })
+}
+
+/// The skulls that have any occurrence, sorted by name
+fn plotted<'a>(
+    skulls: &'a [types::Skull],
+    days: &'a Days,
+) -> Vec<(
+    &'a types::Skull,
+    &'a std::collections::BTreeMap<chrono::NaiveDate, f32>,
+)> {
+    let mut plotted = days
+        .iter()
+        .filter_map(|(id, amounts)| Some((skulls.iter().find(|s| s.id == *id)?, amounts)))
+        .collect::<Vec<_>>();
+    plotted.sort_unstable_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    plotted
+}
+
+/// Every date from the first to the last day with occurrences
+fn dates(days: &Days) -> Vec<chrono::NaiveDate> {
+    let first = days.values().filter_map(|d| d.keys().next()).min();
+    let last = days.values().filter_map(|d| d.keys().next_back()).max();
+
+    match (first, last) {
+        (Some(first), Some(last)) => first.iter_days().take_while(|d| d <= last).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn scale(amount: f32) -> u64 {
+    // Allow(clippy::cast_possible_truncation, clippy::cast_sign_loss): Amounts are always
+    // positive and small
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    {
+        (amount * 100.).round() as u64
+    }
+}
+
+fn color(color: u32) -> ratatui::style::Color {
+    let (r, g, b) = into_rgb(color);
+    ratatui::style::Color::Rgb(r, g, b)
+}
+
+/// Blends the skull color into the empty cell color, proportionally to `ratio`
+fn shade(color: u32, ratio: f32) -> ratatui::style::Color {
+    let blend = |empty: u8, full: u8| {
+        let empty = f32::from(empty);
+        let full = f32::from(full);
+        // Allow(clippy::cast_possible_truncation, clippy::cast_sign_loss): The result is always
+        // between both `u8` values
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            (empty + (full - empty) * ratio).round() as u8
+        }
+    };
+
+    let (r, g, b) = into_rgb(color);
+    ratatui::style::Color::Rgb(blend(EMPTY.0, r), blend(EMPTY.1, g), blend(EMPTY.2, b))
+}
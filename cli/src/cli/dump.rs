@@ -3,26 +3,32 @@ pub fn output(skulls: Vec<types::Skull>, occurrences: &[types::Occurrence]) {
 
     let skull_lookup = skulls
         .into_iter()
-        .map(|s| {
-            let name = if s.name.contains(',') {
-                format!(r#""{}""#, s.name)
-            } else {
-                s.name
-            };
-            (s.id, name)
-        })
+        .map(|s| (s.id, escape(s.name)))
         .collect::<std::collections::HashMap<_, _>>();
 
     let mut stdout = std::io::stdout().lock();
 
-    drop(writeln!(stdout, "skull,amount,millis"));
+    drop(writeln!(stdout, "skull,amount,millis,note"));
     for o in occurrences {
         let amount = o.amount;
         let millis = o.millis;
+        let note = o.note.clone().map(escape).unwrap_or_default();
         if let Some(skull) = skull_lookup.get(&o.skull) {
-            drop(writeln!(stdout, "{skull},{amount},{millis}"));
+            drop(writeln!(stdout, "{skull},{amount},{millis},{note}"));
         } else {
-            drop(writeln!(stdout, "<unknown:{}>,{amount},{millis}", o.skull));
+            drop(writeln!(
+                stdout,
+                "<unknown:{}>,{amount},{millis},{note}",
+                o.skull
+            ));
         }
     }
 }
+
+fn escape(value: String) -> String {
+    if value.contains([',', '"']) {
+        format!(r#""{}""#, value.replace('"', r#""""#))
+    } else {
+        value
+    }
+}
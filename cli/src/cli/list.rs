@@ -26,20 +26,33 @@ fn month_name(month: u32) -> &'static str {
     }
 }
 
-pub fn output(skulls: Vec<types::Skull>, occurrences: &[types::Occurrence]) {
+/// The start of the first day of the listed window
+pub fn start(settings: &types::Settings) -> types::Millis {
+    let now = settings.now();
+    let days = i64::from(settings.window.saturating_sub(1));
+    types::Millis::from(settings.start(types::Period::Day, &now) - chrono::Duration::days(days))
+}
+
+pub fn output(
+    skulls: Vec<types::Skull>,
+    occurrences: &[types::Occurrence],
+    settings: &types::Settings,
+) {
     use chrono::{Datelike, Timelike};
     use rucline::crossterm::style::Colorize;
 
-    let day_limit =
-        types::Millis::from(chrono::Utc::now().with_hour(5).unwrap().timestamp_millis());
-    let consumption_limit =
-        types::Millis::from(chrono::Utc::now() - chrono::Duration::hours(24 * 3 / 4));
+    let now = settings.now();
+    let start = start(settings);
+    let day_limit = types::Millis::from(settings.start(types::Period::Day, &now));
 
     let skull_len = max_skull_name_len(&skulls);
 
     let skulls = skulls
         .into_iter()
-        .map(|s| (s.id, (s.name, s.color, s.limit)))
+        .map(|s| {
+            let consumption_limit = types::Millis::from(settings.start(s.period, &now));
+            (s.id, (s.name, s.color, s.limit, consumption_limit))
+        })
         .collect::<std::collections::HashMap<_, _>>();
 
     let mut consumption = std::collections::HashMap::<types::SkullId, (f32, f32)>::new();
@@ -48,8 +61,22 @@ pub fn output(skulls: Vec<types::Skull>, occurrences: &[types::Occurrence]) {
         .iter()
         .rev()
         .filter_map(|o| skulls.get(&o.skull).map(|skull| (skull, o)))
-        .for_each(|((skull, color, limit), occurrence)| {
-            let timestamp = chrono::DateTime::<chrono::Local>::from(occurrence.millis);
+        .for_each(|((skull, color, limit, consumption_limit), occurrence)| {
+            let included = occurrence.millis >= *consumption_limit;
+
+            if included && let Some(limit) = *limit {
+                consumption
+                    .entry(occurrence.skull)
+                    .or_insert((limit, 0.0))
+                    .1 += occurrence.amount;
+            }
+
+            if occurrence.millis < start {
+                return;
+            }
+
+            let timestamp = chrono::DateTime::<chrono::Utc>::from(occurrence.millis)
+                .with_timezone(&settings.offset());
             let timestamp = format!(
                 "{day:02}-{month} {hour:02}:{minute:02}",
                 day = timestamp.day(),
@@ -58,13 +85,7 @@ pub fn output(skulls: Vec<types::Skull>, occurrences: &[types::Occurrence]) {
                 minute = timestamp.minute()
             );
 
-            let included = if occurrence.millis >= consumption_limit {
-                if let Some(limit) = *limit {
-                    consumption
-                        .entry(occurrence.skull)
-                        .or_insert((limit, 0.0))
-                        .1 += occurrence.amount;
-                }
+            let included = if included {
                 rucline::crossterm::style::style("┃").with((200, 50, 50).into())
             } else {
                 rucline::crossterm::style::style(" ")
@@ -91,7 +112,7 @@ pub fn output(skulls: Vec<types::Skull>, occurrences: &[types::Occurrence]) {
     if !consumption.is_empty() {
         println!();
         for (skull, (limit, amount)) in &consumption {
-            let (skull, _, _) = skulls.get(skull).unwrap();
+            let (skull, _, _, _) = skulls.get(skull).unwrap();
             let line = format!("{skull:<skull_len$} {amount}/{limit}");
             println!(
                 "{}",
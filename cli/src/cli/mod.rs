@@ -1,7 +1,12 @@
+mod calendar;
 pub mod dump;
 pub mod list;
 pub mod plot;
 pub mod register;
+pub mod settings;
+pub mod spend;
+mod svg;
+pub mod tui;
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -29,8 +34,28 @@ pub enum Error {
     InvalidRangeValue(String),
     #[error("Invalid sliding window value: {0}")]
     InvalidSlidingWindowValue(String),
+    #[error("Invalid step value: {0}")]
+    InvalidStepValue(String),
     #[error("Failed to draw TUI: {0}")]
     Ratatui(std::io::Error),
+    #[error("Missing value for {0}")]
+    MissingValue(&'static str),
+    #[error("Unsupported output format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Only line plots can be exported")]
+    UnsupportedExport,
+    #[error("Invalid plot mode: {0}")]
+    InvalidPlotMode(String),
+    #[error("Failed to build the chart: {0}")]
+    Svg(resvg::usvg::Error),
+    #[error("Failed to rasterize the chart: {0}")]
+    Rasterize(String),
+    #[error("Failed to write the chart: {0}")]
+    Export(std::io::Error),
+    #[error("Unknown setting: {0}")]
+    UnknownSetting(String),
+    #[error("Invalid setting value: {0}")]
+    InvalidSettingValue(String),
 }
 
 impl crate::PostAction for Error {}
@@ -56,6 +81,16 @@ fn into_millis(time: impl AsRef<str>) -> Result<types::Millis> {
     }
 }
 
+fn into_range(range: String) -> Result<(Option<types::Millis>, Option<types::Millis>)> {
+    match range.split_once("..") {
+        Some(("", "")) => Ok((None, None)),
+        Some((start, "")) => Ok((Some(into_millis(start)?), None)),
+        Some(("", end)) => Ok((None, Some(into_millis(end)?))),
+        Some((start, end)) => Ok((Some(into_millis(start)?), Some(into_millis(end)?))),
+        None => Err(Error::InvalidRangeValue(range)),
+    }
+}
+
 fn parse_duration(duration: impl AsRef<str>) -> Result<std::time::Duration> {
     let duration = duration.as_ref();
     if let Some(amount) = duration.strip_suffix('w') {
@@ -94,3 +129,50 @@ fn into_rgb(color: u32) -> (u8, u8, u8) {
     let b = (color & 0xff) as u8;
     (r, g, b)
 }
+
+struct Canvas {
+    terminal: ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+}
+
+impl Canvas {
+    fn new() -> Result<Self> {
+        use rucline::crossterm;
+        use std::io::Write;
+
+        crossterm::terminal::enable_raw_mode().map_err(Error::Terminal)?;
+        let mut stdout = std::io::stdout();
+        crossterm::execute!(&mut stdout, crossterm::terminal::EnterAlternateScreen)
+            .map_err(Error::Terminal)?;
+        let backend = ratatui::backend::CrosstermBackend::new(stdout);
+        let terminal = ratatui::Terminal::new(backend).map_err(Error::Ratatui)?;
+        Ok(Self { terminal })
+    }
+
+    /// Keeps redrawing until `q` is pressed
+    fn show(&mut self, mut draw: impl FnMut(&mut ratatui::Frame<'_>)) -> Result<()> {
+        loop {
+            self.terminal.draw(&mut draw).map_err(Error::Ratatui)?;
+
+            rucline::crossterm::event::poll(std::time::Duration::from_secs(10))
+                .map_err(Error::Terminal)?;
+            if let rucline::crossterm::event::Event::Key(key) =
+                rucline::crossterm::event::read().map_err(Error::Terminal)?
+                && let rucline::crossterm::event::KeyCode::Char('q') = key.code
+            {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl Drop for Canvas {
+    fn drop(&mut self) {
+        use std::io::Write;
+
+        drop(rucline::crossterm::terminal::disable_raw_mode());
+        drop(rucline::crossterm::execute!(
+            self.terminal.backend_mut(),
+            rucline::crossterm::terminal::LeaveAlternateScreen
+        ));
+    }
+}
@@ -1,14 +1,21 @@
-use super::{Error, Result, into_millis, into_rgb};
+use super::{Canvas, Error, Result, calendar, into_range, into_rgb, svg};
 
 pub fn input<Args>(
-    mut args: Args,
+    args: Args,
     skulls: &[types::Skull],
-) -> Result<(types::request::occurrence::Search, SlidingWindowProto)>
+    tags: &[types::Tag],
+) -> Result<(
+    types::request::occurrence::Search,
+    Mode,
+    Option<std::path::PathBuf>,
+)>
 where
     Args: Iterator<Item = String>,
 {
+    let (mut args, kind, output) = extract_options(args)?;
+
     let selected_skulls = args.next();
-    let window = args.next();
+    let window = if kind.windowed() { args.next() } else { None };
     let range = args.next();
 
     let space_denier = [(
@@ -19,35 +26,64 @@ where
 
     let skulls_str = match selected_skulls {
         Some(skulls_str) => skulls_str,
-        None => get_skulls(skulls, &space_denier)?,
+        None => get_skulls(skulls, tags, &space_denier)?,
     };
-    let skulls = skulls_str
-        .split(',')
-        .map(|s| {
-            skulls
-                .iter()
-                .find(|skull| skull.name == s)
-                .map(|s| s.id)
-                .ok_or(Error::UnknownSkull(String::from(s)))
-        })
-        .collect::<Result<_>>()?;
+
+    let mut selected_skulls = std::collections::HashSet::new();
+    let mut selected_tags = std::collections::HashSet::new();
+    for name in skulls_str.split(',') {
+        if let Some(skull) = skulls.iter().find(|skull| skull.name == name) {
+            selected_skulls.insert(skull.id);
+        } else if tags.iter().any(|tag| tag.name == name) {
+            selected_tags.insert(String::from(name));
+        } else {
+            return Err(Error::UnknownSkull(String::from(name)));
+        }
+    }
 
     if args.next().is_some() {
         return Err(Error::TooManyArgs);
     }
 
-    let window_str = match window {
-        Some(window_str) => window_str,
-        None => get_window(&skulls_str, &space_denier)?,
-    };
-    let (window, step) = match window_str.as_str().split_once('/') {
-        Some(("", _) | (_, "")) | None => return Err(Error::InvalidSlidingWindowValue(window_str)),
-        Some((window, step)) => (
-            i64::try_from(super::parse_duration(window)?.as_millis())
-                .expect("window should always fit in an `i64`"),
-            usize::try_from(super::parse_duration(step)?.as_millis())
-                .expect("step should always fit in a `usize`"),
-        ),
+    let (mode, window_str) = match kind {
+        Kind::Sliding => {
+            let window_str = match window {
+                Some(window_str) => window_str,
+                None => get_window(&skulls_str, &space_denier)?,
+            };
+            let (window, step) = match window_str.as_str().split_once('/') {
+                Some(("", _) | (_, "")) | None => {
+                    return Err(Error::InvalidSlidingWindowValue(window_str));
+                }
+                Some((window, step)) => (
+                    i64::try_from(super::parse_duration(window)?.as_millis())
+                        .expect("window should always fit in an `i64`"),
+                    usize::try_from(super::parse_duration(step)?.as_millis())
+                        .expect("step should always fit in a `usize`"),
+                ),
+            };
+            (
+                Mode::Sliding(SlidingWindowProto { window, step }),
+                window_str,
+            )
+        }
+        Kind::Cumulative => {
+            let step_str = match window {
+                Some(step_str) => step_str,
+                None => get_window(&skulls_str, &space_denier)?,
+            };
+            let step = usize::try_from(super::parse_duration(&step_str)?.as_millis())
+                .expect("step should always fit in a `usize`");
+            if step == 0 {
+                return Err(Error::InvalidStepValue(step_str));
+            }
+            (
+                Mode::Cumulative(SlidingWindowProto { window: 0, step }),
+                step_str,
+            )
+        }
+        Kind::Daily => (Mode::Daily, String::from("daily")),
+        Kind::Heatmap => (Mode::Heatmap, String::from("heatmap")),
     };
 
     let range = match range {
@@ -55,13 +91,7 @@ where
         None => get_range(&skulls_str, &window_str, &space_denier)?,
     };
 
-    let (start, end) = match range.split_once("..") {
-        Some(("", "")) => (None, None),
-        Some((start, "")) => (Some(into_millis(start)?), None),
-        Some(("", end)) => (None, Some(into_millis(end)?)),
-        Some((start, end)) => (Some(into_millis(start)?), Some(into_millis(end)?)),
-        None => return Err(Error::InvalidRangeValue(range)),
-    };
+    let (start, end) = into_range(range)?;
 
     // Example:
     // start = 4
@@ -70,47 +100,95 @@ where
     //
     // 0 1 2 3 4 5 6 7 8 9
     //    [---|         ]
-    let start = start.map(|start| (i64::from(start) - window).into());
+    let start = match &mode {
+        Mode::Sliding(proto) => start.map(|start| (i64::from(start) - proto.window).into()),
+        Mode::Cumulative(_) | Mode::Daily | Mode::Heatmap => start,
+    };
     let search = types::request::occurrence::Search {
-        skulls: Some(skulls),
+        skulls: Some(selected_skulls).filter(|s| !s.is_empty()),
+        tags: Some(selected_tags).filter(|t| !t.is_empty()),
         start,
         end,
         limit: None,
+        cursor: None,
     };
-    let proto = SlidingWindowProto { window, step };
 
-    Ok((search, proto))
+    Ok((search, mode, output))
+}
+
+fn extract_options<Args>(
+    mut args: Args,
+) -> Result<(std::vec::IntoIter<String>, Kind, Option<std::path::PathBuf>)>
+where
+    Args: Iterator<Item = String>,
+{
+    let mut kind = Kind::Sliding;
+    let mut output = None;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "-o" || arg == "--output" {
+            let path = args
+                .next()
+                .map(std::path::PathBuf::from)
+                .ok_or(Error::MissingValue("--output"))?;
+            svg::Format::from_path(&path)?;
+            output = Some(path);
+        } else if arg == "-m" || arg == "--mode" {
+            kind = match args.next().as_deref() {
+                Some("sliding") => Kind::Sliding,
+                Some("cumulative") => Kind::Cumulative,
+                Some("daily") => Kind::Daily,
+                Some("heatmap") => Kind::Heatmap,
+                Some(mode) => return Err(Error::InvalidPlotMode(String::from(mode))),
+                None => return Err(Error::MissingValue("--mode")),
+            };
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    if output.is_some() && !kind.windowed() {
+        return Err(Error::UnsupportedExport);
+    }
+
+    Ok((positional.into_iter(), kind, output))
 }
 
 fn get_skulls(
     skulls: &[types::Skull],
+    tags: &[types::Tag],
     space_denier: &rucline::actions::KeyBindings,
 ) -> Result<String> {
     use rucline::{crossterm::style::Colorize, prompt::Builder};
 
+    let names = skulls
+        .iter()
+        .map(|s| s.name.as_str())
+        .chain(tags.iter().map(|t| t.name.as_str()))
+        .collect::<Vec<_>>();
+
     rucline::prompt::Prompt::from("> ".white())
         .suggester_fn(|buffer| {
             if buffer.is_empty() {
-                skulls
+                names
                     .iter()
-                    .map(|s| std::borrow::Cow::Borrowed(s.name.as_str()))
+                    .map(|&s| std::borrow::Cow::Borrowed(s))
                     .collect()
             } else {
                 match buffer.rsplit_once(',') {
                     Some((head, current)) => {
                         let already_chosen = head.split(',').collect::<Vec<_>>();
-                        skulls
+                        names
                             .iter()
-                            .map(|s| s.name.as_str())
                             .filter(|s| !already_chosen.contains(s) && s.starts_with(current))
                             .map(|s| std::borrow::Cow::Owned(format!("{head},{s}")))
                             .collect()
                     }
-                    None => skulls
+                    None => names
                         .iter()
-                        .map(|s| s.name.as_str())
                         .filter(|s| s.starts_with(buffer.as_str()))
-                        .map(std::borrow::Cow::Borrowed)
+                        .map(|&s| std::borrow::Cow::Borrowed(s))
                         .collect(),
                 }
             }
@@ -123,18 +201,17 @@ fn get_skulls(
                     Some((_, "")) => None,
                     Some((head, current)) => {
                         let already_chosen = head.split(',').collect::<Vec<_>>();
-                        skulls.iter().find_map(|s| {
-                            let name = s.name.as_str();
-                            if already_chosen.contains(&name) {
+                        names.iter().find_map(|name| {
+                            if already_chosen.contains(name) {
                                 None
                             } else {
                                 name.strip_prefix(current)
                             }
                         })
                     }
-                    None => skulls
+                    None => names
                         .iter()
-                        .find_map(|s| s.name.strip_prefix(buffer.as_str())),
+                        .find_map(|name| name.strip_prefix(buffer.as_str())),
                 }
             }
         })
@@ -195,13 +272,70 @@ fn get_range(
 pub fn output(
     skulls: &[types::Skull],
     occurrences: &[types::Occurrence],
-    proto: SlidingWindowProto,
+    settings: &types::Settings,
+    mode: Mode,
+    path: Option<&std::path::Path>,
 ) -> Result<()> {
-    let buckets = aggregate(occurrences, proto);
-    display(skulls, buckets)
+    let buckets = match mode {
+        Mode::Sliding(proto) => aggregate(occurrences, proto),
+        Mode::Cumulative(proto) => accumulate(occurrences, proto),
+        Mode::Daily => {
+            return calendar::bars(skulls, &calendar::aggregate(occurrences, settings));
+        }
+        Mode::Heatmap => {
+            return calendar::heatmap(skulls, &calendar::aggregate(occurrences, settings));
+        }
+    };
+
+    match path {
+        Some(path) => svg::export(&Series::new(skulls, buckets), path),
+        None => display(skulls, buckets),
+    }
 }
 
-fn aggregate(occurrences: &[types::Occurrence], proto: SlidingWindowProto) -> Buckets {
+pub(super) fn aggregate(occurrences: &[types::Occurrence], proto: SlidingWindowProto) -> Buckets {
+    let (min, max, timed_amounts) = group(occurrences);
+    let sliding_window = proto.finalize(min, max);
+
+    timed_amounts
+        .iter()
+        .map(|(&skull, occurrences)| {
+            let windows = collect_sum_of_sliding_window(sliding_window.iter(), occurrences);
+            (skull, windows)
+        })
+        .collect()
+}
+
+fn accumulate(occurrences: &[types::Occurrence], proto: SlidingWindowProto) -> Buckets {
+    let (min, max, timed_amounts) = group(occurrences);
+    let steps = proto.finalize(min, max);
+
+    timed_amounts
+        .iter()
+        .map(|(&skull, occurrences)| {
+            let mut total = 0.;
+            let mut cursor = 0;
+            let totals = steps
+                .iter()
+                .map(|(_, end)| {
+                    while let Some(occurrence) = occurrences.get(cursor).filter(|o| o.millis <= end)
+                    {
+                        total += occurrence.amount;
+                        cursor += 1;
+                    }
+                    TimedAmount {
+                        millis: end,
+                        amount: total,
+                    }
+                })
+                .collect();
+            (skull, totals)
+        })
+        .collect()
+}
+
+/// Groups the occurrences per skull sorted by time, along with the time range they cover
+fn group(occurrences: &[types::Occurrence]) -> (i64, i64, Buckets) {
     let (min, max, mut timed_amounts) = occurrences.iter().fold(
         (i64::MAX, i64::MIN, Buckets::new()),
         |(min, max, mut acc), curr| {
@@ -214,19 +348,11 @@ fn aggregate(occurrences: &[types::Occurrence], proto: SlidingWindowProto) -> Bu
         },
     );
 
-    let sliding_window = proto.finalize(min, max);
-
     for amounts in timed_amounts.values_mut() {
         amounts.sort_unstable_by_key(|a| a.millis);
     }
 
-    timed_amounts
-        .iter()
-        .map(|(&skull, occurrences)| {
-            let windows = collect_sum_of_sliding_window(sliding_window.iter(), occurrences);
-            (skull, windows)
-        })
-        .collect()
+    (min, max, timed_amounts)
 }
 
 fn collect_sum_of_sliding_window(
@@ -269,83 +395,108 @@ fn sum_all_occurrences_within_window(
 }
 
 fn display(skulls: &[types::Skull], buckets: Buckets) -> Result<()> {
-    let mut min_x = f64::MAX;
-    let mut max_x = f64::MIN;
-    let mut min_y = f64::MAX;
-    let mut max_y = f64::MIN;
-
-    let buckets = buckets
-        .into_iter()
-        .filter_map(|(skull, data)| {
-            let skull = skulls.iter().find(|s| s.id == skull)?;
-            let data = data
-                .into_iter()
-                .map(|TimedAmount { millis, amount }| {
-                    // Allow(clippy::cast_precision_loss): This is the best we can do
-                    #[allow(clippy::cast_precision_loss)]
-                    let millis = millis as f64;
-                    let amount = f64::from(amount);
-
-                    min_x = min_x.min(millis);
-                    max_x = max_x.max(millis);
-                    min_y = min_y.min(amount);
-                    max_y = max_y.max(amount);
-
-                    (millis, amount)
-                })
-                .collect::<Vec<_>>();
-            Some((skull, data))
-        })
-        .collect::<Vec<_>>();
+    let series = Series::new(skulls, buckets);
+    let chart = series.chart();
 
-    let datasets = buckets
-        .iter()
-        .map(|(skull, data)| {
-            ratatui::widgets::Dataset::default()
-                .name(skull.name.as_str())
-                .marker(ratatui::symbols::Marker::Braille)
-                .graph_type(ratatui::widgets::GraphType::Line)
-                .style(ratatui::style::Style::default().fg({
-                    let color = into_rgb(skull.color);
-                    ratatui::style::Color::Rgb(color.0, color.1, color.2)
-                }))
-                .data(data.as_slice())
-        })
-        .collect();
-
-    let chart = ratatui::widgets::Chart::new(datasets)
-        .block(ratatui::widgets::Block::default())
-        .x_axis(
-            ratatui::widgets::Axis::default()
-                .bounds([min_x, max_x])
-                .title("Date"),
-        )
-        .y_axis(
-            ratatui::widgets::Axis::default()
-                .bounds([min_y, max_y])
-                .title("Sum"),
-        );
-
-    let mut canvas = Canvas::new()?;
-    loop {
-        canvas
-            .terminal
-            .draw(|f| {
-                f.render_widget(chart.clone(), f.area());
+    Canvas::new()?.show(|f| f.render_widget(chart.clone(), f.area()))
+}
+
+/// The aggregated buckets as chart points, along with the bounds that fit all of them
+pub(super) struct Series<'a> {
+    pub(super) data: Vec<(&'a types::Skull, Vec<(f64, f64)>)>,
+    pub(super) x: [f64; 2],
+    pub(super) y: [f64; 2],
+}
+
+impl<'a> Series<'a> {
+    pub(super) fn new(skulls: &'a [types::Skull], buckets: Buckets) -> Self {
+        let mut min_x = f64::MAX;
+        let mut max_x = f64::MIN;
+        let mut min_y = f64::MAX;
+        let mut max_y = f64::MIN;
+
+        let data = buckets
+            .into_iter()
+            .filter_map(|(skull, data)| {
+                let skull = skulls.iter().find(|s| s.id == skull)?;
+                let data = data
+                    .into_iter()
+                    .map(|TimedAmount { millis, amount }| {
+                        // Allow(clippy::cast_precision_loss): This is the best we can do
+                        #[allow(clippy::cast_precision_loss)]
+                        let millis = millis as f64;
+                        let amount = f64::from(amount);
+
+                        min_x = min_x.min(millis);
+                        max_x = max_x.max(millis);
+                        min_y = min_y.min(amount);
+                        max_y = max_y.max(amount);
+
+                        (millis, amount)
+                    })
+                    .collect::<Vec<_>>();
+                Some((skull, data))
             })
-            .map_err(Error::Ratatui)?;
-
-        rucline::crossterm::event::poll(std::time::Duration::from_secs(10))
-            .map_err(Error::Terminal)?;
-        if let rucline::crossterm::event::Event::Key(key) =
-            rucline::crossterm::event::read().map_err(Error::Terminal)?
-            && let rucline::crossterm::event::KeyCode::Char('q') = key.code
-        {
-            break;
+            .collect::<Vec<_>>();
+
+        Self {
+            data,
+            x: [min_x, max_x],
+            y: [min_y, max_y],
         }
     }
 
-    Ok(())
+    pub(super) fn chart(&self) -> ratatui::widgets::Chart<'_> {
+        let datasets = self
+            .data
+            .iter()
+            .map(|(skull, data)| {
+                ratatui::widgets::Dataset::default()
+                    .name(skull.name.as_str())
+                    .marker(ratatui::symbols::Marker::Braille)
+                    .graph_type(ratatui::widgets::GraphType::Line)
+                    .style(ratatui::style::Style::default().fg({
+                        let color = into_rgb(skull.color);
+                        ratatui::style::Color::Rgb(color.0, color.1, color.2)
+                    }))
+                    .data(data.as_slice())
+            })
+            .collect();
+
+        ratatui::widgets::Chart::new(datasets)
+            .block(ratatui::widgets::Block::default())
+            .x_axis(
+                ratatui::widgets::Axis::default()
+                    .bounds(self.x)
+                    .title("Date"),
+            )
+            .y_axis(
+                ratatui::widgets::Axis::default()
+                    .bounds(self.y)
+                    .title("Sum"),
+            )
+    }
+}
+
+pub enum Mode {
+    Sliding(SlidingWindowProto),
+    /// The running total, sampled every `step`
+    Cumulative(SlidingWindowProto),
+    Daily,
+    Heatmap,
+}
+
+enum Kind {
+    Sliding,
+    Cumulative,
+    Daily,
+    Heatmap,
+}
+
+impl Kind {
+    fn windowed(&self) -> bool {
+        matches!(self, Self::Sliding | Self::Cumulative)
+    }
 }
 
 pub struct SlidingWindowProto {
@@ -364,9 +515,9 @@ impl SlidingWindowProto {
     }
 }
 
-type Buckets = std::collections::HashMap<types::SkullId, Vec<TimedAmount>>;
+pub(super) type Buckets = std::collections::HashMap<types::SkullId, Vec<TimedAmount>>;
 
-struct TimedAmount {
+pub(super) struct TimedAmount {
     millis: i64,
     amount: f32,
 }
@@ -397,34 +548,3 @@ impl SlidingWindow {
             .map(move |i| (i - window, i))
     }
 }
-
-struct Canvas {
-    terminal: ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
-}
-
-impl Canvas {
-    fn new() -> Result<Self> {
-        use rucline::crossterm;
-        use std::io::Write;
-
-        crossterm::terminal::enable_raw_mode().map_err(Error::Terminal)?;
-        let mut stdout = std::io::stdout();
-        crossterm::execute!(&mut stdout, crossterm::terminal::EnterAlternateScreen)
-            .map_err(Error::Terminal)?;
-        let backend = ratatui::backend::CrosstermBackend::new(stdout);
-        let terminal = ratatui::Terminal::new(backend).map_err(Error::Ratatui)?;
-        Ok(Self { terminal })
-    }
-}
-
-impl Drop for Canvas {
-    fn drop(&mut self) {
-        use std::io::Write;
-
-        drop(rucline::crossterm::terminal::disable_raw_mode());
-        drop(rucline::crossterm::execute!(
-            self.terminal.backend_mut(),
-            rucline::crossterm::terminal::LeaveAlternateScreen
-        ));
-    }
-}
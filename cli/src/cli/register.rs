@@ -1,33 +1,40 @@
 use super::{Error, Result, into_millis};
 
-struct Completions {
+pub(super) struct Completions {
     options: Vec<String>,
     matcher: fuzzy_matcher::skim::SkimMatcherV2,
 }
 
 impl Completions {
-    fn new(options: Vec<String>) -> Self {
+    pub(super) fn new(options: Vec<String>) -> Self {
         Self {
             options,
             matcher: fuzzy_matcher::skim::SkimMatcherV2::default(),
         }
     }
-}
 
-impl rucline::completion::Suggester for Completions {
-    fn suggest_for(&self, buffer: &rucline::Buffer) -> Vec<std::borrow::Cow<'_, str>> {
-        let buffer = buffer.as_str();
-        let mut suggestions = self
+    /// The options matching `buffer`, from the weakest to the strongest match
+    pub(super) fn matches(&self, buffer: &str) -> Vec<&str> {
+        let mut matches = self
             .options
             .iter()
             .filter_map(|opt| {
                 self.matcher
                     .fuzzy(opt, buffer, false)
-                    .map(|(score, _)| (score, std::borrow::Cow::Borrowed(opt.as_str())))
+                    .map(|(score, _)| (score, opt.as_str()))
             })
             .collect::<Vec<_>>();
-        suggestions.sort_unstable();
-        suggestions.into_iter().map(|(_, opt)| opt).collect()
+        matches.sort_unstable();
+        matches.into_iter().map(|(_, opt)| opt).collect()
+    }
+}
+
+impl rucline::completion::Suggester for Completions {
+    fn suggest_for(&self, buffer: &rucline::Buffer) -> Vec<std::borrow::Cow<'_, str>> {
+        self.matches(buffer.as_str())
+            .into_iter()
+            .map(std::borrow::Cow::Borrowed)
+            .collect()
     }
 }
 
@@ -41,22 +48,50 @@ pub fn input<Args>(
     args: Args,
     skulls: &[types::Skull],
     quicks: &[types::Quick],
+    prediction: Option<&types::Prediction>,
 ) -> Result<Vec<types::request::occurrence::Item>>
 where
     Args: Iterator<Item = String>,
 {
-    args.collect::<Vec<_>>()
-        .join(" ")
-        .split(',')
-        .map(|params| parse_skull_params(params, skulls, quicks))
+    split_items(&args.collect::<Vec<_>>().join(" "))
+        .iter()
+        .map(|params| parse_skull_params(params, skulls, quicks, prediction))
         .collect()
 }
 
+/// Whether registering `args` prompts for a skull, which happens for any item naming none
+pub fn prompts(args: &[String]) -> bool {
+    split_items(&args.join(" "))
+        .iter()
+        .any(|params| split_note(params).0.trim().is_empty())
+}
+
+/// Splits the items on `,`, keeping `\,` as a literal comma so that notes may contain commas
+fn split_items(input: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = input.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' if chars.peek() == Some(&',') => {
+                chars.next();
+                current.push(',');
+            }
+            ',' => items.push(std::mem::take(&mut current)),
+            char => current.push(char),
+        }
+    }
+    items.push(current);
+    items
+}
+
 fn parse_skull_params(
     params: &str,
     skulls: &[types::Skull],
     quicks: &[types::Quick],
+    prediction: Option<&types::Prediction>,
 ) -> Result<types::request::occurrence::Item> {
+    let (params, note) = split_note(params);
     let mut args = params.split(' ').filter(|b| !b.is_empty());
 
     let skull = args.next();
@@ -67,10 +102,10 @@ fn parse_skull_params(
         return Err(Error::TooManyArgs);
     }
 
-    let skull = match skull {
-        Some(s) => into_skull(skulls, s),
-        None => get_skull(skulls).and_then(|s| into_skull(skulls, &s)),
-    }?;
+    let (skull, amount) = match skull {
+        Some(s) => (into_skull(skulls, s)?, amount.map(String::from)),
+        None => prompt_skull(skulls, prediction)?,
+    };
 
     let amount = match amount {
         Some(amount) => into_amount(amount),
@@ -86,15 +121,60 @@ fn parse_skull_params(
         skull: skull.id,
         amount,
         millis,
+        note: note.map(String::from),
     })
 }
 
-fn get_skull(skulls: &[types::Skull]) -> Result<String> {
+fn split_note(params: &str) -> (&str, Option<&str>) {
+    match params.split_once('#') {
+        Some((params, note)) => (params, Some(note.trim()).filter(|n| !n.is_empty())),
+        None => (params, None),
+    }
+}
+
+/// Prompts for the skull, which may be followed by the amount when accepting the prediction
+///
+/// As names may contain spaces, the amount is only split off when what precedes it is a known skull
+fn prompt_skull<'a>(
+    skulls: &'a [types::Skull],
+    prediction: Option<&types::Prediction>,
+) -> Result<(&'a types::Skull, Option<String>)> {
+    let input = get_skull(skulls, prediction)?;
+    let input = input.trim();
+
+    let known = |name: &str| skulls.iter().any(|s| s.name == name);
+    let (skull, amount) = match input.rsplit_once(' ') {
+        Some((skull, amount)) if !known(input) && known(skull.trim_end()) => {
+            (skull.trim_end(), Some(String::from(amount)))
+        }
+        _ => (input, None),
+    };
+
+    into_skull(skulls, skull).map(|skull| (skull, amount))
+}
+
+fn get_skull(skulls: &[types::Skull], prediction: Option<&types::Prediction>) -> Result<String> {
     use rucline::{crossterm::style::Colorize, prompt::Builder};
 
-    let completions = Completions::new(skulls.iter().map(|s| s.name.clone()).collect());
+    let completions = Completions::new(
+        skulls
+            .iter()
+            .filter(|s| !s.archived)
+            .map(|s| s.name.clone())
+            .collect(),
+    );
+
+    let prediction = prediction
+        .and_then(|p| {
+            skulls
+                .iter()
+                .find(|s| s.id == p.skull && !s.archived)
+                .map(|s| format!("{} {}", s.name, p.amount))
+        })
+        .unwrap_or_default();
 
     rucline::prompt::Prompt::from("> ".white())
+        .buffer(rucline::Buffer::from(prediction.as_str()))
         .completer_ref(&completions)
         .suggester_ref(&completions)
         .erase_after_read(true)
@@ -135,7 +215,7 @@ fn get_amount(skull: &types::Skull, quicks: &[types::Quick]) -> Result<String> {
         .ok_or(Error::Canceled)
 }
 
-fn into_amount<A>(amount: A) -> Result<f32>
+pub(super) fn into_amount<A>(amount: A) -> Result<f32>
 where
     A: AsRef<str>,
 {
@@ -160,3 +240,22 @@ fn get_time(skull: &str, amount: f32) -> Result<String> {
         .filter(|input| !input.is_empty())
         .ok_or(Error::Canceled)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{split_items, split_note};
+
+    #[test]
+    fn split_on_every_comma() {
+        let items = split_items("bla 1 now #milk, Ble 1 now");
+        assert_eq!(items, ["bla 1 now #milk", " Ble 1 now"]);
+        assert_eq!(split_note(&items[0]), ("bla 1 now ", Some("milk")));
+    }
+
+    #[test]
+    fn split_keeps_escaped_commas() {
+        let items = split_items(r"bla 1 now #milk\, sugar, ble 1 now");
+        assert_eq!(items, ["bla 1 now #milk, sugar", " ble 1 now"]);
+        assert_eq!(split_note(&items[0]), ("bla 1 now ", Some("milk, sugar")));
+    }
+}
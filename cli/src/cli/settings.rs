@@ -0,0 +1,78 @@
+use super::{Error, Result};
+
+/// Parses the setting to change, or `None` if the settings are only to be shown
+pub fn input<Args>(mut args: Args) -> Result<Option<types::request::settings::Set>>
+where
+    Args: Iterator<Item = String>,
+{
+    let Some(key) = args.next() else {
+        return Ok(None);
+    };
+    let value = args.next().ok_or(Error::MissingValue("setting"))?;
+
+    if args.next().is_some() {
+        return Err(Error::TooManyArgs);
+    }
+
+    let mut set = types::request::settings::Set {
+        timezone: None,
+        day_start: None,
+        window: None,
+        quick_count: None,
+        currency: None,
+    };
+
+    match key.as_str() {
+        "timezone" => set.timezone = Some(parse_offset(&value)?.into()),
+        "day-start" => set.day_start = Some(parse_number(&value)?.into()),
+        "window" => set.window = Some(parse_number(&value)?.into()),
+        "quick-count" => set.quick_count = Some(parse_number(&value)?.into()),
+        // An empty value goes back to not showing any currency
+        "currency" => set.currency = Some(Some(value).filter(|v| !v.is_empty()).into()),
+        _ => return Err(Error::UnknownSetting(key)),
+    }
+
+    Ok(Some(set))
+}
+
+pub fn output(settings: &types::Settings) {
+    use rucline::crossterm::style::Colorize;
+
+    let offset = settings.timezone.unsigned_abs();
+    let timezone = format!(
+        "{sign}{hours:02}:{minutes:02}",
+        sign = if settings.timezone < 0 { '-' } else { '+' },
+        hours = offset / 60,
+        minutes = offset % 60,
+    );
+
+    for (key, value) in [
+        ("timezone", timezone),
+        ("day-start", format!("{:02}:00", settings.day_start)),
+        ("window", format!("{} days", settings.window)),
+        ("quick-count", settings.quick_count.to_string()),
+        (
+            "currency",
+            settings
+                .currency
+                .clone()
+                .unwrap_or_else(|| String::from("-")),
+        ),
+    ] {
+        println!("{key:<12} {value}", key = key.white());
+    }
+}
+
+/// Parses an offset from UTC in the `+HH:MM` format into minutes
+fn parse_offset(value: &str) -> Result<i32> {
+    value
+        .parse::<chrono::FixedOffset>()
+        .map(|offset| offset.local_minus_utc() / 60)
+        .map_err(|_| Error::InvalidSettingValue(String::from(value)))
+}
+
+fn parse_number(value: &str) -> Result<u32> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidSettingValue(String::from(value)))
+}
@@ -0,0 +1,69 @@
+use super::{Error, Result, into_range, into_rgb};
+
+pub fn input<Args>(mut args: Args) -> Result<types::request::occurrence::Spending>
+where
+    Args: Iterator<Item = String>,
+{
+    let range = args.next();
+
+    if args.next().is_some() {
+        return Err(Error::TooManyArgs);
+    }
+
+    let (start, end) = match range {
+        Some(range) => into_range(range)?,
+        None => (None, None),
+    };
+
+    Ok(types::request::occurrence::Spending {
+        skulls: None,
+        start,
+        end,
+    })
+}
+
+pub fn output(skulls: &[types::Skull], spending: &[types::Spending], currency: Option<&str>) {
+    use rucline::crossterm::style::Colorize;
+
+    const TOTAL: &str = "Total";
+
+    let skulls = skulls
+        .iter()
+        .map(|s| (s.id, s))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let skull_len = spending
+        .iter()
+        .filter_map(|s| skulls.get(&s.skull))
+        .map(|s| s.name.chars().count())
+        .chain(std::iter::once(TOTAL.len()))
+        .max()
+        .unwrap_or(0);
+
+    let currency = currency.map(|c| format!(" {c}")).unwrap_or_default();
+    let mut total = 0.0;
+
+    for entry in spending {
+        let Some(skull) = skulls.get(&entry.skull) else {
+            continue;
+        };
+
+        let color = into_rgb(skull.color);
+        let bullet = rucline::crossterm::style::style('●').with(color.into());
+
+        println!(
+            "{bullet} {skull:<skull_len$} {amount:<8} {cost:>10.2}{currency}",
+            skull = skull.name.as_str().white(),
+            amount = entry.amount,
+            cost = entry.cost,
+        );
+        total += entry.cost;
+    }
+
+    println!();
+    println!(
+        "  {total_label:<skull_len$} {empty:<8} {total:>10.2}{currency}",
+        total_label = TOTAL.white(),
+        empty = "",
+    );
+}
@@ -0,0 +1,170 @@
+use super::{Error, Result, into_rgb, plot};
+
+const WIDTH: f64 = 960.;
+const HEIGHT: f64 = 540.;
+const LEFT: f64 = 70.;
+const RIGHT: f64 = 180.;
+const TOP: f64 = 30.;
+const BOTTOM: f64 = 50.;
+const TICKS: u32 = 5;
+
+pub(super) enum Format {
+    Svg,
+    Png,
+}
+
+impl Format {
+    pub(super) fn from_path(path: &std::path::Path) -> Result<Self> {
+        match path.extension().and_then(std::ffi::OsStr::to_str) {
+            Some(ext) if ext.eq_ignore_ascii_case("svg") => Ok(Self::Svg),
+            Some(ext) if ext.eq_ignore_ascii_case("png") => Ok(Self::Png),
+            _ => Err(Error::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+pub(super) fn export(series: &plot::Series<'_>, path: &std::path::Path) -> Result<()> {
+    let format = Format::from_path(path)?;
+    let svg = render(series);
+
+    let bytes = match format {
+        Format::Svg => svg.into_bytes(),
+        Format::Png => rasterize(&svg)?,
+    };
+
+    std::fs::write(path, bytes).map_err(Error::Export)
+}
+
+fn rasterize(svg: &str) -> Result<Vec<u8>> {
+    let mut options = resvg::usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = resvg::usvg::Tree::from_str(svg, &options).map_err(Error::Svg)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or(Error::Rasterize(String::from("invalid image size")))?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+
+    pixmap
+        .encode_png()
+        .map_err(|err| Error::Rasterize(err.to_string()))
+}
+
+fn render(series: &plot::Series<'_>) -> String {
+    let x_bounds = bounds(series.x);
+    let y_bounds = bounds([series.y[0].min(0.), series.y[1]]);
+
+    let plot_width = WIDTH - LEFT - RIGHT;
+    let plot_height = HEIGHT - TOP - BOTTOM;
+    let to_x =
+        |millis: f64| LEFT + (millis - x_bounds[0]) / (x_bounds[1] - x_bounds[0]) * plot_width;
+    let to_y = |amount: f64| {
+        TOP + plot_height - (amount - y_bounds[0]) / (y_bounds[1] - y_bounds[0]) * plot_height
+    };
+
+    let mut elements = vec![format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="12">"#
+    )];
+    elements.push(String::from(
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##,
+    ));
+
+    // Grid and tick labels
+    for i in 0..=TICKS {
+        let ratio = f64::from(i) / f64::from(TICKS);
+
+        let amount = y_bounds[0] + (y_bounds[1] - y_bounds[0]) * ratio;
+        let position = to_y(amount);
+        elements.push(format!(
+            r##"<line x1="{LEFT}" y1="{position:.1}" x2="{right:.1}" y2="{position:.1}" stroke="#e0e0e0"/><text x="{label:.1}" y="{position:.1}" text-anchor="end" dominant-baseline="middle">{amount}</text>"##,
+            right = WIDTH - RIGHT,
+            label = LEFT - 8.,
+            amount = format_amount(amount),
+        ));
+
+        let millis = x_bounds[0] + (x_bounds[1] - x_bounds[0]) * ratio;
+        let position = to_x(millis);
+        elements.push(format!(
+            r##"<line x1="{position:.1}" y1="{TOP}" x2="{position:.1}" y2="{bottom:.1}" stroke="#e0e0e0"/><text x="{position:.1}" y="{label:.1}" text-anchor="middle">{date}</text>"##,
+            bottom = HEIGHT - BOTTOM,
+            label = HEIGHT - BOTTOM + 18.,
+            date = format_millis(millis),
+        ));
+    }
+
+    // Axes
+    elements.push(format!(
+        r##"<polyline points="{LEFT},{TOP} {LEFT},{bottom} {right},{bottom}" fill="none" stroke="#000000"/><text x="{center_x:.1}" y="{date_y:.1}" text-anchor="middle">Date</text><text x="16" y="{center_y:.1}" text-anchor="middle" transform="rotate(-90 16 {center_y:.1})">Sum</text>"##,
+        bottom = HEIGHT - BOTTOM,
+        right = WIDTH - RIGHT,
+        center_x = LEFT + plot_width / 2.,
+        date_y = HEIGHT - 10.,
+        center_y = TOP + plot_height / 2.,
+    ));
+
+    // Lines and legend
+    for (index, (skull, data)) in series.data.iter().enumerate() {
+        let color = into_rgb(skull.color);
+        let color = format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2);
+
+        let points = data
+            .iter()
+            .map(|(millis, amount)| format!("{:.1},{:.1}", to_x(*millis), to_y(*amount)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        elements.push(format!(
+            r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="2"/>"#
+        ));
+
+        // Allow(clippy::cast_precision_loss): There are never that many skulls
+        #[allow(clippy::cast_precision_loss)]
+        let legend_y = TOP + 20. * index as f64;
+        elements.push(format!(
+            r#"<rect x="{x:.1}" y="{legend_y:.1}" width="12" height="12" fill="{color}"/><text x="{text_x:.1}" y="{text_y:.1}">{name}</text>"#,
+            x = WIDTH - RIGHT + 20.,
+            text_x = WIDTH - RIGHT + 38.,
+            text_y = legend_y + 10.,
+            name = escape(&skull.name),
+        ));
+    }
+
+    elements.push(String::from("</svg>"));
+    elements.concat()
+}
+
+/// Widens empty or single-valued bounds, so that the points can be scaled into them
+fn bounds([min, max]: [f64; 2]) -> [f64; 2] {
+    if min > max {
+        [0., 1.]
+    } else if (max - min).abs() < f64::EPSILON {
+        [min - 1., max + 1.]
+    } else {
+        [min, max]
+    }
+}
+
+fn format_amount(amount: f64) -> String {
+    let formatted = format!("{amount:.2}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    String::from(formatted)
+}
+
+fn format_millis(millis: f64) -> String {
+    // Allow(clippy::cast_possible_truncation): The bounds came from `i64` millis
+    #[allow(clippy::cast_possible_truncation)]
+    let millis = types::Millis::from(millis as i64);
+    chrono::DateTime::<chrono::Local>::from(millis)
+        .format("%d-%b %H:%M")
+        .to_string()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,498 @@
+use super::{Canvas, Error, Result, into_millis, into_rgb, plot, register};
+
+/// The sliding window of the chart pane
+const WINDOW: i64 = 24 * 60 * 60 * 1000;
+const STEP: usize = 60 * 60 * 1000;
+/// How far back the chart pane goes
+const SPAN: i64 = 7 * 24 * 60 * 60 * 1000;
+
+pub struct Data {
+    pub skulls: Vec<types::Skull>,
+    pub quicks: Vec<types::Quick>,
+    pub occurrences: Vec<types::Occurrence>,
+    pub settings: types::Settings,
+}
+
+pub enum Action {
+    Register(types::request::occurrence::Item),
+    Update(types::request::occurrence::Update),
+    Delete(types::OccurrenceId),
+    Refresh,
+    Quit,
+}
+
+enum Mode {
+    Browse,
+    Skull {
+        input: String,
+    },
+    Amount {
+        skull: types::SkullId,
+        name: String,
+        input: String,
+    },
+    Edit {
+        id: types::OccurrenceId,
+        name: String,
+        input: String,
+    },
+    Delete {
+        id: types::OccurrenceId,
+        name: String,
+    },
+}
+
+/// Searches far enough back for the chart and for the consumption of every limited skull
+pub fn search(
+    skulls: &[types::Skull],
+    settings: &types::Settings,
+) -> types::request::occurrence::Search {
+    let now = settings.now();
+    let chart_start = types::Millis::from(now.timestamp_millis() - SPAN - WINDOW);
+
+    let start = skulls
+        .iter()
+        .filter(|s| s.limit.is_some())
+        .map(|s| types::Millis::from(settings.start(s.period, &now)))
+        .fold(chart_start, std::cmp::min);
+
+    types::request::occurrence::Search {
+        skulls: None,
+        tags: None,
+        start: Some(start),
+        end: None,
+        limit: None,
+        cursor: None,
+    }
+}
+
+pub struct Dashboard {
+    canvas: Canvas,
+    table: ratatui::widgets::TableState,
+    mode: Mode,
+    status: Option<String>,
+}
+
+impl Dashboard {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            canvas: Canvas::new()?,
+            table: ratatui::widgets::TableState::new().with_selected(0),
+            mode: Mode::Browse,
+            status: None,
+        })
+    }
+
+    /// Shows a message in the bottom line until the next key press
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.status = Some(message.into());
+    }
+
+    /// Draws the dashboard and handles key presses until the user asks for an action
+    pub fn next(&mut self, data: &Data) -> Result<Action> {
+        // Anything printed while the action was running is painted over
+        self.canvas.terminal.clear().map_err(Error::Ratatui)?;
+
+        let completions = register::Completions::new(
+            data.skulls
+                .iter()
+                .filter(|s| !s.archived)
+                .map(|s| s.name.clone())
+                .collect(),
+        );
+        let chart_start = chrono::Utc::now().timestamp_millis() - SPAN;
+        let chart = plot::aggregate(
+            &data
+                .occurrences
+                .iter()
+                .filter(|o| i64::from(o.millis) >= chart_start)
+                .cloned()
+                .collect::<Vec<_>>(),
+            plot::SlidingWindowProto {
+                window: WINDOW,
+                step: STEP,
+            },
+        );
+        let chart = plot::Series::new(&data.skulls, chart);
+
+        loop {
+            if let Some(selected) = self.table.selected()
+                && selected >= data.occurrences.len()
+            {
+                self.table
+                    .select(Some(data.occurrences.len().saturating_sub(1)));
+            }
+
+            let line = self.prompt(&completions);
+            self.canvas
+                .terminal
+                .draw(|f| {
+                    let [top, chart_area, prompt] = ratatui::layout::Layout::vertical([
+                        ratatui::layout::Constraint::Min(0),
+                        ratatui::layout::Constraint::Percentage(40),
+                        ratatui::layout::Constraint::Length(1),
+                    ])
+                    .areas(f.area());
+                    let [recent, limits] = ratatui::layout::Layout::horizontal([
+                        ratatui::layout::Constraint::Percentage(60),
+                        ratatui::layout::Constraint::Percentage(40),
+                    ])
+                    .areas(top);
+
+                    f.render_stateful_widget(recent_table(data), recent, &mut self.table);
+                    render_limits(f, limits, data);
+                    f.render_widget(
+                        chart
+                            .chart()
+                            .block(ratatui::widgets::Block::bordered().title(format!(
+                                " Sliding window of {}h over {}h ",
+                                WINDOW / 3_600_000,
+                                STEP / 3_600_000
+                            ))),
+                        chart_area,
+                    );
+                    f.render_widget(ratatui::widgets::Paragraph::new(line), prompt);
+                })
+                .map_err(Error::Ratatui)?;
+
+            if !rucline::crossterm::event::poll(std::time::Duration::from_secs(10))
+                .map_err(Error::Terminal)?
+            {
+                continue;
+            }
+
+            if let rucline::crossterm::event::Event::Key(key) =
+                rucline::crossterm::event::read().map_err(Error::Terminal)?
+            {
+                self.status = None;
+                if let Some(action) = self.handle(key, data, &completions) {
+                    return Ok(action);
+                }
+            }
+        }
+    }
+
+    fn prompt(&self, completions: &register::Completions) -> ratatui::text::Line<'static> {
+        if let Some(status) = &self.status {
+            return ratatui::text::Line::from(status.clone())
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::Yellow));
+        }
+
+        match &self.mode {
+            Mode::Browse => ratatui::text::Line::from("[r]egister [e]dit [d]elete [u]pdate [q]uit")
+                .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray)),
+            Mode::Skull { input } => {
+                let suggestions = completions
+                    .matches(input)
+                    .into_iter()
+                    .rev()
+                    .take(5)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                ratatui::text::Line::from(vec![
+                    ratatui::text::Span::raw(format!("> {input}")),
+                    ratatui::text::Span::styled(
+                        format!("  {suggestions}"),
+                        ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
+                    ),
+                ])
+            }
+            Mode::Amount { name, input, .. } => {
+                ratatui::text::Line::from(format!("{name}> {input}"))
+            }
+            Mode::Edit { name, input, .. } => {
+                ratatui::text::Line::from(format!("Edit {name}> {input}"))
+            }
+            Mode::Delete { name, .. } => ratatui::text::Line::from(format!("Delete {name}? [y/N]")),
+        }
+    }
+
+    fn handle(
+        &mut self,
+        key: rucline::crossterm::event::KeyEvent,
+        data: &Data,
+        completions: &register::Completions,
+    ) -> Option<Action> {
+        use rucline::crossterm::event::{KeyCode, KeyModifiers};
+
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Some(Action::Quit);
+        }
+
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Browse => self.browse(key.code, data),
+            Mode::Delete { id, .. } => {
+                (key.code == KeyCode::Char('y')).then_some(Action::Delete(id))
+            }
+            _ if key.code == KeyCode::Esc => None,
+            Mode::Skull { input } => {
+                self.select_skull(key.code, input, data, completions);
+                None
+            }
+            Mode::Amount {
+                skull,
+                name,
+                mut input,
+            } => {
+                if !edit(key.code, &mut input) {
+                    self.mode = Mode::Amount { skull, name, input };
+                    return None;
+                }
+
+                match parse_amount(&input) {
+                    Ok((amount, millis)) => {
+                        Some(Action::Register(types::request::occurrence::Item {
+                            skull,
+                            amount,
+                            millis: millis.unwrap_or_else(|| chrono::Utc::now().into()),
+                            note: None,
+                        }))
+                    }
+                    Err(err) => {
+                        self.notify(err.to_string());
+                        self.mode = Mode::Amount { skull, name, input };
+                        None
+                    }
+                }
+            }
+            Mode::Edit {
+                id,
+                name,
+                mut input,
+            } => {
+                if !edit(key.code, &mut input) {
+                    self.mode = Mode::Edit { id, name, input };
+                    return None;
+                }
+
+                match parse_amount(&input) {
+                    Ok((amount, millis)) => {
+                        Some(Action::Update(types::request::occurrence::Update {
+                            id,
+                            skull: None,
+                            amount: Some(amount.into()),
+                            millis: millis.map(Into::into),
+                            note: None,
+                        }))
+                    }
+                    Err(err) => {
+                        self.notify(err.to_string());
+                        self.mode = Mode::Edit { id, name, input };
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn browse(&mut self, code: rucline::crossterm::event::KeyCode, data: &Data) -> Option<Action> {
+        use rucline::crossterm::event::KeyCode;
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Char('u') => return Some(Action::Refresh),
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Char('r') => {
+                self.mode = Mode::Skull {
+                    input: String::new(),
+                };
+            }
+            KeyCode::Char('e') => {
+                if let Some((occurrence, skull)) = self.selected(data) {
+                    self.mode = Mode::Edit {
+                        id: occurrence.id,
+                        name: skull.name.clone(),
+                        input: occurrence.amount.to_string(),
+                    };
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some((occurrence, skull)) = self.selected(data) {
+                    self.mode = Mode::Delete {
+                        id: occurrence.id,
+                        name: skull.name.clone(),
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn select_skull(
+        &mut self,
+        code: rucline::crossterm::event::KeyCode,
+        mut input: String,
+        data: &Data,
+        completions: &register::Completions,
+    ) {
+        if code == rucline::crossterm::event::KeyCode::Tab {
+            let input = completions
+                .matches(&input)
+                .last()
+                .map_or(input, |&name| String::from(name));
+            self.mode = Mode::Skull { input };
+            return;
+        }
+
+        if !edit(code, &mut input) {
+            self.mode = Mode::Skull { input };
+            return;
+        }
+
+        let name = if data.skulls.iter().any(|s| s.name == input) {
+            Some(input.as_str())
+        } else {
+            completions.matches(&input).last().copied()
+        };
+
+        let Some(skull) = name.and_then(|name| data.skulls.iter().find(|s| s.name == name)) else {
+            self.notify(Error::UnknownSkull(input.clone()).to_string());
+            self.mode = Mode::Skull { input };
+            return;
+        };
+
+        let input = data
+            .quicks
+            .iter()
+            .find(|q| q.skull == skull.id)
+            .map_or_else(|| String::from("1"), |q| q.amount.to_string());
+        self.mode = Mode::Amount {
+            skull: skull.id,
+            name: skull.name.clone(),
+            input,
+        };
+    }
+
+    fn selected<'a>(&self, data: &'a Data) -> Option<(&'a types::Occurrence, &'a types::Skull)> {
+        let occurrence = data.occurrences.get(self.table.selected()?)?;
+        let skull = data.skulls.iter().find(|s| s.id == occurrence.skull)?;
+        Some((occurrence, skull))
+    }
+}
+
+/// Applies a key press to a text input, returning whether the input was submitted
+fn edit(code: rucline::crossterm::event::KeyCode, input: &mut String) -> bool {
+    use rucline::crossterm::event::KeyCode;
+
+    match code {
+        KeyCode::Enter => return true,
+        KeyCode::Char(c) => input.push(c),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        _ => {}
+    }
+
+    false
+}
+
+/// Parses `amount [time]`, where time takes the same format as when registering
+fn parse_amount(input: &str) -> Result<(f32, Option<types::Millis>)> {
+    let mut args = input.split_whitespace();
+    let amount = register::into_amount(args.next().unwrap_or_default())?;
+    let millis = args.next().map(into_millis).transpose()?;
+
+    if args.next().is_some() {
+        return Err(Error::TooManyArgs);
+    }
+
+    Ok((amount, millis))
+}
+
+fn skull_color(skull: &types::Skull) -> ratatui::style::Color {
+    let (r, g, b) = into_rgb(skull.color);
+    ratatui::style::Color::Rgb(r, g, b)
+}
+
+fn recent_table(data: &Data) -> ratatui::widgets::Table<'_> {
+    let rows = data.occurrences.iter().map(|occurrence| {
+        let skull = data.skulls.iter().find(|s| s.id == occurrence.skull);
+        let timestamp = chrono::DateTime::<chrono::Utc>::from(occurrence.millis)
+            .with_timezone(&data.settings.offset());
+
+        ratatui::widgets::Row::new([
+            ratatui::text::Line::from(ratatui::text::Span::styled(
+                "●",
+                ratatui::style::Style::default()
+                    .fg(skull.map_or(ratatui::style::Color::Reset, skull_color)),
+            )),
+            ratatui::text::Line::from(skull.map_or("??", |s| s.name.as_str())),
+            ratatui::text::Line::from(occurrence.amount.to_string()),
+            ratatui::text::Line::from(timestamp.format("%d-%b %H:%M").to_string()),
+            ratatui::text::Line::from(occurrence.note.as_deref().unwrap_or_default()),
+        ])
+    });
+
+    ratatui::widgets::Table::new(
+        rows,
+        [
+            ratatui::layout::Constraint::Length(1),
+            ratatui::layout::Constraint::Max(20),
+            ratatui::layout::Constraint::Length(8),
+            ratatui::layout::Constraint::Length(12),
+            ratatui::layout::Constraint::Min(0),
+        ],
+    )
+    .block(ratatui::widgets::Block::bordered().title(" Occurrences "))
+    .row_highlight_style(
+        ratatui::style::Style::default().add_modifier(ratatui::style::Modifier::REVERSED),
+    )
+}
+
+fn render_limits(f: &mut ratatui::Frame<'_>, area: ratatui::layout::Rect, data: &Data) {
+    let block = ratatui::widgets::Block::bordered().title(" Limits ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let now = data.settings.now();
+    let limited = data
+        .skulls
+        .iter()
+        .filter(|s| !s.archived)
+        .filter_map(|skull| {
+            let limit = skull.limit?;
+            let start = types::Millis::from(data.settings.start(skull.period, &now));
+            let consumed = data
+                .occurrences
+                .iter()
+                .filter(|o| o.skull == skull.id && o.millis >= start)
+                .map(|o| o.amount)
+                .sum::<f32>();
+            Some((skull, consumed, limit))
+        })
+        .collect::<Vec<_>>();
+
+    let rows = ratatui::layout::Layout::vertical(
+        limited
+            .iter()
+            .map(|_| ratatui::layout::Constraint::Length(1)),
+    )
+    .split(inner);
+
+    for ((skull, consumed, limit), row) in limited.iter().zip(rows.iter()) {
+        let color = if consumed > limit {
+            ratatui::style::Color::Red
+        } else if *consumed > limit - 1. {
+            ratatui::style::Color::Yellow
+        } else {
+            skull_color(skull)
+        };
+
+        let ratio = if *limit > 0. {
+            f64::from(consumed / limit).clamp(0., 1.)
+        } else {
+            1.
+        };
+
+        f.render_widget(
+            ratatui::widgets::LineGauge::default()
+                .label(format!("{} {consumed}/{limit}", skull.name))
+                .ratio(ratio)
+                .filled_style(ratatui::style::Style::default().fg(color)),
+            *row,
+        );
+    }
+}
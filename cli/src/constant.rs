@@ -7,6 +7,7 @@ pub const ENV_USER: &str = "SKULL_USER";
 pub const ENV_PASSWORD: &str = "SKULL_PASSWORD";
 pub const ENV_HOST: &str = "SKULL_HOST";
 pub const ENDGAME_COOKIE: &str = "endgame";
+pub const RETRIES: usize = 3;
 
 pub mod path {
     pub fn cache() -> Option<&'static std::path::PathBuf> {
@@ -15,6 +16,12 @@ pub mod path {
             .as_ref()
     }
 
+    pub fn queue() -> Option<&'static std::path::PathBuf> {
+        static PATH: std::sync::OnceLock<Option<std::path::PathBuf>> = std::sync::OnceLock::new();
+        PATH.get_or_init(|| cache().map(|p| p.join("queue")))
+            .as_ref()
+    }
+
     pub fn host() -> Option<&'static std::path::PathBuf> {
         static PATH: std::sync::OnceLock<Option<std::path::PathBuf>> = std::sync::OnceLock::new();
         PATH.get_or_init(|| dirs::data_local_dir().map(|p| p.join(super::APP_NAME).join("host")))
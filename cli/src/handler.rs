@@ -1,7 +1,10 @@
-use crate::{cli, request};
+use crate::{cli, request, socket};
 
 type Result<T = ()> = std::result::Result<T, Error>;
 
+/// How long to wait for further pushes before rendering the watched list
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(200);
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
@@ -38,38 +41,195 @@ impl Handler {
     }
 
     pub async fn list(&self) -> Result {
-        use chrono::Timelike;
-
-        let start = types::Millis::from(
-            chrono::Utc::now()
-                .with_hour(5)
-                .unwrap()
-                .with_minute(0)
-                .unwrap()
-                .with_second(0)
-                .unwrap()
-                .with_nanosecond(0)
-                .unwrap()
-                - chrono::Duration::days(1),
+        let (skulls, occurrences, settings) = self.fetch_list().await?;
+        cli::list::output(skulls, &occurrences, &settings);
+        Ok(())
+    }
+
+    pub async fn watch(&self) -> Result {
+        let mut socket = self.request.socket()?;
+        let mut list = None;
+        let mut stale = true;
+        let mut refresh_cache = false;
+        let mut status = String::from("Connecting...");
+        let mut notice = None;
+
+        loop {
+            if refresh_cache || stale {
+                match self.refresh(refresh_cache).await {
+                    Ok(fresh) => {
+                        list = Some(fresh);
+                        stale = false;
+                        refresh_cache = false;
+                    }
+                    // Retried once the socket reconnects
+                    Err(Error::Request(request::Error::ReqwestSend(err))) => {
+                        status = format!("Failed to fetch the occurrences: {err}");
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+
+            // Clear the screen and move the cursor to the top
+            print!("\x1b[2J\x1b[H");
+            if let Some((skulls, occurrences, settings)) = &list {
+                cli::list::output(skulls.clone(), occurrences, settings);
+                println!();
+                if let Some(notice) = notice
+                    .take()
+                    .and_then(|(reached, consumption)| limit_notice(skulls, reached, &consumption))
+                {
+                    println!("{notice}");
+                }
+            }
+            println!("{status}");
+
+            // Bursts of events are gathered into a single render
+            let mut event = socket.next().await;
+            loop {
+                match event {
+                    socket::Event::Connected => {
+                        // Pushes may have been missed while disconnected
+                        status = String::from("Watching for changes");
+                        refresh_cache = true;
+                    }
+                    socket::Event::Disconnected(socket::Error::Unauthorized) => {
+                        return Err(self.request.reject().into());
+                    }
+                    socket::Event::Disconnected(err) => {
+                        status = format!("{err}. Reconnecting...");
+                    }
+                    socket::Event::Push(push) => match push {
+                        types::Push::LimitReached(consumption) => {
+                            notice = Some((true, consumption));
+                        }
+                        types::Push::LimitExceeded(consumption) => {
+                            notice = Some((false, consumption));
+                        }
+                        types::Push::SkullCreated(_)
+                        | types::Push::SkullUpdated(_)
+                        | types::Push::SkullDeleted(_)
+                        | types::Push::SkullArchived(_)
+                        | types::Push::SkullUnarchived(_)
+                        | types::Push::SkullMerged(_)
+                        | types::Push::TagCreated(_)
+                        | types::Push::TagUpdated(_)
+                        | types::Push::TagDeleted(_)
+                        | types::Push::SettingsChanged(_) => refresh_cache = true,
+                        types::Push::OccurrencesCreated(_)
+                        | types::Push::OccurrenceUpdated(_)
+                        | types::Push::OccurrencesUpdated(_)
+                        | types::Push::OccurrenceDeleted(_)
+                        | types::Push::OccurrencesDeleted(_) => stale = true,
+                    },
+                }
+
+                match tokio::time::timeout(WATCH_DEBOUNCE, socket.next()).await {
+                    Ok(next) => event = next,
+                    Err(_) => break,
+                }
+            }
+        }
+    }
+
+    async fn refresh(
+        &self,
+        refresh_cache: bool,
+    ) -> Result<(Vec<types::Skull>, Vec<types::Occurrence>, types::Settings)> {
+        if refresh_cache {
+            self.request.update().await?;
+        }
+        self.fetch_list().await
+    }
+
+    async fn fetch_list(
+        &self,
+    ) -> Result<(Vec<types::Skull>, Vec<types::Occurrence>, types::Settings)> {
+        let (skulls, settings) = tokio::join!(
+            self.request.get_cacheable::<types::Skull>(),
+            self.request.get_settings()
         );
+        let skulls = skulls?;
+        let settings = settings?;
+
+        // Fetch far enough back to account for the consumption of every limited skull
+        let now = settings.now();
+        let start = skulls
+            .iter()
+            .filter(|s| s.limit.is_some())
+            .map(|s| types::Millis::from(settings.start(s.period, &now)))
+            .fold(cli::list::start(&settings), std::cmp::min);
 
         let search = types::request::occurrence::Search {
             skulls: None,
+            tags: None,
             start: Some(start),
             end: None,
             limit: None,
+            cursor: None,
         };
 
-        let (skulls, occurrences) = tokio::join!(
+        let occurrences = self.request.get_occurrences(search).await?;
+
+        Ok((skulls, occurrences, settings))
+    }
+
+    pub async fn tui(&self) -> Result {
+        let mut dashboard = cli::tui::Dashboard::new()?;
+        let mut data = self.fetch_dashboard().await?;
+
+        loop {
+            let result = match dashboard.next(&data)? {
+                cli::tui::Action::Quit => return Ok(()),
+                cli::tui::Action::Refresh => self.request.update().await.map(|()| "Updated"),
+                cli::tui::Action::Register(item) => self
+                    .request
+                    .post_occurrences(vec![item])
+                    .await
+                    .map(|()| "Registered"),
+                cli::tui::Action::Update(update) => self
+                    .request
+                    .patch_occurrence(update)
+                    .await
+                    .map(|()| "Edited"),
+                cli::tui::Action::Delete(id) => {
+                    self.request.delete_occurrence(id).await.map(|()| "Deleted")
+                }
+            };
+
+            // Failures are shown in the dashboard instead of closing it
+            match result {
+                Ok(message) => dashboard.notify(message),
+                Err(err) => dashboard.notify(err.to_string()),
+            }
+
+            match self.fetch_dashboard().await {
+                Ok(fresh) => data = fresh,
+                Err(err) => dashboard.notify(err.to_string()),
+            }
+        }
+    }
+
+    async fn fetch_dashboard(&self) -> Result<cli::tui::Data> {
+        let (skulls, quicks, settings) = tokio::join!(
             self.request.get_cacheable(),
-            self.request.get_occurrences(search)
+            self.request.get_cacheable(),
+            self.request.get_settings()
         );
         let skulls = skulls?;
-        let occurrences = occurrences?;
-
-        cli::list::output(skulls, &occurrences);
+        let quicks = quicks?;
+        let settings = settings?;
+        let occurrences = self
+            .request
+            .get_occurrences(cli::tui::search(&skulls, &settings))
+            .await?;
 
-        Ok(())
+        Ok(cli::tui::Data {
+            skulls,
+            quicks,
+            occurrences,
+            settings,
+        })
     }
 
     pub async fn update(&self) -> Result {
@@ -80,12 +240,26 @@ impl Handler {
     where
         Args: Iterator<Item = String>,
     {
+        let args = args.collect::<Vec<_>>();
         let (skulls, quicks) =
-            tokio::join!(self.request.get_cacheable(), self.request.get_cacheable());
+            tokio::join!(self.request.get_cacheable(), self.request.get_cacheable(),);
         let skulls = skulls?;
         let quicks = quicks?;
 
-        let occurrences = cli::register::input(args, &skulls, &quicks)?;
+        // The prediction only prefills the skull prompt, so registering still works when it is
+        // unreachable
+        let prediction = if cli::register::prompts(&args) {
+            self.request
+                .get_prediction(types::Millis::from(chrono::Utc::now()))
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+
+        let occurrences =
+            cli::register::input(args.into_iter(), &skulls, &quicks, prediction.as_ref())?;
 
         self.request
             .post_occurrences(occurrences)
@@ -106,14 +280,106 @@ impl Handler {
         Ok(())
     }
 
+    pub async fn spend<I: Iterator<Item = String>>(&self, args: I) -> Result {
+        let spending = cli::spend::input(args)?;
+        let (skulls, settings, spending) = tokio::join!(
+            self.request.get_cacheable(),
+            self.request.get_settings(),
+            self.request.get_spending(spending)
+        );
+        let skulls = skulls?;
+        let settings = settings?;
+        let spending = spending?;
+
+        cli::spend::output(&skulls, &spending, settings.currency.as_deref());
+
+        Ok(())
+    }
+
+    pub async fn settings<I: Iterator<Item = String>>(&self, args: I) -> Result {
+        if let Some(set) = cli::settings::input(args)? {
+            self.request.patch_settings(set).await?;
+            self.request.update().await?;
+        }
+
+        let settings = self.request.get_settings().await?;
+        cli::settings::output(&settings);
+
+        Ok(())
+    }
+
+    pub async fn sync(&self) -> Result {
+        match self.request.sync().await? {
+            0 => println!("Nothing to sync"),
+            1 => println!("Synced 1 queued registration"),
+            synced => println!("Synced {synced} queued registrations"),
+        }
+        Ok(())
+    }
+
+    /// Sends the queued registrations once the server is known to be reachable again
+    pub async fn flush(&self) {
+        if !self.request.reached() {
+            return;
+        }
+
+        match self.request.sync().await {
+            Ok(0) => {}
+            Ok(1) => eprintln!("Synced 1 queued registration"),
+            Ok(synced) => eprintln!("Synced {synced} queued registrations"),
+            Err(err) => eprintln!("Failed to sync the queued registrations: {err}"),
+        }
+    }
+
+    pub async fn undo(&self) -> Result {
+        self.request.post_journal("undo").await?;
+        self.request.update().await.map_err(Into::into)
+    }
+
+    pub async fn redo(&self) -> Result {
+        self.request.post_journal("redo").await?;
+        self.request.update().await.map_err(Into::into)
+    }
+
     pub async fn plot<I: Iterator<Item = String>>(&self, args: I) -> Result {
-        let skulls = self.request.get_cacheable().await?;
-        let (search, proto) = cli::plot::input(args, &skulls)?;
+        let (skulls, tags, settings) = tokio::join!(
+            self.request.get_cacheable(),
+            self.request.get_cacheable(),
+            self.request.get_settings()
+        );
+        let skulls = skulls?;
+        let tags = tags?;
+        let settings = settings?;
+        let (search, mode, path) = cli::plot::input(args, &skulls, &tags)?;
         let occurrences = self.request.get_occurrences(search).await?;
-        cli::plot::output(&skulls, &occurrences, proto).map_err(Into::into)
+        cli::plot::output(&skulls, &occurrences, &settings, mode, path.as_deref())
+            .map_err(Into::into)
     }
 }
 
+fn limit_notice(
+    skulls: &[types::Skull],
+    reached: bool,
+    consumption: &types::ws::Consumption,
+) -> Option<String> {
+    use rucline::crossterm::style::Colorize;
+
+    let skull = skulls.iter().find(|s| s.id == consumption.skull)?;
+    let line = format!(
+        "Limit {} for {}: {}/{}",
+        if reached { "reached" } else { "exceeded" },
+        skull.name,
+        consumption.consumed,
+        consumption.limit
+    );
+
+    Some(if reached {
+        line.yellow().to_string()
+    } else {
+        line.red().to_string()
+    })
+}
+
 impl crate::PostAction for Handler {
     fn post(self) {
         self.request.post();
@@ -6,6 +6,7 @@ mod constant;
 mod handler;
 mod request;
 mod secret;
+mod socket;
 
 macro_rules! pass {
     ($fallible: expr) => {
@@ -47,12 +48,21 @@ fn main() -> std::process::ExitCode {
 async fn async_main(command: args::Command, handler: handler::Handler) -> std::process::ExitCode {
     pass!(match command {
         args::Command::List => handler.list().await,
+        args::Command::Watch => handler.watch().await,
+        args::Command::Tui => handler.tui().await,
         args::Command::Update => handler.update().await,
         args::Command::Register(args) => handler.register(args).await,
         args::Command::Dump => handler.dump().await,
         args::Command::Plot(args) => handler.plot(args).await,
+        args::Command::Spend(args) => handler.spend(args).await,
+        args::Command::Settings(args) => handler.settings(args).await,
+        args::Command::Undo => handler.undo().await,
+        args::Command::Redo => handler.redo().await,
+        args::Command::Sync => handler.sync().await,
     });
 
+    handler.flush().await;
+
     handler.post();
     std::process::ExitCode::SUCCESS
 }
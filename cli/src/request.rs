@@ -1,4 +1,4 @@
-use crate::{constant, secret};
+use crate::{constant, secret, socket};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -22,8 +22,12 @@ pub enum Error {
     ReqwestJson(reqwest::Error),
     #[error("Server responded with an error: {0}")]
     Server(reqwest::StatusCode, Option<types::Error>),
+    #[error("Failed to access the offline queue")]
+    Queue,
+    #[error("Failed to build the websocket request: {0}")]
+    Socket(tokio_tungstenite::tungstenite::Error),
     #[error("Unexpected response for request {0} {1}")]
-    UnexpectedResponse(reqwest::Method, &'static str, types::Payload),
+    UnexpectedResponse(reqwest::Method, &'static str, Box<types::Payload>),
 }
 
 impl crate::PostAction for Error {
@@ -61,6 +65,7 @@ pub struct Request {
     client: reqwest::Client,
     secret: secret::Secret,
     unauthorized: std::sync::atomic::AtomicBool,
+    reached: std::sync::atomic::AtomicBool,
 }
 
 impl Request {
@@ -79,23 +84,29 @@ impl Request {
             .map_err(Error::ReqwestBuild)?;
 
         let unauthorized = std::sync::atomic::AtomicBool::new(false);
+        let reached = std::sync::atomic::AtomicBool::new(false);
 
         Ok(Self {
             host,
             client,
             secret,
             unauthorized,
+            reached,
         })
     }
 
     pub async fn update(&self) -> Result<()> {
-        let (skulls, quicks) = tokio::join!(
+        let (skulls, quicks, tags, settings) = tokio::join!(
             self.get_resource_no_cache::<types::Skull>(None),
             self.get_resource_no_cache::<types::Quick>(None),
+            self.get_resource_no_cache::<types::Tag>(None),
+            self.get_resource_no_cache::<types::Settings>(None),
         );
 
         cache::save(&skulls?);
         cache::save(&quicks?);
+        cache::save(&tags?);
+        cache::save(&settings?);
         Ok(())
     }
 
@@ -112,6 +123,11 @@ impl Request {
         Ok(response)
     }
 
+    pub async fn get_settings(&self) -> Result<types::Settings> {
+        let settings = self.get_cacheable::<types::Settings>().await?;
+        Ok(settings.into_iter().next().unwrap_or_default())
+    }
+
     pub async fn get_occurrences<Search>(&self, search: Search) -> Result<Vec<types::Occurrence>>
     where
         Search: Into<Option<types::request::occurrence::Search>>,
@@ -125,27 +141,181 @@ impl Request {
         .await
     }
 
+    pub async fn get_spending(
+        &self,
+        spending: types::request::occurrence::Spending,
+    ) -> Result<Vec<types::Spending>> {
+        self.get_resource_no_cache(Some(spending.to_query())).await
+    }
+
+    pub async fn get_prediction(&self, millis: types::Millis) -> Result<Option<types::Prediction>> {
+        let predict = types::request::occurrence::Predict { millis };
+        self.get_resource_no_cache(Some(predict.to_query()))
+            .await
+            .map(|prediction| prediction.into_iter().next())
+    }
+
     pub async fn post_occurrences(
         &self,
         occurrences: Vec<types::request::occurrence::Item>,
     ) -> Result<()> {
+        let body = types::request::occurrence::Create {
+            items: occurrences,
+            key: Some(idempotency_key()),
+        };
+
+        match self.post_create(&body).await {
+            Err(Error::ReqwestSend(err)) => {
+                queue::push(body).ok_or(Error::ReqwestSend(err))?;
+                eprintln!("Server unreachable, queued the occurrences for the next sync");
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Sends the queued occurrences, stopping when the server cannot be reached
+    ///
+    /// Entries the server rejects, or older than the server remembers their key, are dropped and
+    /// reported. Entries failing for any other reason are kept for the next sync
+    ///
+    /// Returns how many queued registrations were sent
+    pub async fn sync(&self) -> Result<usize> {
+        let Some(mut queued) = queue::read() else {
+            return Err(Error::Queue);
+        };
+
+        let mut synced = 0;
+        let mut index = 0;
+        while let Some(body) = queued.get(index) {
+            if expired(body) {
+                eprintln!(
+                    "Dropped a queued registration older than the server retains: {}",
+                    describe(body)
+                );
+                queued.remove(index);
+            } else {
+                match self.post_create(body).await {
+                    Ok(()) => {
+                        queued.remove(index);
+                        synced += 1;
+                    }
+                    Err(Error::Server(status, error)) if status.is_client_error() => {
+                        let reason = error
+                            .and_then(|error| error.message)
+                            .unwrap_or_else(|| status.to_string());
+                        eprintln!(
+                            "Dropped a queued registration rejected by the server ({reason}): {}",
+                            describe(body)
+                        );
+                        queued.remove(index);
+                    }
+                    Err(err @ (Error::ReqwestSend(_) | Error::Unauthorized)) => return Err(err),
+                    Err(err) => {
+                        eprintln!("Kept a queued registration that failed to sync: {err}");
+                        index += 1;
+                        continue;
+                    }
+                }
+            }
+
+            queue::save(&queued).ok_or(Error::Queue)?;
+        }
+
+        Ok(synced)
+    }
+
+    /// Whether any request in this session got a response from the server
+    pub fn reached(&self) -> bool {
+        self.reached.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn socket(&self) -> Result<socket::Socket> {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+        let mut url = self.host.join("ws/binary").map_err(Error::Url)?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        // An unexpected scheme is kept as is, so that building the request reports it
+        let _ = url.set_scheme(scheme);
+
+        let mut request = url.as_str().into_client_request().map_err(Error::Socket)?;
+        request.headers_mut().insert(
+            reqwest::header::COOKIE,
+            reqwest::header::HeaderValue::from_str(self.secret.cookie())
+                .map_err(|_| Error::Token)?,
+        );
+
+        Ok(socket::Socket::new(request))
+    }
+
+    /// Flags the session as unauthorized, so that the secret is not kept
+    pub fn reject(&self) -> Error {
+        self.unauthorized
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        Error::Unauthorized
+    }
+
+    pub async fn patch_occurrence(&self, update: types::request::occurrence::Update) -> Result<()> {
         let url = self.url_for::<types::Occurrence>()?;
+        let request = self.client.patch(url).json(&update);
+        self.send_no_content(request).await
+    }
+
+    pub async fn delete_occurrence(&self, id: types::OccurrenceId) -> Result<()> {
+        let url = self.url_for::<types::Occurrence>()?;
+        let request = self
+            .client
+            .delete(url)
+            .json(&types::request::occurrence::Delete { id });
+        self.send_no_content(request).await
+    }
 
-        let body = types::request::occurrence::Create { items: occurrences };
-        let request = self.client.post(url).json(&body);
+    pub async fn patch_settings(&self, set: types::request::settings::Set) -> Result<()> {
+        let url = self.url_for::<types::Settings>()?;
+        let request = self.client.patch(url).json(&set);
+        self.send_no_content(request).await
+    }
+
+    pub async fn post_journal(&self, action: &'static str) -> Result<()> {
+        let url = self.host.join(action).map_err(Error::Url)?;
+
+        let request = self.client.post(url);
         let response = self.send(request).await?;
         match response {
-            types::Payload::Change(types::Change::Created) => Ok(()),
+            types::Payload::Change(types::Change::Updated) => Ok(()),
             payload => Err(Error::UnexpectedResponse(
                 reqwest::Method::POST,
-                "occurrence",
-                payload,
+                action,
+                Box::new(payload),
             )),
         }
     }
 }
 
 impl Request {
+    async fn post_create(&self, body: &types::request::occurrence::Create) -> Result<()> {
+        let url = self.url_for::<types::Occurrence>()?;
+
+        // Retries reuse the key, so that the server creates the occurrences only once
+        let mut attempt = 1;
+        let response = loop {
+            let request = self.client.post(url.clone()).json(body);
+            match self.send(request).await {
+                Err(Error::ReqwestSend(_)) if attempt < constant::RETRIES => attempt += 1,
+                response => break response?,
+            }
+        };
+
+        match response {
+            types::Payload::Change(types::Change::Created) => Ok(()),
+            payload => Err(Error::UnexpectedResponse(
+                reqwest::Method::POST,
+                "occurrence",
+                Box::new(payload),
+            )),
+        }
+    }
+
     fn url_for<R>(&self) -> Result<reqwest::Url>
     where
         R: sealed::Resource,
@@ -165,41 +335,95 @@ impl Request {
 
         let response = self.send(request).await?;
         R::extract(response)
-            .map_err(|r| Error::UnexpectedResponse(reqwest::Method::GET, R::PATH, r))
+            .map_err(|r| Error::UnexpectedResponse(reqwest::Method::GET, R::PATH, Box::new(r)))
     }
 
     async fn send(&self, request: reqwest::RequestBuilder) -> Result<types::Payload> {
+        let response = self.dispatch(request).await?;
+        let status = response.status();
+        let body = response.json::<types::Response>().await;
+
+        if status.is_success() {
+            match body.map_err(Error::ReqwestJson)? {
+                types::Response::Error(error) => Err(Error::Server(status, Some(error))),
+                types::Response::Payload(payload) => Ok(payload),
+            }
+        } else {
+            Err(Error::Server(status, server_error(body)))
+        }
+    }
+
+    /// Sends a request that is answered without a body when successful
+    async fn send_no_content(&self, request: reqwest::RequestBuilder) -> Result<()> {
+        let response = self.dispatch(request).await?;
+        let status = response.status();
+
+        if status == reqwest::StatusCode::NO_CONTENT {
+            Ok(())
+        } else {
+            let body = response.json::<types::Response>().await;
+            Err(Error::Server(status, server_error(body)))
+        }
+    }
+
+    async fn dispatch(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
         if self.unauthorized.load(std::sync::atomic::Ordering::Relaxed) {
             return Err(Error::Unauthorized);
         }
 
         let response = request.send().await.map_err(Error::ReqwestSend)?;
+        self.reached
+            .store(true, std::sync::atomic::Ordering::Relaxed);
 
-        let status = response.status();
-
-        if unauthorized(status) {
+        if unauthorized(response.status()) {
             self.unauthorized
                 .store(true, std::sync::atomic::Ordering::Relaxed);
             return Err(Error::Unauthorized);
         }
 
-        let body = response.json::<types::Response>().await;
-
-        if status.is_success() {
-            match body.map_err(Error::ReqwestJson)? {
-                types::Response::Error(error) => Err(Error::Server(status, Some(error))),
-                types::Response::Payload(payload) => Ok(payload),
-            }
-        } else {
-            let error = body.ok().and_then(|response| match response {
-                types::Response::Error(error) => Some(error),
-                types::Response::Payload(_) => None,
-            });
-            Err(Error::Server(status, error))
-        }
+        Ok(response)
     }
 }
 
+fn server_error(body: reqwest::Result<types::Response>) -> Option<types::Error> {
+    body.ok().and_then(|response| match response {
+        types::Response::Error(error) => Some(error),
+        types::Response::Payload(_) => None,
+    })
+}
+
+fn idempotency_key() -> String {
+    format!("{:x}-{:x}", now_nanos(), std::process::id())
+}
+
+/// Whether the server may have forgotten the key of a queued registration
+///
+/// The key starts with the nanos it was made at, as made by [`idempotency_key`]
+fn expired(body: &types::request::occurrence::Create) -> bool {
+    let Some(made) = body
+        .key
+        .as_deref()
+        .and_then(|key| key.split('-').next())
+        .and_then(|nanos| u128::from_str_radix(nanos, 16).ok())
+    else {
+        return false;
+    };
+
+    let retention =
+        u128::from(types::request::occurrence::KEY_RETENTION.unsigned_abs()) * 1_000_000;
+    now_nanos().saturating_sub(made) > retention
+}
+
+fn describe(body: &types::request::occurrence::Create) -> String {
+    serde_json::to_string(&body.items).unwrap_or_default()
+}
+
+fn now_nanos() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos())
+}
+
 impl crate::PostAction for Request {
     fn post(self) {
         self.secret
@@ -240,6 +464,20 @@ mod sealed {
         }
     }
 
+    /// The prediction is an optional single entry, which is handled as a list to share the fetching
+    impl Resource for types::Prediction {
+        type Query = types::request::occurrence::Predict;
+
+        const PATH: &'static str = "occurrence/predict";
+
+        fn extract(payload: types::Payload) -> Result<Vec<Self>, types::Payload> {
+            match payload {
+                types::Payload::Prediction(prediction) => Ok(prediction.into_iter().collect()),
+                payload => Err(payload),
+            }
+        }
+    }
+
     impl Resource for types::Occurrence {
         type Query = types::request::occurrence::Search;
 
@@ -247,7 +485,48 @@ mod sealed {
 
         fn extract(payload: types::Payload) -> Result<Vec<Self>, types::Payload> {
             match payload {
-                types::Payload::Occurrences(occurrences) => Ok(occurrences),
+                types::Payload::Occurrences(occurrences)
+                | types::Payload::Page(types::Page { occurrences, .. }) => Ok(occurrences),
+                payload => Err(payload),
+            }
+        }
+    }
+
+    impl Resource for types::Spending {
+        type Query = types::request::occurrence::Spending;
+
+        const PATH: &'static str = "occurrence/spending";
+
+        fn extract(payload: types::Payload) -> Result<Vec<Self>, types::Payload> {
+            match payload {
+                types::Payload::Spending(spending) => Ok(spending),
+                payload => Err(payload),
+            }
+        }
+    }
+
+    impl Resource for types::Tag {
+        type Query = ();
+
+        const PATH: &'static str = "tag";
+
+        fn extract(payload: types::Payload) -> Result<Vec<Self>, types::Payload> {
+            match payload {
+                types::Payload::Tags(tags) => Ok(tags),
+                payload => Err(payload),
+            }
+        }
+    }
+
+    /// The settings are a single entry, which is handled as a list to share the cache
+    impl Resource for types::Settings {
+        type Query = ();
+
+        const PATH: &'static str = "settings";
+
+        fn extract(payload: types::Payload) -> Result<Vec<Self>, types::Payload> {
+            match payload {
+                types::Payload::Settings(settings) => Ok(vec![settings]),
                 payload => Err(payload),
             }
         }
@@ -257,6 +536,8 @@ mod sealed {
 
     impl Cacheable for types::Skull {}
     impl Cacheable for types::Quick {}
+    impl Cacheable for types::Tag {}
+    impl Cacheable for types::Settings {}
 }
 
 fn get_host() -> Result<reqwest::Url> {
@@ -331,3 +612,45 @@ mod cache {
         std::fs::write(path, bytes).ok()
     }
 }
+
+mod queue {
+    use crate::constant;
+
+    type Queued = types::request::occurrence::Create;
+
+    /// Returns `None` if the queue exists but cannot be read, so that it is never overwritten
+    pub fn read() -> Option<Vec<Queued>> {
+        let path = constant::path::queue()?;
+        if !path.exists() {
+            return Some(Vec::new());
+        }
+        let bytes = std::fs::read(path).ok()?;
+        serde_json::from_slice(bytes.as_slice()).ok()
+    }
+
+    pub fn save(queued: &[Queued]) -> Option<()> {
+        let path = constant::path::queue()?;
+        if queued.is_empty() {
+            return if path.exists() {
+                std::fs::remove_file(path).ok()
+            } else {
+                Some(())
+            };
+        }
+
+        if let Some(parent) = path.parent()
+            && !parent.exists()
+        {
+            std::fs::create_dir_all(parent).ok()?;
+        }
+
+        let bytes = serde_json::to_vec(queued).ok()?;
+        std::fs::write(path, bytes).ok()
+    }
+
+    pub fn push(body: Queued) -> Option<()> {
+        let mut queued = read()?;
+        queued.push(body);
+        save(&queued)
+    }
+}
@@ -0,0 +1,134 @@
+use tokio_tungstenite::tungstenite;
+
+type Stream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// How long to stay silent before pinging the server
+const PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// How long without hearing from the server before dropping the connection
+const HEARTBEAT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(75);
+const MIN_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Failed to connect: {0}")]
+    Connect(tungstenite::Error),
+    #[error("Connection lost: {0}")]
+    Receive(tungstenite::Error),
+    #[error("Failed to decode message: {0}")]
+    Decode(rmp_serde::decode::Error),
+    #[error("Server stopped responding")]
+    Heartbeat,
+    #[error("Connection closed")]
+    Closed,
+}
+
+pub enum Event {
+    Connected,
+    Disconnected(Error),
+    Push(types::Push),
+}
+
+pub struct Socket {
+    request: tungstenite::http::Request<()>,
+    stream: Option<Stream>,
+    backoff: Option<std::time::Duration>,
+}
+
+impl Socket {
+    pub fn new(request: tungstenite::http::Request<()>) -> Self {
+        Self {
+            request,
+            stream: None,
+            backoff: None,
+        }
+    }
+
+    /// Waits for the next event, reconnecting with a backoff whenever the connection drops
+    pub async fn next(&mut self) -> Event {
+        let Some(stream) = self.stream.as_mut() else {
+            return self.connect().await;
+        };
+
+        match receive(stream).await {
+            Ok(push) => Event::Push(push),
+            Err(err) => {
+                self.stream = None;
+                Event::Disconnected(err)
+            }
+        }
+    }
+
+    async fn connect(&mut self) -> Event {
+        if let Some(backoff) = self.backoff {
+            tokio::time::sleep(backoff).await;
+        }
+
+        match tokio_tungstenite::connect_async(self.request.clone()).await {
+            Ok((stream, _)) => {
+                self.stream = Some(stream);
+                self.backoff = None;
+                Event::Connected
+            }
+            Err(tungstenite::Error::Http(response)) if unauthorized(response.status()) => {
+                Event::Disconnected(Error::Unauthorized)
+            }
+            Err(err) => {
+                self.backoff = Some(
+                    self.backoff
+                        .map_or(MIN_BACKOFF, |backoff| backoff * 2)
+                        .min(MAX_BACKOFF),
+                );
+                Event::Disconnected(Error::Connect(err))
+            }
+        }
+    }
+}
+
+async fn receive(stream: &mut Stream) -> Result<types::Push, Error> {
+    use futures_util::{SinkExt, StreamExt};
+
+    let mut last_seen = std::time::Instant::now();
+
+    loop {
+        let message = match tokio::time::timeout(PING_INTERVAL, stream.next()).await {
+            Ok(Some(message)) => message.map_err(Error::Receive)?,
+            Ok(None) => return Err(Error::Closed),
+            Err(_) => {
+                if last_seen.elapsed() >= HEARTBEAT_TIMEOUT {
+                    return Err(Error::Heartbeat);
+                }
+                stream
+                    .send(tungstenite::Message::Ping(tungstenite::Bytes::new()))
+                    .await
+                    .map_err(Error::Receive)?;
+                continue;
+            }
+        };
+
+        last_seen = std::time::Instant::now();
+
+        match message {
+            tungstenite::Message::Binary(bytes) => {
+                match rmp_serde::from_slice(&bytes).map_err(Error::Decode)? {
+                    types::Message::Push(push) => return Ok(push),
+                    types::Message::Response(_) => {}
+                }
+            }
+            tungstenite::Message::Close(_) => return Err(Error::Closed),
+            tungstenite::Message::Text(_)
+            | tungstenite::Message::Ping(_)
+            | tungstenite::Message::Pong(_)
+            | tungstenite::Message::Frame(_) => {}
+        }
+    }
+}
+
+fn unauthorized(status: tungstenite::http::StatusCode) -> bool {
+    status == tungstenite::http::StatusCode::UNAUTHORIZED
+        || status == tungstenite::http::StatusCode::FORBIDDEN
+        || status == tungstenite::http::StatusCode::FOUND
+}
@@ -77,7 +77,7 @@ async fn ingest_skulls(
 
         let new_id = store
             .skulls()
-            .create(name, color, icon, price, limit)
+            .create(name, color, icon, price, limit, types::Period::default())
             .await
             .map_err(|e| format!("Skulls: Line {i}: Failed to write to store: {e}"))?
             .id;
@@ -177,7 +177,11 @@ async fn ingest_occurrences(
         if buffer.len() == 20 {
             store
                 .occurrences()
-                .create(buffer.iter().copied())
+                .create(
+                    buffer
+                        .iter()
+                        .map(|&(skull, amount, millis)| (skull, amount, millis, None)),
+                )
                 .await
                 .map_err(|e| format!("Failed to write to store: {e}"))?;
             buffer.clear();
@@ -188,7 +192,11 @@ async fn ingest_occurrences(
     if !buffer.is_empty() {
         store
             .occurrences()
-            .create(buffer)
+            .create(
+                buffer
+                    .into_iter()
+                    .map(|(skull, amount, millis)| (skull, amount, millis, None)),
+            )
             .await
             .map_err(|e| format!("Failed to write to store: {e}"))?;
     }
@@ -0,0 +1,36 @@
+use types::{
+    Response,
+    request::{Audit, audit::List},
+};
+
+use super::QueryRejection;
+use crate::service::Service;
+
+pub fn build() -> axum::Router {
+    axum::Router::new().route("/", axum::routing::get(get))
+}
+
+async fn get(
+    axum::Extension(service): axum::Extension<Service>,
+    ListQuery(list): ListQuery,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Audit(Audit::List(list))).await
+}
+
+#[repr(transparent)]
+struct ListQuery(List);
+
+impl<S> axum::extract::FromRequestParts<S> for ListQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            List::from_query(parts.uri.query().unwrap_or(""))
+                .map(ListQuery)
+                .map_err(QueryRejection)
+        })
+    }
+}
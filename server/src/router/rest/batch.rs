@@ -0,0 +1,14 @@
+use types::{Request, Response};
+
+use crate::service::Service;
+
+pub fn build() -> axum::Router {
+    axum::Router::new().route("/batch", axum::routing::post(batch))
+}
+
+async fn batch(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(requests): axum::Json<Vec<Request>>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, Request::Batch(requests)).await
+}
@@ -0,0 +1,21 @@
+use types::Response;
+
+use crate::service::Service;
+
+pub fn build() -> axum::Router {
+    axum::Router::new()
+        .route("/undo", axum::routing::post(undo))
+        .route("/redo", axum::routing::post(redo))
+}
+
+async fn undo(
+    axum::Extension(service): axum::Extension<Service>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Undo).await
+}
+
+async fn redo(
+    axum::Extension(service): axum::Extension<Service>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Redo).await
+}
@@ -1,5 +1,10 @@
+mod audit;
+mod batch;
+mod journal;
 mod occurrence;
+mod settings;
 mod skull;
+mod tag;
 
 use crate::service::Service;
 
@@ -7,6 +12,11 @@ pub fn build() -> axum::Router {
     axum::Router::new()
         .nest("/skull", skull::build())
         .nest("/occurrence", occurrence::build())
+        .nest("/tag", tag::build())
+        .nest("/audit", audit::build())
+        .nest("/settings", settings::build())
+        .merge(journal::build())
+        .merge(batch::build())
 }
 
 async fn handle(
@@ -20,9 +30,19 @@ async fn handle(
                 types::Payload::Change(types::Change::Updated | types::Change::Deleted) => {
                     hyper::StatusCode::NO_CONTENT
                 }
-                types::Payload::Skulls(_)
+                types::Payload::Skull(_)
+                | types::Payload::Skulls(_)
                 | types::Payload::Quicks(_)
-                | types::Payload::Occurrences(_) => hyper::StatusCode::OK,
+                | types::Payload::Prediction(_)
+                | types::Payload::Occurrence(_)
+                | types::Payload::Occurrences(_)
+                | types::Payload::Page(_)
+                | types::Payload::Aggregates(_)
+                | types::Payload::Spending(_)
+                | types::Payload::Tags(_)
+                | types::Payload::Audits(_)
+                | types::Payload::Settings(_)
+                | types::Payload::Batch(_) => hyper::StatusCode::OK,
             };
             (status, axum::Json(types::Response::Payload(payload)))
         }
@@ -36,3 +56,12 @@ async fn handle(
         }
     }
 }
+
+#[repr(transparent)]
+struct QueryRejection(types::request::occurrence::query::Error);
+
+impl axum::response::IntoResponse for QueryRejection {
+    fn into_response(self) -> axum::response::Response {
+        (hyper::StatusCode::BAD_REQUEST, self.0.to_string()).into_response()
+    }
+}
@@ -2,50 +2,141 @@ use types::{
     Response,
     request::{
         Occurrence,
-        occurrence::{Create, Delete, Search, Update, query::Error},
+        occurrence::{
+            Aggregate, Create, Delete, DeleteBatch, Get, Predict, Quick, Search, Spending, Update,
+            UpdateBatch,
+        },
     },
 };
 
+use super::QueryRejection;
 use crate::service::Service;
 
 pub fn build() -> axum::Router {
     axum::Router::new()
         .route("/", axum::routing::get(get))
+        .route("/{id}", axum::routing::get(get_one))
         .route("/quick", axum::routing::get(quick))
+        .route("/predict", axum::routing::get(predict))
+        .route("/aggregate", axum::routing::get(aggregate))
+        .route("/spending", axum::routing::get(spending))
         .route("/", axum::routing::post(post))
         .route("/", axum::routing::patch(patch))
         .route("/", axum::routing::delete(delete))
+        .route("/batch", axum::routing::patch(patch_batch))
+        .route("/batch", axum::routing::delete(delete_batch))
+        .route("/search", axum::routing::delete(delete_search))
 }
 
 async fn get(
     axum::Extension(service): axum::Extension<Service>,
+    axum::extract::OriginalUri(uri): axum::extract::OriginalUri,
     SearchQuery(search): SearchQuery,
-) -> (hyper::StatusCode, axum::Json<Response>) {
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
     if search.skulls.is_none()
+        && search.tags.is_none()
         && search.start.is_none()
         && search.end.is_none()
         && search.limit.is_none()
+        && search.cursor.is_none()
+    {
+        return super::handle(service, types::Request::Occurrence(Occurrence::List))
+            .await
+            .into_response();
+    }
+
+    let (status, response) = super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Search(search.clone())),
+    )
+    .await;
+
+    if let Response::Payload(types::Payload::Page(types::Page {
+        next: Some(next), ..
+    })) = &response.0
     {
-        super::handle(service, types::Request::Occurrence(Occurrence::List)).await
+        let search = Search {
+            cursor: Some(*next),
+            ..search
+        };
+        let link = format!(
+            "<{path}?{query}>; rel=\"next\"",
+            path = uri.path(),
+            query = search.to_query()
+        );
+        (status, [(hyper::header::LINK, link)], response).into_response()
     } else {
-        super::handle(
-            service,
-            types::Request::Occurrence(Occurrence::Search(search)),
-        )
-        .await
+        (status, response).into_response()
     }
 }
 
+async fn get_one(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::extract::Path(id): axum::extract::Path<types::OccurrenceId>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Get(Get { id })),
+    )
+    .await
+}
+
 async fn quick(
     axum::Extension(service): axum::Extension<Service>,
+    QuickQuery(quick): QuickQuery,
 ) -> (hyper::StatusCode, axum::Json<Response>) {
-    super::handle(service, types::Request::Occurrence(Occurrence::Quick)).await
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Quick(quick)),
+    )
+    .await
+}
+
+async fn predict(
+    axum::Extension(service): axum::Extension<Service>,
+    PredictQuery(predict): PredictQuery,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Predict(predict)),
+    )
+    .await
+}
+
+async fn aggregate(
+    axum::Extension(service): axum::Extension<Service>,
+    AggregateQuery(aggregate): AggregateQuery,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Aggregate(aggregate)),
+    )
+    .await
+}
+
+async fn spending(
+    axum::Extension(service): axum::Extension<Service>,
+    SpendingQuery(spending): SpendingQuery,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::Spending(spending)),
+    )
+    .await
 }
 
 async fn post(
     axum::Extension(service): axum::Extension<Service>,
-    axum::Json(request): axum::Json<Create>,
+    IdempotencyKey(key): IdempotencyKey,
+    axum::Json(mut request): axum::Json<Create>,
 ) -> (hyper::StatusCode, axum::Json<Response>) {
+    // A key in the body takes precedence over the header
+    if request.key.is_none() {
+        request.key = key;
+    }
+
     super::handle(
         service,
         types::Request::Occurrence(Occurrence::Create(request)),
@@ -75,11 +166,44 @@ async fn delete(
     .await
 }
 
+async fn patch_batch(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<UpdateBatch>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::UpdateBatch(request)),
+    )
+    .await
+}
+
+async fn delete_batch(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<DeleteBatch>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::DeleteBatch(request)),
+    )
+    .await
+}
+
+async fn delete_search(
+    axum::Extension(service): axum::Extension<Service>,
+    SearchQuery(search): SearchQuery,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(
+        service,
+        types::Request::Occurrence(Occurrence::DeleteSearch(search)),
+    )
+    .await
+}
+
 #[repr(transparent)]
 struct SearchQuery(Search);
 
 impl<S> axum::extract::FromRequestParts<S> for SearchQuery {
-    type Rejection = SearchQueryRejection;
+    type Rejection = QueryRejection;
 
     fn from_request_parts(
         parts: &mut hyper::http::request::Parts,
@@ -88,16 +212,106 @@ impl<S> axum::extract::FromRequestParts<S> for SearchQuery {
         Box::pin(async move {
             Search::from_query(parts.uri.query().unwrap_or(""))
                 .map(SearchQuery)
-                .map_err(SearchQueryRejection)
+                .map_err(QueryRejection)
+        })
+    }
+}
+
+#[repr(transparent)]
+struct QuickQuery(Quick);
+
+impl<S> axum::extract::FromRequestParts<S> for QuickQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            Quick::from_query(parts.uri.query().unwrap_or(""))
+                .map(QuickQuery)
+                .map_err(QueryRejection)
+        })
+    }
+}
+
+#[repr(transparent)]
+struct PredictQuery(Predict);
+
+impl<S> axum::extract::FromRequestParts<S> for PredictQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            Predict::from_query(parts.uri.query().unwrap_or(""))
+                .map(PredictQuery)
+                .map_err(QueryRejection)
         })
     }
 }
 
 #[repr(transparent)]
-struct SearchQueryRejection(Error);
+struct AggregateQuery(Aggregate);
 
-impl axum::response::IntoResponse for SearchQueryRejection {
-    fn into_response(self) -> axum::response::Response {
-        (hyper::StatusCode::BAD_REQUEST, self.0.to_string()).into_response()
+impl<S> axum::extract::FromRequestParts<S> for AggregateQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            Aggregate::from_query(parts.uri.query().unwrap_or(""))
+                .map(AggregateQuery)
+                .map_err(QueryRejection)
+        })
+    }
+}
+
+#[repr(transparent)]
+struct SpendingQuery(Spending);
+
+impl<S> axum::extract::FromRequestParts<S> for SpendingQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            Spending::from_query(parts.uri.query().unwrap_or(""))
+                .map(SpendingQuery)
+                .map_err(QueryRejection)
+        })
+    }
+}
+
+#[repr(transparent)]
+struct IdempotencyKey(Option<String>);
+
+impl<S> axum::extract::FromRequestParts<S> for IdempotencyKey {
+    type Rejection = (hyper::StatusCode, &'static str);
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            parts
+                .headers
+                .get("idempotency-key")
+                .map(|key| key.to_str().map(String::from))
+                .transpose()
+                .map(IdempotencyKey)
+                .map_err(|_| {
+                    (
+                        hyper::StatusCode::BAD_REQUEST,
+                        "Invalid value for header `Idempotency-Key`",
+                    )
+                })
+        })
     }
 }
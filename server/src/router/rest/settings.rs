@@ -0,0 +1,25 @@
+use types::{
+    Response,
+    request::{Settings, settings::Set},
+};
+
+use crate::service::Service;
+
+pub fn build() -> axum::Router {
+    axum::Router::new()
+        .route("/", axum::routing::get(get))
+        .route("/", axum::routing::patch(patch))
+}
+
+async fn get(
+    axum::Extension(service): axum::Extension<Service>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Settings(Settings::Get)).await
+}
+
+async fn patch(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Set>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Settings(Settings::Set(request))).await
+}
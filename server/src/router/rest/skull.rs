@@ -2,24 +2,37 @@ use types::{
     Response,
     request::{
         Skull,
-        skull::{Create, Delete, Update},
+        skull::{Archive, Create, Delete, Get, List, Merge, Unarchive, Update},
     },
 };
 
+use super::QueryRejection;
 use crate::service::Service;
 
 pub fn build() -> axum::Router {
     axum::Router::new()
         .route("/", axum::routing::get(get))
+        .route("/{id}", axum::routing::get(get_one))
         .route("/", axum::routing::post(post))
         .route("/", axum::routing::patch(patch))
         .route("/", axum::routing::delete(delete))
+        .route("/archive", axum::routing::post(archive))
+        .route("/unarchive", axum::routing::post(unarchive))
+        .route("/merge", axum::routing::post(merge))
 }
 
 async fn get(
     axum::Extension(service): axum::Extension<Service>,
+    ListQuery(list): ListQuery,
 ) -> (hyper::StatusCode, axum::Json<Response>) {
-    super::handle(service, types::Request::Skull(Skull::List)).await
+    super::handle(service, types::Request::Skull(Skull::List(list))).await
+}
+
+async fn get_one(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::extract::Path(id): axum::extract::Path<types::SkullId>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Skull(Skull::Get(Get { id }))).await
 }
 
 async fn post(
@@ -42,3 +55,42 @@ async fn delete(
 ) -> (hyper::StatusCode, axum::Json<Response>) {
     super::handle(service, types::Request::Skull(Skull::Delete(request))).await
 }
+
+async fn archive(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Archive>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Skull(Skull::Archive(request))).await
+}
+
+async fn unarchive(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Unarchive>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Skull(Skull::Unarchive(request))).await
+}
+
+async fn merge(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Merge>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Skull(Skull::Merge(request))).await
+}
+
+#[repr(transparent)]
+struct ListQuery(List);
+
+impl<S> axum::extract::FromRequestParts<S> for ListQuery {
+    type Rejection = QueryRejection;
+
+    fn from_request_parts(
+        parts: &mut hyper::http::request::Parts,
+        _: &S,
+    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
+        Box::pin(async move {
+            List::from_query(parts.uri.query().unwrap_or(""))
+                .map(ListQuery)
+                .map_err(QueryRejection)
+        })
+    }
+}
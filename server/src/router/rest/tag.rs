@@ -0,0 +1,60 @@
+use types::{
+    Response,
+    request::{
+        Tag,
+        tag::{Attach, Create, Delete, Detach, Update},
+    },
+};
+
+use crate::service::Service;
+
+pub fn build() -> axum::Router {
+    axum::Router::new()
+        .route("/", axum::routing::get(get))
+        .route("/", axum::routing::post(post))
+        .route("/", axum::routing::patch(patch))
+        .route("/", axum::routing::delete(delete))
+        .route("/attach", axum::routing::post(attach))
+        .route("/detach", axum::routing::post(detach))
+}
+
+async fn get(
+    axum::Extension(service): axum::Extension<Service>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::List)).await
+}
+
+async fn post(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Create>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::Create(request))).await
+}
+
+async fn patch(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Update>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::Update(request))).await
+}
+
+async fn delete(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Delete>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::Delete(request))).await
+}
+
+async fn attach(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Attach>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::Attach(request))).await
+}
+
+async fn detach(
+    axum::Extension(service): axum::Extension<Service>,
+    axum::Json(request): axum::Json<Detach>,
+) -> (hyper::StatusCode, axum::Json<Response>) {
+    super::handle(service, types::Request::Tag(Tag::Detach(request))).await
+}
@@ -0,0 +1,35 @@
+use types::{
+    Payload,
+    request::{Audit, audit::List},
+};
+
+use super::Service;
+
+type Result = std::result::Result<types::Payload, store::Error>;
+
+pub async fn handle(service: &Service, request: Audit) -> Result {
+    let audits = Audits::new(service);
+    match request {
+        Audit::List(request) => audits.list(request).await,
+    }
+}
+
+struct Audits<'a> {
+    store: store::store::audits::Audits<'a>,
+}
+
+impl<'a> Audits<'a> {
+    fn new(service: &'a Service) -> Self {
+        let store = service.store.audits();
+        Self { store }
+    }
+}
+
+impl Audits<'_> {
+    async fn list(&self, request: List) -> Result {
+        self.store
+            .list(request.since, request.limit)
+            .await
+            .map(Payload::Audits)
+    }
+}
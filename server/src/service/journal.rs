@@ -0,0 +1,46 @@
+use store::store::journal::{Entry, Step};
+use types::{Change, Payload, Push};
+
+use super::{Broadcaster, Service};
+
+type Result = std::result::Result<types::Payload, store::Error>;
+
+pub async fn undo(service: &Service) -> Result {
+    let steps = service.store.journal().undo().await?;
+
+    broadcast(&service.broadcaster, steps);
+    Ok(Payload::Change(Change::Updated))
+}
+
+pub async fn redo(service: &Service) -> Result {
+    let steps = service.store.journal().redo().await?;
+
+    broadcast(&service.broadcaster, steps);
+    Ok(Payload::Change(Change::Updated))
+}
+
+fn broadcast(broadcaster: &Broadcaster, steps: Vec<Step>) {
+    let mut created = Vec::new();
+
+    for step in steps {
+        let push = match step {
+            Step::Created(Entry::Skull(skull)) => Push::SkullCreated(skull),
+            Step::Updated(Entry::Skull(skull)) => Push::SkullUpdated(skull),
+            Step::Deleted(Entry::Skull(skull)) => Push::SkullDeleted(skull.id),
+            Step::Created(Entry::Occurrence(occurrence)) => {
+                created.push(occurrence);
+                continue;
+            }
+            Step::Updated(Entry::Occurrence(occurrence)) => Push::OccurrenceUpdated(occurrence),
+            Step::Deleted(Entry::Occurrence(occurrence)) => Push::OccurrenceDeleted(occurrence.id),
+            Step::Created(Entry::Tag(tag)) => Push::TagCreated(tag),
+            Step::Updated(Entry::Tag(tag)) => Push::TagUpdated(tag),
+            Step::Deleted(Entry::Tag(tag)) => Push::TagDeleted(tag.id),
+        };
+        broadcaster.send(push);
+    }
+
+    if !created.is_empty() {
+        broadcaster.send(Push::OccurrencesCreated(created));
+    }
+}
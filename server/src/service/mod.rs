@@ -1,5 +1,9 @@
+mod audits;
+mod journal;
 mod occurrences;
+mod settings;
 mod skulls;
+mod tags;
 
 pub async fn new(
     db_root: std::path::PathBuf,
@@ -8,7 +12,7 @@ pub async fn new(
     let mut services = std::collections::HashMap::with_capacity(users.len());
 
     for user in users {
-        let service = Service::new(db_root.join(&user)).await?;
+        let service = Service::new(db_root.join(&user), &user).await?;
         services.insert(user, service);
     }
 
@@ -39,15 +43,54 @@ pub struct Service {
 #[derive(Debug, Clone)]
 struct Broadcaster {
     sender: tokio::sync::broadcast::Sender<types::Push>,
+    /// Holds back the pushes of a batch until it is committed
+    pending: Option<std::sync::Arc<std::sync::Mutex<Vec<types::Push>>>>,
 }
 
 impl Broadcaster {
     fn new() -> Self {
         let (sender, _) = tokio::sync::broadcast::channel::<types::Push>(16);
-        Self { sender }
+        Self {
+            sender,
+            pending: None,
+        }
+    }
+
+    fn pending(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            pending: Some(std::sync::Arc::default()),
+        }
+    }
+
+    fn flush(&self) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+
+        let pushes = std::mem::take(
+            &mut *pending
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        );
+
+        for push in pushes {
+            self.broadcast(push);
+        }
     }
 
     fn send(&self, push: types::Push) {
+        if let Some(pending) = &self.pending {
+            pending
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(push);
+        } else {
+            self.broadcast(push);
+        }
+    }
+
+    fn broadcast(&self, push: types::Push) {
         if let Ok(count) = self.sender.send(push) {
             if count == 1 {
                 tracing::debug!("Broadcasting to 1 listener");
@@ -60,12 +103,7 @@ impl Broadcaster {
 
 impl Service {
     pub async fn handle(&self, request: types::Request) -> types::Response {
-        let result = match request {
-            types::Request::Skull(request) => skulls::handle(self, request).await,
-            types::Request::Occurrence(request) => occurrences::handle(self, request).await,
-        };
-
-        match result {
+        match self.dispatch(request).await {
             Ok(payload) => types::Response::Payload(payload),
             Err(error) => {
                 if error.kind() == types::Kind::InternalError {
@@ -76,12 +114,46 @@ impl Service {
         }
     }
 
+    async fn dispatch(&self, request: types::Request) -> store::Result<types::Payload> {
+        match request {
+            types::Request::Skull(request) => skulls::handle(self, request).await,
+            types::Request::Occurrence(request) => occurrences::handle(self, request).await,
+            types::Request::Tag(request) => tags::handle(self, request).await,
+            types::Request::Audit(request) => audits::handle(self, request).await,
+            types::Request::Settings(request) => settings::handle(self, request).await,
+            types::Request::Undo => journal::undo(self).await,
+            types::Request::Redo => journal::redo(self).await,
+            types::Request::Batch(requests) => self.batch(requests).await,
+        }
+    }
+
+    async fn batch(&self, requests: Vec<types::Request>) -> store::Result<types::Payload> {
+        let batch = self.store.batch().await?;
+        let service = Self {
+            store: batch.store().clone(),
+            broadcaster: self.broadcaster.pending(),
+        };
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            let payload = Box::pin(service.dispatch(request)).await?;
+            responses.push(types::Response::Payload(payload));
+        }
+
+        let Self { store, broadcaster } = service;
+        drop(store);
+        batch.commit().await?;
+
+        broadcaster.flush();
+        Ok(types::Payload::Batch(responses))
+    }
+
     pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<types::Push> {
         self.broadcaster.sender.subscribe()
     }
 
-    async fn new<P: AsRef<std::path::Path>>(path: P) -> store::Result<Self> {
-        let store = store::Store::new(path, 1).await?;
+    async fn new<P: AsRef<std::path::Path>>(path: P, user: &str) -> store::Result<Self> {
+        let store = store::Store::new(path, 1).await?.with_actor(user);
         store.migrate().await?;
 
         let broadcaster = Broadcaster::new();
@@ -1,9 +1,13 @@
 use types::{
-    Change, Payload, Push, Setter,
+    Change, Cursor, Page, Payload, Push, Setter,
     request::{
         Occurrence,
-        occurrence::{Create, Search, Update},
+        occurrence::{
+            Aggregate, Create, DeleteBatch, Get, Predict, Quick, Search, Spending, Update,
+            UpdateBatch,
+        },
     },
+    ws::Consumption,
 };
 
 use super::{Broadcaster, Service};
@@ -14,11 +18,18 @@ pub async fn handle(service: &Service, request: Occurrence) -> Result {
     let occurrences = Occurrences::new(service);
     match request {
         Occurrence::List => occurrences.list().await,
-        Occurrence::Quick => occurrences.quick().await,
+        Occurrence::Get(request) => occurrences.get(request).await,
+        Occurrence::Quick(request) => occurrences.quick(request).await,
+        Occurrence::Predict(request) => occurrences.predict(request).await,
         Occurrence::Search(request) => occurrences.search(request).await,
+        Occurrence::Aggregate(request) => occurrences.aggregate(request).await,
+        Occurrence::Spending(request) => occurrences.spending(request).await,
         Occurrence::Create(request) => occurrences.create(request).await,
         Occurrence::Update(request) => occurrences.update(request).await,
+        Occurrence::UpdateBatch(request) => occurrences.update_batch(request).await,
         Occurrence::Delete(request) => occurrences.delete(request).await,
+        Occurrence::DeleteBatch(request) => occurrences.delete_batch(request).await,
+        Occurrence::DeleteSearch(request) => occurrences.delete_search(request).await,
     }
 }
 
@@ -40,34 +51,85 @@ impl Occurrences<'_> {
         self.store.list().await.map(Payload::Occurrences)
     }
 
-    async fn quick(&self) -> Result {
-        self.store.quick().await.map(Payload::Quicks)
+    async fn get(&self, request: Get) -> Result {
+        self.store.get(request.id).await.map(Payload::Occurrence)
     }
 
-    async fn search(&self, request: Search) -> Result {
+    async fn quick(&self, request: Quick) -> Result {
         self.store
+            .quick(request.half_life, request.limit, request.hour)
+            .await
+            .map(Payload::Quicks)
+    }
+
+    async fn predict(&self, request: Predict) -> Result {
+        self.store
+            .predict(request.millis)
+            .await
+            .map(Payload::Prediction)
+    }
+
+    async fn search(&self, request: Search) -> Result {
+        let occurrences = self
+            .store
             .search(
                 request.skulls.as_ref(),
+                request.tags.as_ref(),
                 request.start,
                 request.end,
                 request.limit,
+                request.cursor,
+            )
+            .await?;
+
+        let Some(limit) = request.limit else {
+            return Ok(Payload::Occurrences(occurrences));
+        };
+
+        let next = Cursor::next(&occurrences, limit);
+        Ok(Payload::Page(Page { occurrences, next }))
+    }
+
+    async fn aggregate(&self, request: Aggregate) -> Result {
+        self.store
+            .aggregate(
+                request.skulls.as_ref(),
+                request.start,
+                request.end,
+                request.bucket,
             )
             .await
-            .map(Payload::Occurrences)
+            .map(Payload::Aggregates)
+    }
+
+    async fn spending(&self, request: Spending) -> Result {
+        self.store
+            .spending(request.skulls.as_ref(), request.start, request.end)
+            .await
+            .map(Payload::Spending)
     }
 
     async fn create(&self, request: Create) -> Result {
-        let created = self
+        let items = request
+            .items
+            .into_iter()
+            .map(|item| (item.skull, item.amount, item.millis, item.note));
+
+        let Some(created) = self
             .store
-            .create(
-                request
-                    .items
-                    .into_iter()
-                    .map(|item| (item.skull, item.amount, item.millis)),
-            )
-            .await?;
+            .create_metered(request.key.as_deref(), items)
+            .await?
+        else {
+            return Ok(Payload::Change(Change::Created));
+        };
 
-        self.broadcaster.send(Push::OccurrencesCreated(created));
+        self.broadcaster
+            .send(Push::OccurrencesCreated(created.occurrences));
+        for consumption in created.consumptions {
+            limits(consumption)
+                .into_iter()
+                .for_each(|push| self.broadcaster.send(push));
+        }
         Ok(Payload::Change(Change::Created))
     }
 
@@ -79,6 +141,7 @@ impl Occurrences<'_> {
                 request.skull.map(Setter::set),
                 request.amount.map(Setter::set),
                 request.millis.map(Setter::set),
+                request.note.map(Setter::set),
             )
             .await?;
 
@@ -86,10 +149,78 @@ impl Occurrences<'_> {
         Ok(Payload::Change(Change::Updated))
     }
 
+    async fn update_batch(&self, request: UpdateBatch) -> Result {
+        let updated = self
+            .store
+            .update_batch(request.items.into_iter().map(|item| {
+                (
+                    item.id,
+                    item.skull.map(Setter::set),
+                    item.amount.map(Setter::set),
+                    item.millis.map(Setter::set),
+                    item.note.map(Setter::set),
+                )
+            }))
+            .await?;
+
+        self.broadcaster.send(Push::OccurrencesUpdated(updated));
+        Ok(Payload::Change(Change::Updated))
+    }
+
     async fn delete(&self, request: types::request::occurrence::Delete) -> Result {
         self.store.delete(request.id).await?;
 
         self.broadcaster.send(Push::OccurrenceDeleted(request.id));
         Ok(Payload::Change(Change::Deleted))
     }
+
+    async fn delete_batch(&self, request: DeleteBatch) -> Result {
+        let deleted = self.store.delete_batch(request.ids).await?;
+
+        self.broadcaster.send(Push::OccurrencesDeleted(deleted));
+        Ok(Payload::Change(Change::Deleted))
+    }
+
+    async fn delete_search(&self, request: Search) -> Result {
+        let deleted = self
+            .store
+            .delete_search(
+                request.skulls.as_ref(),
+                request.tags.as_ref(),
+                request.start,
+                request.end,
+                request.limit,
+                request.cursor,
+            )
+            .await?;
+
+        if !deleted.is_empty() {
+            self.broadcaster.send(Push::OccurrencesDeleted(deleted));
+        }
+        Ok(Payload::Change(Change::Deleted))
+    }
+}
+
+/// Alerts only when the created occurrences pushed the skull over its limit
+fn limits(consumption: store::store::occurrences::Consumption) -> Vec<Push> {
+    let store::store::occurrences::Consumption {
+        skull,
+        limit,
+        before,
+        after,
+    } = consumption;
+    let consumption = Consumption {
+        skull,
+        consumed: after,
+        limit,
+    };
+
+    let mut pushes = Vec::new();
+    if before < limit && limit <= after {
+        pushes.push(Push::LimitReached(consumption.clone()));
+    }
+    if before <= limit && limit < after {
+        pushes.push(Push::LimitExceeded(consumption));
+    }
+    pushes
 }
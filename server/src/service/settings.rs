@@ -0,0 +1,39 @@
+use types::{Change, Payload, Push, request::settings::Set};
+
+use super::{Broadcaster, Service};
+
+type Result = std::result::Result<types::Payload, store::Error>;
+
+pub async fn handle(service: &Service, request: types::request::Settings) -> Result {
+    let settings = Settings::new(service);
+    match request {
+        types::request::Settings::Get => settings.get().await,
+        types::request::Settings::Set(request) => settings.set(request).await,
+    }
+}
+
+struct Settings<'a> {
+    store: store::store::settings::Settings<'a>,
+    broadcaster: &'a Broadcaster,
+}
+
+impl<'a> Settings<'a> {
+    fn new(service: &'a Service) -> Self {
+        let store = service.store.settings();
+        let broadcaster = &service.broadcaster;
+        Self { store, broadcaster }
+    }
+}
+
+impl Settings<'_> {
+    async fn get(&self) -> Result {
+        self.store.get().await.map(Payload::Settings)
+    }
+
+    async fn set(&self, request: Set) -> Result {
+        let settings = self.store.set(request.into_settings()).await?;
+
+        self.broadcaster.send(Push::SettingsChanged(settings));
+        Ok(Payload::Change(Change::Updated))
+    }
+}
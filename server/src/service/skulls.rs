@@ -2,7 +2,7 @@ use types::{
     Change, Payload, Push, Setter,
     request::{
         Skull,
-        skull::{Create, Delete, Update},
+        skull::{Archive, Create, Delete, Get, List, Merge, Unarchive, Update},
     },
 };
 
@@ -13,10 +13,14 @@ type Result = std::result::Result<types::Payload, store::Error>;
 pub async fn handle(service: &Service, request: Skull) -> Result {
     let skulls = Skulls::new(service);
     match request {
-        Skull::List => skulls.list().await,
+        Skull::List(request) => skulls.list(request).await,
+        Skull::Get(request) => skulls.get(request).await,
         Skull::Create(request) => skulls.create(request).await,
         Skull::Update(request) => skulls.update(request).await,
         Skull::Delete(request) => skulls.delete(request).await,
+        Skull::Archive(request) => skulls.archive(request).await,
+        Skull::Unarchive(request) => skulls.unarchive(request).await,
+        Skull::Merge(request) => skulls.merge(request).await,
     }
 }
 
@@ -34,8 +38,15 @@ impl<'a> Skulls<'a> {
 }
 
 impl Skulls<'_> {
-    async fn list(&self) -> Result {
-        self.store.list().await.map(Payload::Skulls)
+    async fn list(&self, request: List) -> Result {
+        self.store
+            .list(request.include_archived.unwrap_or(true))
+            .await
+            .map(Payload::Skulls)
+    }
+
+    async fn get(&self, request: Get) -> Result {
+        self.store.get(request.id).await.map(Payload::Skull)
     }
 
     async fn create(&self, request: Create) -> Result {
@@ -47,6 +58,7 @@ impl Skulls<'_> {
                 request.icon,
                 request.price,
                 request.limit,
+                request.period,
             )
             .await?;
 
@@ -64,6 +76,7 @@ impl Skulls<'_> {
                 request.icon.map(Setter::set),
                 request.price.map(Setter::set),
                 request.limit.map(Setter::set),
+                request.period.map(Setter::set),
             )
             .await?;
 
@@ -77,4 +90,28 @@ impl Skulls<'_> {
         self.broadcaster.send(Push::SkullDeleted(request.id));
         Ok(Payload::Change(Change::Deleted))
     }
+
+    async fn archive(&self, request: Archive) -> Result {
+        self.store.archive(request.id, true).await?;
+
+        self.broadcaster.send(Push::SkullArchived(request.id));
+        Ok(Payload::Change(Change::Updated))
+    }
+
+    async fn unarchive(&self, request: Unarchive) -> Result {
+        self.store.archive(request.id, false).await?;
+
+        self.broadcaster.send(Push::SkullUnarchived(request.id));
+        Ok(Payload::Change(Change::Updated))
+    }
+
+    async fn merge(&self, request: Merge) -> Result {
+        self.store.merge(request.from, request.into).await?;
+
+        self.broadcaster.send(Push::SkullMerged(types::ws::Merge {
+            from: request.from,
+            into: request.into,
+        }));
+        Ok(Payload::Change(Change::Updated))
+    }
 }
@@ -0,0 +1,80 @@
+use types::{
+    Change, Payload, Push, Setter,
+    request::{
+        Tag,
+        tag::{Attach, Create, Delete, Detach, Update},
+    },
+};
+
+use super::{Broadcaster, Service};
+
+type Result = std::result::Result<types::Payload, store::Error>;
+
+pub async fn handle(service: &Service, request: Tag) -> Result {
+    let tags = Tags::new(service);
+    match request {
+        Tag::List => tags.list().await,
+        Tag::Create(request) => tags.create(request).await,
+        Tag::Update(request) => tags.update(request).await,
+        Tag::Delete(request) => tags.delete(request).await,
+        Tag::Attach(request) => tags.attach(request).await,
+        Tag::Detach(request) => tags.detach(request).await,
+    }
+}
+
+struct Tags<'a> {
+    store: store::store::tags::Tags<'a>,
+    broadcaster: &'a Broadcaster,
+}
+
+impl<'a> Tags<'a> {
+    fn new(service: &'a Service) -> Self {
+        let store = service.store.tags();
+        let broadcaster = &service.broadcaster;
+        Self { store, broadcaster }
+    }
+}
+
+impl Tags<'_> {
+    async fn list(&self) -> Result {
+        self.store.list().await.map(Payload::Tags)
+    }
+
+    async fn create(&self, request: Create) -> Result {
+        let created = self.store.create(request.name).await?;
+
+        self.broadcaster.send(Push::TagCreated(created));
+        Ok(Payload::Change(Change::Created))
+    }
+
+    async fn update(&self, request: Update) -> Result {
+        let updated = self
+            .store
+            .update(request.id, request.name.map(Setter::set))
+            .await?;
+
+        self.broadcaster.send(Push::TagUpdated(updated));
+        Ok(Payload::Change(Change::Updated))
+    }
+
+    async fn delete(&self, request: Delete) -> Result {
+        self.store.delete(request.id).await?;
+
+        self.broadcaster.send(Push::TagDeleted(request.id));
+        Ok(Payload::Change(Change::Deleted))
+    }
+
+    async fn attach(&self, request: Attach) -> Result {
+        let updated = self.store.attach(request.id, request.skull).await?;
+
+        self.broadcaster.send(Push::TagUpdated(updated));
+        Ok(Payload::Change(Change::Updated))
+    }
+
+    async fn detach(&self, request: Detach) -> Result {
+        let updated = self.store.detach(request.id, request.skull).await?;
+
+        self.broadcaster.send(Push::TagUpdated(updated));
+        Ok(Payload::Change(Change::Updated))
+    }
+}
@@ -3,23 +3,61 @@ pub fn incoming(request: &types::Request) -> (Resource, Action) {
         types::Request::Skull(skull) => (
             Resource::Skull,
             match skull {
-                types::request::Skull::List => Action::List,
+                types::request::Skull::List(_) => Action::List,
+                types::request::Skull::Get(_) => Action::Get,
                 types::request::Skull::Create(_) => Action::Create,
                 types::request::Skull::Update(_) => Action::Update,
                 types::request::Skull::Delete(_) => Action::Delete,
+                types::request::Skull::Archive(_) => Action::Archive,
+                types::request::Skull::Unarchive(_) => Action::Unarchive,
+                types::request::Skull::Merge(_) => Action::Merge,
             },
         ),
         types::Request::Occurrence(occurrence) => (
             Resource::Occurrence,
             match occurrence {
                 types::request::Occurrence::List => Action::List,
-                types::request::Occurrence::Quick => Action::Quick,
+                types::request::Occurrence::Get(_) => Action::Get,
+                types::request::Occurrence::Quick(_) => Action::Quick,
+                types::request::Occurrence::Predict(_) => Action::Predict,
                 types::request::Occurrence::Search(_) => Action::Search,
+                types::request::Occurrence::Aggregate(_) => Action::Aggregate,
+                types::request::Occurrence::Spending(_) => Action::Spending,
                 types::request::Occurrence::Create(_) => Action::Create,
-                types::request::Occurrence::Update(_) => Action::Update,
-                types::request::Occurrence::Delete(_) => Action::Delete,
+                types::request::Occurrence::Update(_)
+                | types::request::Occurrence::UpdateBatch(_) => Action::Update,
+                types::request::Occurrence::Delete(_)
+                | types::request::Occurrence::DeleteBatch(_)
+                | types::request::Occurrence::DeleteSearch(_) => Action::Delete,
             },
         ),
+        types::Request::Tag(tag) => (
+            Resource::Tag,
+            match tag {
+                types::request::Tag::List => Action::List,
+                types::request::Tag::Create(_) => Action::Create,
+                types::request::Tag::Update(_) => Action::Update,
+                types::request::Tag::Delete(_) => Action::Delete,
+                types::request::Tag::Attach(_) => Action::Attach,
+                types::request::Tag::Detach(_) => Action::Detach,
+            },
+        ),
+        types::Request::Audit(audit) => (
+            Resource::Audit,
+            match audit {
+                types::request::Audit::List(_) => Action::List,
+            },
+        ),
+        types::Request::Settings(settings) => (
+            Resource::Settings,
+            match settings {
+                types::request::Settings::Get => Action::Get,
+                types::request::Settings::Set(_) => Action::Update,
+            },
+        ),
+        types::Request::Undo => (Resource::Journal, Action::Undo),
+        types::Request::Redo => (Resource::Journal, Action::Redo),
+        types::Request::Batch(_) => (Resource::Batch, Action::Execute),
     }
 }
 
@@ -30,9 +68,19 @@ pub fn outgoing(response: &types::Response) -> Outcome {
             types::Payload::Change(types::Change::Created) => Outcome::Created,
             types::Payload::Change(types::Change::Updated) => Outcome::Updated,
             types::Payload::Change(types::Change::Deleted) => Outcome::Deleted,
-            types::Payload::Skulls(_)
+            types::Payload::Skull(_)
+            | types::Payload::Skulls(_)
             | types::Payload::Quicks(_)
-            | types::Payload::Occurrences(_) => Outcome::Ok,
+            | types::Payload::Prediction(_)
+            | types::Payload::Occurrence(_)
+            | types::Payload::Occurrences(_)
+            | types::Payload::Page(_)
+            | types::Payload::Aggregates(_)
+            | types::Payload::Spending(_)
+            | types::Payload::Tags(_)
+            | types::Payload::Audits(_)
+            | types::Payload::Settings(_)
+            | types::Payload::Batch(_) => Outcome::Ok,
         },
     }
 }
@@ -41,6 +89,11 @@ pub fn outgoing(response: &types::Response) -> Outcome {
 pub enum Resource {
     Skull,
     Occurrence,
+    Tag,
+    Audit,
+    Settings,
+    Journal,
+    Batch,
 }
 
 impl std::fmt::Display for Resource {
@@ -48,6 +101,11 @@ impl std::fmt::Display for Resource {
         match self {
             Self::Skull => f.write_str("skull"),
             Self::Occurrence => f.write_str("occurrence"),
+            Self::Tag => f.write_str("tag"),
+            Self::Audit => f.write_str("audit"),
+            Self::Settings => f.write_str("settings"),
+            Self::Journal => f.write_str("journal"),
+            Self::Batch => f.write_str("batch"),
         }
     }
 }
@@ -55,22 +113,46 @@ impl std::fmt::Display for Resource {
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Action {
     List,
+    Get,
     Quick,
+    Predict,
     Search,
+    Aggregate,
+    Spending,
     Create,
     Update,
     Delete,
+    Archive,
+    Unarchive,
+    Merge,
+    Attach,
+    Detach,
+    Undo,
+    Redo,
+    Execute,
 }
 
 impl std::fmt::Display for Action {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::List => f.write_str("list"),
+            Self::Get => f.write_str("get"),
             Self::Quick => f.write_str("quick"),
+            Self::Predict => f.write_str("predict"),
             Self::Search => f.write_str("search"),
+            Self::Aggregate => f.write_str("aggregate"),
+            Self::Spending => f.write_str("spending"),
             Self::Create => f.write_str("create"),
             Self::Update => f.write_str("update"),
             Self::Delete => f.write_str("delete"),
+            Self::Archive => f.write_str("archive"),
+            Self::Unarchive => f.write_str("unarchive"),
+            Self::Merge => f.write_str("merge"),
+            Self::Attach => f.write_str("attach"),
+            Self::Detach => f.write_str("detach"),
+            Self::Undo => f.write_str("undo"),
+            Self::Redo => f.write_str("redo"),
+            Self::Execute => f.write_str("execute"),
         }
     }
 }
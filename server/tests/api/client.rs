@@ -1,8 +1,15 @@
 use crate::{server, utils};
 
+#[cfg(unix)]
+pub type Socket = tokio_tungstenite::WebSocketStream<tokio::net::UnixStream>;
+#[cfg(not(unix))]
+pub type Socket = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+
 #[derive(Debug, Clone)]
 pub struct Client {
     client: reqwest::Client,
+    #[cfg(unix)]
+    socket: String,
     #[cfg(not(unix))]
     uri: std::sync::Arc<String>,
 }
@@ -15,6 +22,7 @@ impl From<&server::Server> for Client {
                 .unix_socket(server.uri())
                 .build()
                 .unwrap(),
+            socket: server.uri(),
         }
     }
 
@@ -52,6 +60,17 @@ impl Client {
         self.client.execute(request).await.unwrap()
     }
 
+    pub async fn post_with(
+        &self,
+        path_and_query: impl AsRef<str>,
+        body: impl Into<hyper::body::Bytes>,
+        f: impl Fn(&mut reqwest::Request),
+    ) -> reqwest::Response {
+        let mut request = self.post_request(path_and_query, body);
+        f(&mut request);
+        self.client.execute(request).await.unwrap()
+    }
+
     pub async fn patch(
         &self,
         path_and_query: impl AsRef<str>,
@@ -93,6 +112,37 @@ impl Client {
     }
 }
 
+impl Client {
+    /// Opens a text websocket, which receives the pushes of the test user
+    pub async fn socket(&self) -> Socket {
+        use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+        #[cfg(unix)]
+        let (uri, stream) = (
+            "ws://localhost/ws/text",
+            tokio::net::UnixStream::connect(&self.socket).await.unwrap(),
+        );
+        #[cfg(not(unix))]
+        let (uri, stream) = (
+            format!("ws://{authority}/ws/text", authority = self.uri.as_str()),
+            tokio::net::TcpStream::connect(self.uri.as_str())
+                .await
+                .unwrap(),
+        );
+
+        let mut request = uri.into_client_request().unwrap();
+        request.headers_mut().insert(
+            utils::USER_HEADER,
+            hyper::header::HeaderValue::from_static(utils::USER),
+        );
+
+        tokio_tungstenite::client_async(request, stream)
+            .await
+            .unwrap()
+            .0
+    }
+}
+
 impl Client {
     fn request(
         &self,
@@ -198,9 +198,8 @@ impl Populator {
     }
 
     async fn populate(&self) {
-        const SKULL: &str =
-            r#"{"name":"skull$","color":$,"icon":"icon$","price":0.$,"limit":null}"#;
-        const OCCURRENCE: &str = r#"{"skull":$,"amount":$.0,"millis":$}"#;
+        const SKULL: &str = r#"{"name":"skull$","color":$,"icon":"icon$","price":0.$,"limit":null,"period":"day","archived":false}"#;
+        const OCCURRENCE: &str = r#"{"skull":$,"amount":$.0,"millis":$,"note":null}"#;
 
         self.insert_items("skull", SKULL).await;
         self.check_items("skull", SKULL).await;
@@ -5,7 +5,8 @@ use crate::{
     client::Client,
     server,
     utils::{
-        EMPTY_USER, USER_HEADER, build_occurrence_payload, build_skull_payload, eq, extract_body,
+        EMPTY_USER, USER_HEADER, build_occurrence_payload, build_page_payload, build_skull_payload,
+        eq, extract_body, extract_pushes,
     },
 };
 
@@ -37,15 +38,50 @@ pub fn test<'a>(
             test!(search),
             test!(search_not_found),
             test!(search_limit),
+            test!(search_cursor),
+            test!(get),
+            test!(get_not_found),
+            test!(aggregate),
+            test!(aggregate_missing_bucket),
+            test!(spending),
+            test!(quick),
+            test!(quick_bad_query),
+            test!(predict),
+            test!(predict_missing_millis),
             test!(update),
+            test!(update_note),
             test!(update_no_change),
             test!(update_not_found),
             test!(update_constraint),
             test!(update_conflict),
             test!(update_bad_payload),
+            test!(update_batch),
+            test!(update_batch_rollback),
             test!(delete),
             test!(delete_not_found),
             test!(delete_rejected),
+            test!(delete_batch_not_found),
+            test!(delete_search_no_filters),
+            test!(archive),
+            test!(archive_not_found),
+            test!(archive_bad_query),
+            test!(merge),
+            test!(merge_not_found),
+            test!(tags),
+            test!(tags_not_found),
+            test!(audit),
+            test!(audit_bad_query),
+            test!(undo),
+            test!(redo_empty),
+            test!(delete_batch),
+            test!(delete_search),
+            test!(batch),
+            test!(batch_rollback),
+            test!(create_idempotent),
+            test!(create_idempotent_bad_header),
+            test!(settings),
+            test!(settings_invalid),
+            test!(limit_pushes),
         ]
     })
 }
@@ -200,15 +236,147 @@ async fn search_limit(client: Client) {
         let response = client.get(format!("occurrence?limit={i}")).await;
 
         let payload = match i {
-            0 => build_occurrence_payload([]),
-            1 => build_occurrence_payload([3]),
-            2 => build_occurrence_payload([3, 2]),
-            _ => build_occurrence_payload([3, 2, 1]),
+            0 => build_page_payload([], None),
+            1 => build_page_payload([3], Some(3)),
+            2 => build_page_payload([3, 2], Some(2)),
+            3 => build_page_payload([3, 2, 1], Some(1)),
+            _ => build_page_payload([3, 2, 1], None),
         };
         check!(eq(response, StatusCode::OK, payload));
     }
 }
 
+async fn search_cursor(client: Client) {
+    let response = client.get("occurrence?limit=2").await;
+    assert_eq!(
+        response.headers().get(hyper::header::LINK).unwrap(),
+        r#"</occurrence?limit=2&cursor=2,2>; rel="next""#
+    );
+    check!(eq(
+        response,
+        StatusCode::OK,
+        build_page_payload([3, 2], Some(2))
+    ));
+
+    let response = client.get("occurrence?limit=2&cursor=2,2").await;
+    assert!(response.headers().get(hyper::header::LINK).is_none());
+    check!(eq(response, StatusCode::OK, build_page_payload([1], None)));
+}
+
+async fn get(client: Client) {
+    let response = client.get("skull/2").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"skull":{"id":2,"name":"skull2","color":2,"icon":"icon2","price":0.2,"limit":null,"period":"day","archived":false}}"#
+    ));
+
+    let response = client.get("occurrence/2").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"occurrence":{"id":2,"skull":2,"amount":2.0,"millis":2,"note":null}}"#
+    ));
+}
+
+async fn get_not_found(client: Client) {
+    let response = client.get("skull/27").await;
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+
+    let response = client.get("occurrence/27").await;
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+}
+
+async fn aggregate(client: Client) {
+    let response = client
+        .get("occurrence/aggregate?skulls=1,3&bucket=day")
+        .await;
+
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"aggregates":[{"skull":1,"millis":0,"count":1,"sum":1.0,"min":1.0,"max":1.0},{"skull":3,"millis":0,"count":1,"sum":3.0,"min":3.0,"max":3.0}]}"#
+    ));
+}
+
+async fn aggregate_missing_bucket(client: Client) {
+    let response = client.get("occurrence/aggregate?skulls=1").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Missing field `bucket`"
+    ));
+}
+
+async fn spending(client: Client) {
+    let response = client.get("occurrence/spending?start=2").await;
+
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"spending":[{"skull":2,"amount":2.0,"cost":0.4},{"skull":3,"amount":3.0,"cost":0.90000004}]}"#
+    ));
+}
+
+async fn quick(client: Client) {
+    let response = client
+        .get("occurrence/quick?halfLife=1000&limit=2&hour=0")
+        .await;
+
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"quicks":[{"skull":3,"amount":3.0},{"skull":2,"amount":2.0}]}"#
+    ));
+}
+
+async fn quick_bad_query(client: Client) {
+    let response = client.get("occurrence/quick?hour=noon").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Failed to parse field `hour`: invalid digit found in string"
+    ));
+
+    let response = client.get("occurrence/quick?hour=24").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        r#"{"error":{"kind":"BadRequest","message":"invalid value for `hour`"}}"#
+    ));
+}
+
+async fn predict(client: Client) {
+    let response = client.get("occurrence/predict?millis=0").await;
+
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"prediction":{"skull":3,"amount":3.0}}"#
+    ));
+}
+
+async fn predict_missing_millis(client: Client) {
+    let response = client.get("occurrence/predict").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Missing field `millis`"
+    ));
+}
+
 async fn update(client: Client) {
     let response = client.get("occurrence").await;
     let original = extract_body(response).await;
@@ -231,6 +399,26 @@ async fn update(client: Client) {
     check!(eq(response, StatusCode::OK, modified));
 }
 
+async fn update_note(client: Client) {
+    let response = client.get("occurrence").await;
+    let original = extract_body(response).await;
+
+    let response = client
+        .patch("occurrence", r#"{"id":3,"note":{"set":"note"}}"#)
+        .await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    let modified = original.replacen("\"note\":null", "\"note\":\"note\"", 1);
+    check!(eq(response, StatusCode::OK, modified));
+
+    let response = client.patch("occurrence", r#"{"id":3,"note":{}}"#).await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, original));
+}
+
 async fn update_no_change(client: Client) {
     let response = client.patch("occurrence", "{\"id\": 3}").await;
     check!(eq(
@@ -313,6 +501,54 @@ async fn update_bad_payload(client: Client) {
     ));
 }
 
+async fn update_batch(client: Client) {
+    let response = client.get("occurrence").await;
+    let original = extract_body(response).await;
+
+    let response = client
+        .patch(
+            "occurrence/batch",
+            r#"{"items":[{"id":3,"note":{"set":"note"}},{"id":2,"note":{"set":"note"}}]}"#,
+        )
+        .await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    let modified = original.replacen("\"note\":null", "\"note\":\"note\"", 2);
+    check!(eq(response, StatusCode::OK, modified));
+
+    let response = client
+        .patch(
+            "occurrence/batch",
+            r#"{"items":[{"id":3,"note":{}},{"id":2,"note":{}}]}"#,
+        )
+        .await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, original));
+}
+
+async fn update_batch_rollback(client: Client) {
+    let response = client.get("occurrence").await;
+    let original = extract_body(response).await;
+
+    let response = client
+        .patch(
+            "occurrence/batch",
+            r#"{"items":[{"id":3,"amount":{"set":27}},{"id":27,"amount":{"set":27}}]}"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, original));
+}
+
 async fn delete(client: Client) {
     let response = client.delete("occurrence", "{\"id\":3}").await;
     check!(eq(response, StatusCode::NO_CONTENT, ""));
@@ -344,3 +580,458 @@ async fn delete_rejected(client: Client) {
         "{\"error\":{\"kind\":\"BadRequest\",\"message\":\"entry fails constraint check: FOREIGN KEY constraint failed\"}}"
     ));
 }
+
+async fn delete_batch_not_found(client: Client) {
+    let response = client.delete("occurrence/batch", "{\"ids\":[2,27]}").await;
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+
+    let response = client.get("occurrence").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        build_occurrence_payload([2, 1])
+    ));
+}
+
+async fn delete_search_no_filters(client: Client) {
+    let response = client.delete("occurrence/search", "").await;
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "{\"error\":{\"kind\":\"BadRequest\",\"message\":\"invalid value for `search`\"}}"
+    ));
+}
+
+async fn archive(client: Client) {
+    let response = client.get("skull").await;
+    let original = extract_body(response).await;
+
+    let response = client.post("skull/archive", "{\"id\":1}").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("skull").await;
+    let archived = original.replacen("\"archived\":false", "\"archived\":true", 1);
+    check!(eq(response, StatusCode::OK, archived));
+
+    let response = client.get("skull?includeArchived=false").await;
+    let active = extract_body(response).await;
+    assert!(!active.contains("\"archived\":true"), "{active}");
+    assert!(!active.contains("\"id\":1,"), "{active}");
+    assert!(active.contains("\"id\":2,"), "{active}");
+
+    let response = client.post("skull/unarchive", "{\"id\":1}").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("skull").await;
+    check!(eq(response, StatusCode::OK, original));
+}
+
+async fn archive_not_found(client: Client) {
+    let response = client.post("skull/archive", "{\"id\":27}").await;
+
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+}
+
+async fn archive_bad_query(client: Client) {
+    let response = client.get("skull?includeArchived=bla").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Invalid value for field `includeArchived`"
+    ));
+}
+
+async fn merge(client: Client) {
+    let response = client.get("skull").await;
+    let original = extract_body(response).await;
+
+    let response = client.post("skull/merge", "{\"from\":2,\"into\":1}").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("skull").await;
+    let merged = original.replacen(
+        r#"{"id":2,"name":"skull2","color":2,"icon":"icon2","price":0.2,"limit":null,"period":"day","archived":false},"#,
+        "",
+        1,
+    );
+    check!(eq(response, StatusCode::OK, merged));
+
+    let response = client.get("occurrence").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        build_occurrence_payload([2, 1]).replacen("\"skull\":2", "\"skull\":1", 1)
+    ));
+}
+
+async fn merge_not_found(client: Client) {
+    let response = client.post("skull/merge", "{\"from\":1,\"into\":27}").await;
+
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+}
+
+async fn tags(client: Client) {
+    let response = client.post("tag", r#"{"name":"group"}"#).await;
+    check!(eq(
+        response,
+        StatusCode::CREATED,
+        "{\"change\":\"created\"}"
+    ));
+
+    let response = client.post("tag/attach", r#"{"id":1,"skull":1}"#).await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("tag").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"tags":[{"id":1,"name":"group","skulls":[1]}]}"#
+    ));
+
+    let response = client.get("occurrence?tags=group&end=1").await;
+    check!(eq(response, StatusCode::OK, build_occurrence_payload([1])));
+
+    let response = client.delete("tag", r#"{"id":1}"#).await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("tag").await;
+    check!(eq(response, StatusCode::OK, r#"{"tags":[]}"#));
+}
+
+async fn tags_not_found(client: Client) {
+    let response = client.post("tag/attach", r#"{"id":27,"skull":1}"#).await;
+
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+}
+
+async fn audit(client: Client) {
+    let response = client.get("audit?limit=1").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = extract_body(response).await;
+    assert!(body.starts_with(r#"{"audits":[{"id":"#), "{body}");
+    assert!(body.contains(r#""actor":"bloink","millis":"#), "{body}");
+    assert!(
+        body.contains(r#""entity":"tag","mutation":"delete","before":"{\"id\":1,\"name\":\"group\",\"skulls\":[1]}","after":null}]}"#),
+        "{body}"
+    );
+
+    let response = client.get("audit?since=99999999999999").await;
+    check!(eq(response, StatusCode::OK, r#"{"audits":[]}"#));
+}
+
+async fn audit_bad_query(client: Client) {
+    let response = client.get("audit?limit=bla").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Failed to parse field `limit`: invalid digit found in string"
+    ));
+}
+
+async fn undo(client: Client) {
+    let response = client.post("undo", "").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("tag").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"tags":[{"id":1,"name":"group","skulls":[1]}]}"#
+    ));
+
+    let response = client.post("redo", "").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("tag").await;
+    check!(eq(response, StatusCode::OK, r#"{"tags":[]}"#));
+}
+
+async fn redo_empty(client: Client) {
+    let response = client.post("redo", "").await;
+
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"nothing to redo\"}}"
+    ));
+}
+
+async fn delete_batch(client: Client) {
+    let response = client.delete("occurrence/batch", "{\"ids\":[2]}").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, build_occurrence_payload([1])));
+}
+
+async fn delete_search(client: Client) {
+    let response = client.delete("occurrence/search?skulls=1", "").await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, "{\"occurrences\":[]}"));
+}
+
+async fn batch(client: Client) {
+    let response = client
+        .post(
+            "batch",
+            r#"[
+                {"skull":{"create":{"name":"batch","color":72,"icon":"batch","price":0.5,"limit":null}}},
+                {"occurrence":{"create":{"items":[{"skull":1,"amount":27,"millis":27}]}}}
+            ]"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"batch":[{"change":"created"},{"change":"created"}]}"#
+    ));
+
+    let response = client.get("occurrence").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"occurrences":[{"id":1,"skull":1,"amount":27.0,"millis":27,"note":null}]}"#
+    ));
+}
+
+async fn batch_rollback(client: Client) {
+    let response = client.get("occurrence").await;
+    let original = extract_body(response).await;
+
+    let response = client
+        .post(
+            "batch",
+            r#"[
+                {"occurrence":{"create":{"items":[{"skull":1,"amount":72,"millis":72}]}}},
+                {"occurrence":{"delete":{"id":27}}}
+            ]"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::NOT_FOUND,
+        "{\"error\":{\"kind\":\"NotFound\",\"message\":\"entry not found for `27`\"}}"
+    ));
+
+    let response = client.get("occurrence").await;
+    check!(eq(response, StatusCode::OK, original));
+}
+
+async fn create_idempotent(client: Client) {
+    for _ in 0..2 {
+        let response = client
+            .post_with(
+                "occurrence",
+                r#"{"items":[{"skull":1,"amount":3,"millis":3}]}"#,
+                |r| {
+                    r.headers_mut().insert(
+                        "Idempotency-Key",
+                        hyper::header::HeaderValue::from_static("retry"),
+                    );
+                },
+            )
+            .await;
+        check!(eq(
+            response,
+            StatusCode::CREATED,
+            "{\"change\":\"created\"}"
+        ));
+    }
+
+    let response = client
+        .post(
+            "occurrence",
+            r#"{"items":[{"skull":1,"amount":3,"millis":3}],"key":"retry"}"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::CREATED,
+        "{\"change\":\"created\"}"
+    ));
+
+    let response = client
+        .post(
+            "occurrence",
+            r#"{"items":[{"skull":1,"amount":4,"millis":3}],"key":"retry"}"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        r#"{"error":{"kind":"BadRequest","message":"invalid value for `key`"}}"#
+    ));
+
+    let response = client.get("occurrence").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"occurrences":[{"id":1,"skull":1,"amount":27.0,"millis":27,"note":null},{"id":2,"skull":1,"amount":3.0,"millis":3,"note":null}]}"#
+    ));
+}
+
+async fn create_idempotent_bad_header(client: Client) {
+    let response = client
+        .post_with(
+            "occurrence",
+            r#"{"items":[{"skull":1,"amount":3,"millis":3}]}"#,
+            |r| {
+                r.headers_mut().insert(
+                    "Idempotency-Key",
+                    hyper::header::HeaderValue::from_bytes(b"\xff").unwrap(),
+                );
+            },
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "Invalid value for header `Idempotency-Key`"
+    ));
+}
+
+async fn settings(client: Client) {
+    let response = client.get("settings").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"settings":{"timezone":0,"dayStart":0,"window":2,"quickCount":12,"currency":null}}"#
+    ));
+
+    let response = client
+        .patch(
+            "settings",
+            r#"{"timezone":{"set":-180},"dayStart":{"set":5},"currency":{"set":"EUR"}}"#,
+        )
+        .await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+
+    let response = client.get("settings").await;
+    check!(eq(
+        response,
+        StatusCode::OK,
+        r#"{"settings":{"timezone":-180,"dayStart":5,"window":2,"quickCount":12,"currency":"EUR"}}"#
+    ));
+
+    let response = client
+        .patch(
+            "settings",
+            r#"{"timezone":{"set":0},"dayStart":{"set":0},"currency":{"set":null}}"#,
+        )
+        .await;
+    check!(eq(response, StatusCode::NO_CONTENT, ""));
+}
+
+async fn settings_invalid(client: Client) {
+    let response = client.patch("settings", r#"{"dayStart":{"set":24}}"#).await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "{\"error\":{\"kind\":\"BadRequest\",\"message\":\"invalid value for `dayStart`\"}}"
+    ));
+
+    let response = client.patch("settings", "{}").await;
+
+    check!(eq(
+        response,
+        StatusCode::BAD_REQUEST,
+        "{\"error\":{\"kind\":\"BadRequest\",\"message\":\"no changes specified\"}}"
+    ));
+}
+
+async fn limit_pushes(client: Client) {
+    let response = client
+        .post(
+            "skull",
+            r#"{
+                "name": "limited",
+                "color": 7,
+                "icon": "icon7",
+                "price": 0.7,
+                "limit": 2,
+                "period": {"hours": 1}
+            }"#,
+        )
+        .await;
+    check!(eq(
+        response,
+        StatusCode::CREATED,
+        "{\"change\":\"created\"}"
+    ));
+
+    let response = client.get("skull").await;
+    let Ok(types::Response::Payload(types::Payload::Skulls(skulls))) =
+        serde_json::from_str(&extract_body(response).await)
+    else {
+        panic!("Expected skulls");
+    };
+    let skull = skulls.iter().find(|s| s.name == "limited").unwrap().id;
+
+    let mut socket = client.socket().await;
+    let mut register = async |millis| {
+        let response = client
+            .post(
+                "occurrence",
+                format!(
+                    r#"{{"items":[{{"skull":{skull},"amount":1,"millis":{millis}}}]}}"#,
+                    skull = i64::from(skull),
+                ),
+            )
+            .await;
+        check!(eq(
+            response,
+            StatusCode::CREATED,
+            "{\"change\":\"created\"}"
+        ));
+
+        extract_pushes(&mut socket)
+            .await
+            .into_iter()
+            .filter(|push| {
+                matches!(
+                    push,
+                    types::ws::Push::LimitReached(_) | types::ws::Push::LimitExceeded(_)
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let consumption = |consumed| types::ws::Consumption {
+        skull,
+        consumed,
+        limit: 2.0,
+    };
+
+    assert_eq!(register(1_000).await, Vec::new());
+    assert_eq!(
+        register(2_000).await,
+        vec![types::ws::Push::LimitReached(consumption(2.0))]
+    );
+    assert_eq!(
+        register(3_000).await,
+        vec![types::ws::Push::LimitExceeded(consumption(3.0))]
+    );
+    assert_eq!(register(4_000).await, Vec::new());
+}
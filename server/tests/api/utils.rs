@@ -74,9 +74,26 @@ pub async fn extract_body(response: reqwest::Response) -> String {
     response.text().await.unwrap()
 }
 
+/// Collects the pushes received by `socket` until it goes quiet
+pub async fn extract_pushes(socket: &mut crate::client::Socket) -> Vec<types::ws::Push> {
+    use futures_util::StreamExt;
+
+    let mut pushes = Vec::new();
+    while let Ok(Some(message)) =
+        tokio::time::timeout(std::time::Duration::from_millis(500), socket.next()).await
+    {
+        if let tokio_tungstenite::tungstenite::Message::Text(text) = message.unwrap()
+            && let types::Message::Push(push) = serde_json::from_str(text.as_str()).unwrap()
+        {
+            pushes.push(push);
+        }
+    }
+    pushes
+}
+
 pub fn build_skull_payload<const N: usize>(ids: [u8; N]) -> String {
     let items = ids
-        .map(|j| format!(r#"{{"id":{j},"name":"skull{j}","color":{j},"icon":"icon{j}","price":0.{j},"limit":null}}"#))
+        .map(|j| format!(r#"{{"id":{j},"name":"skull{j}","color":{j},"icon":"icon{j}","price":0.{j},"limit":null,"period":"day","archived":false}}"#))
         .into_iter()
         .collect::<Vec<_>>().join(",");
 
@@ -84,11 +101,24 @@ pub fn build_skull_payload<const N: usize>(ids: [u8; N]) -> String {
 }
 
 pub fn build_occurrence_payload<const N: usize>(ids: [u8; N]) -> String {
-    let items = ids
-        .map(|j| format!(r#"{{"id":{j},"skull":{j},"amount":{j}.0,"millis":{j}}}"#))
-        .into_iter()
-        .collect::<Vec<_>>()
-        .join(",");
+    let items = build_occurrence_items(ids);
 
     format!("{{\"occurrences\":[{items}]}}")
 }
+
+pub fn build_page_payload<const N: usize>(ids: [u8; N], next: Option<u8>) -> String {
+    let items = build_occurrence_items(ids);
+    let next = next.map_or_else(
+        || String::from("null"),
+        |j| format!(r#"{{"millis":{j},"id":{j}}}"#),
+    );
+
+    format!("{{\"page\":{{\"occurrences\":[{items}],\"next\":{next}}}}}")
+}
+
+fn build_occurrence_items<const N: usize>(ids: [u8; N]) -> String {
+    ids.map(|j| format!(r#"{{"id":{j},"skull":{j},"amount":{j}.0,"millis":{j},"note":null}}"#))
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join(",")
+}
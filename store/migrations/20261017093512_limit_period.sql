@@ -0,0 +1,2 @@
+-- NOTE: Positive values are rolling hours, while -1, -2 and -3 are calendar day, week and month
+ALTER TABLE skulls ADD COLUMN "period" INTEGER NOT NULL DEFAULT 18 CHECK("period" > 0 OR "period" IN (-1, -2, -3));
@@ -0,0 +1,14 @@
+-- Prices
+CREATE TABLE prices (
+  "id"             INTEGER NOT NULL PRIMARY KEY,
+  "skull"          INTEGER NOT NULL,
+  "price"          REAL    NOT NULL CHECK("price" >= 0),
+  "effective_from" INTEGER NOT NULL,
+
+  FOREIGN KEY(skull) REFERENCES skulls(id) ON DELETE CASCADE,
+
+  UNIQUE(skull, effective_from)
+);
+
+-- NOTE: The earliest price of a skull also applies to any occurrence before it
+INSERT INTO prices (skull, price, effective_from) SELECT id, price, 0 FROM skulls;
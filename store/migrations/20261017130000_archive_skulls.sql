@@ -0,0 +1 @@
+ALTER TABLE skulls ADD COLUMN "archived" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1 @@
+ALTER TABLE occurrences ADD COLUMN "note" TEXT;
@@ -0,0 +1,16 @@
+-- Tags
+CREATE TABLE tags (
+  "id"   INTEGER NOT NULL PRIMARY KEY,
+  "name" TEXT    NOT NULL UNIQUE CHECK(LENGTH(TRIM("name")) > 0)
+);
+
+-- Skull tags
+CREATE TABLE skull_tags (
+  "skull" INTEGER NOT NULL,
+  "tag"   INTEGER NOT NULL,
+
+  FOREIGN KEY(skull) REFERENCES skulls(id) ON DELETE CASCADE,
+  FOREIGN KEY(tag)   REFERENCES tags(id)   ON DELETE CASCADE,
+
+  PRIMARY KEY(skull, tag)
+);
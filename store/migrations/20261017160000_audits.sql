@@ -0,0 +1,12 @@
+-- Audits
+CREATE TABLE audits (
+  "id"       INTEGER NOT NULL PRIMARY KEY,
+  "actor"    TEXT,
+  "millis"   INTEGER NOT NULL,
+  "entity"   TEXT    NOT NULL CHECK("entity" IN ('skull', 'occurrence', 'tag')),
+  "mutation" TEXT    NOT NULL CHECK("mutation" IN ('create', 'update', 'delete')),
+  "before"   TEXT,
+  "after"    TEXT
+);
+
+CREATE INDEX audits_millis ON audits(millis);
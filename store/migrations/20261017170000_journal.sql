@@ -0,0 +1,9 @@
+-- Journal
+CREATE TABLE operations (
+  "id"     INTEGER NOT NULL PRIMARY KEY,
+  "undone" BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+ALTER TABLE audits ADD COLUMN "operation" INTEGER REFERENCES operations(id) ON DELETE SET NULL;
+
+CREATE INDEX audits_operation ON audits(operation);
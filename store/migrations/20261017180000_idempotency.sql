@@ -0,0 +1,8 @@
+-- Idempotency
+CREATE TABLE idempotency_keys (
+  "key"    TEXT    NOT NULL PRIMARY KEY,
+  "millis" INTEGER NOT NULL,
+  "hash"   INTEGER NOT NULL
+);
+
+CREATE INDEX idempotency_keys_millis ON idempotency_keys(millis);
@@ -0,0 +1,9 @@
+-- Settings
+-- NOTE: There is a single row, holding the timezone as minutes east of UTC and the hour a day starts at
+CREATE TABLE settings (
+  "id"        INTEGER NOT NULL PRIMARY KEY CHECK("id" = 0),
+  "timezone"  INTEGER NOT NULL DEFAULT 0 CHECK("timezone" > -1440 AND "timezone" < 1440),
+  "day_start" INTEGER NOT NULL DEFAULT 0 CHECK("day_start" >= 0 AND "day_start" < 24)
+);
+
+INSERT INTO settings ("id") VALUES (0);
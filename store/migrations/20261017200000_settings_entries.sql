@@ -0,0 +1,16 @@
+-- Settings as key/value pairs
+-- NOTE: Values are JSON, and any missing key falls back to its default
+ALTER TABLE settings RENAME TO settings_row;
+
+CREATE TABLE settings (
+  "key"   TEXT NOT NULL PRIMARY KEY,
+  "value" TEXT NOT NULL
+);
+
+INSERT INTO settings ("key", "value")
+SELECT 'timezone', CAST("timezone" AS TEXT) FROM settings_row WHERE "timezone" != 0;
+
+INSERT INTO settings ("key", "value")
+SELECT 'dayStart', CAST("day_start" AS TEXT) FROM settings_row WHERE "day_start" != 0;
+
+DROP TABLE settings_row;
@@ -10,11 +10,17 @@ pub enum Error {
     NotFound(types::Id),
     #[error("no changes specified")]
     NoChanges,
+    #[error("nothing to {0}")]
+    EmptyJournal(&'static str),
 
+    #[error("batch is still in use")]
+    BatchInUse,
     #[error(transparent)]
     Sqlx(sqlx::Error),
     #[error(transparent)]
     Migration(#[from] sqlx::migrate::MigrateError),
+    #[error(transparent)]
+    Serialization(#[from] serde_json::Error),
     #[error("referenced ID does not exist")]
     ForeignKey,
     #[error("entry fails constraint check: {0}")]
@@ -34,9 +40,11 @@ impl Error {
             | Self::Constraint(_)
             | Self::DuplicateEntry(_) => types::Kind::BadRequest,
 
-            Self::NotFound(_) => types::Kind::NotFound,
+            Self::NotFound(_) | Self::EmptyJournal(_) => types::Kind::NotFound,
 
-            Self::Sqlx(_) | Self::Migration(_) => types::Kind::InternalError,
+            Self::BatchInUse | Self::Sqlx(_) | Self::Migration(_) | Self::Serialization(_) => {
+                types::Kind::InternalError
+            }
         }
     }
 }
@@ -0,0 +1,287 @@
+use crate::{Result, Store};
+
+pub struct Audits<'a> {
+    store: &'a Store,
+}
+
+impl<'a> Audits<'a> {
+    pub(super) fn new(store: &'a Store) -> Self {
+        Self { store }
+    }
+}
+
+impl Audits<'_> {
+    #[tracing::instrument(skip(self), err)]
+    pub async fn list(
+        &self,
+        since: Option<types::Millis>,
+        limit: Option<usize>,
+    ) -> Result<Vec<types::Audit>> {
+        let mut builder = sqlx::QueryBuilder::new(
+            r#"
+            SELECT
+                "id",
+                "actor",
+                "millis",
+                "entity",
+                "mutation",
+                "before",
+                "after"
+            FROM
+                audits
+            "#,
+        );
+
+        if let Some(since) = since {
+            builder.push(" WHERE millis >= ").push_bind(since);
+        }
+
+        builder.push(" ORDER BY millis DESC, id DESC");
+
+        if let Some(limit) = limit {
+            builder.push(" LIMIT ").push_bind(
+                // allow(clippy::cast_possible_wrap): Limits are way below i64::MAX
+                #[allow(clippy::cast_possible_wrap)]
+                {
+                    limit as i64
+                },
+            );
+        }
+
+        builder
+            .build_query_as()
+            .fetch_all(self.store.acquire().await?.as_mut())
+            .await
+            .map_err(Into::into)
+    }
+}
+
+pub(super) async fn record<T: serde::Serialize>(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: Option<super::journal::Operation>,
+    entity: types::Entity,
+    mutation: types::Mutation,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result {
+    let actor = store.actor.as_deref();
+    let millis = types::Millis::from(chrono::Utc::now());
+    let before = before.map(serde_json::to_string).transpose()?;
+    let after = after.map(serde_json::to_string).transpose()?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO audits (
+            actor,
+            millis,
+            entity,
+            mutation,
+            before,
+            after,
+            operation
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            $7
+        )
+        "#,
+        actor,
+        millis,
+        entity,
+        mutation,
+        before,
+        after,
+        operation,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(value: i64) -> types::Millis {
+        types::Millis::from(value)
+    }
+
+    #[tokio::test]
+    async fn record_skull() {
+        let store = Store::in_memory(1).await.unwrap().with_actor("user");
+
+        let created = store
+            .skulls()
+            .create("one", 1, "icon", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let updated = store
+            .skulls()
+            .update(
+                created.id,
+                Some("two"),
+                None,
+                None::<&str>,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        store.skulls().delete(created.id).await.unwrap();
+
+        let audits = store.audits().list(None, None).await.unwrap();
+        let audits = audits
+            .into_iter()
+            .map(|a| {
+                (
+                    a.actor,
+                    a.entity,
+                    a.mutation,
+                    a.before
+                        .map(|b| serde_json::from_str::<types::Skull>(&b).unwrap()),
+                    a.after
+                        .map(|a| serde_json::from_str::<types::Skull>(&a).unwrap()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let actor = Some(String::from("user"));
+        assert_eq!(
+            audits,
+            vec![
+                (
+                    actor.clone(),
+                    types::Entity::Skull,
+                    types::Mutation::Delete,
+                    Some(updated.clone()),
+                    None
+                ),
+                (
+                    actor.clone(),
+                    types::Entity::Skull,
+                    types::Mutation::Update,
+                    Some(created.clone()),
+                    Some(updated)
+                ),
+                (
+                    actor,
+                    types::Entity::Skull,
+                    types::Mutation::Create,
+                    None,
+                    Some(created)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn record_occurrence() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skull = store
+            .skulls()
+            .create("one", 1, "icon", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let created = store
+            .occurrences()
+            .create([(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap()
+            .remove(0);
+        let updated = store
+            .occurrences()
+            .update(created.id, None, Some(2.0), None, None)
+            .await
+            .unwrap();
+        store.occurrences().delete(created.id).await.unwrap();
+
+        let audits = store
+            .audits()
+            .list(None, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|a| a.entity == types::Entity::Occurrence)
+            .map(|a| {
+                (
+                    a.actor,
+                    a.mutation,
+                    a.before
+                        .map(|b| serde_json::from_str::<types::Occurrence>(&b).unwrap()),
+                    a.after
+                        .map(|a| serde_json::from_str::<types::Occurrence>(&a).unwrap()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            audits,
+            vec![
+                (None, types::Mutation::Delete, Some(updated.clone()), None),
+                (
+                    None,
+                    types::Mutation::Update,
+                    Some(created.clone()),
+                    Some(updated)
+                ),
+                (None, types::Mutation::Create, None, Some(created)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_failed() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skull = store
+            .skulls()
+            .create("one", 1, "icon", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        store
+            .skulls()
+            .create("one", 1, "icon", 1.0, None, types::Period::default())
+            .await
+            .unwrap_err();
+        store.skulls().delete(skull.id).await.unwrap();
+        store.skulls().delete(skull.id).await.unwrap_err();
+
+        let audits = store.audits().list(None, None).await.unwrap();
+        assert_eq!(audits.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn list_since() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        for name in ["one", "two", "three"] {
+            store.tags().create(name).await.unwrap();
+        }
+
+        let audits = store.audits().list(None, None).await.unwrap();
+        assert_eq!(audits.len(), 3);
+
+        let limited = store.audits().list(None, Some(2)).await.unwrap();
+        assert_eq!(limited, audits[..2]);
+
+        let since = store
+            .audits()
+            .list(Some(audits[0].millis), None)
+            .await
+            .unwrap();
+        assert!(since.iter().all(|a| a.millis >= audits[0].millis));
+        assert!(!since.is_empty());
+
+        let future = millis(i64::from(audits[0].millis) + 1_000_000);
+        let future = store.audits().list(Some(future), None).await.unwrap();
+        assert!(future.is_empty());
+    }
+}
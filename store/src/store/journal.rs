@@ -0,0 +1,986 @@
+use crate::{Error, Result, Store};
+
+pub struct Journal<'a> {
+    store: &'a Store,
+}
+
+impl<'a> Journal<'a> {
+    pub(super) fn new(store: &'a Store) -> Self {
+        Self { store }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, sqlx::Type)]
+#[sqlx(transparent)]
+pub(super) struct Operation(i64);
+
+/// A change applied while undoing or redoing an operation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    Created(Entry),
+    Updated(Entry),
+    Deleted(Entry),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    Skull(types::Skull),
+    Occurrence(types::Occurrence),
+    Tag(types::Tag),
+}
+
+impl Journal<'_> {
+    #[tracing::instrument(skip(self), err)]
+    pub async fn undo(&self) -> Result<Vec<Step>> {
+        let mut tx = self.store.begin().await?;
+
+        let operation = sqlx::query_scalar!(
+            r#"
+            SELECT
+                id AS "id: Operation"
+            FROM
+                operations
+            WHERE
+                NOT undone
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(Error::EmptyJournal("undo"))?;
+
+        let mut steps = Vec::new();
+        for record in records(&mut tx, operation).await?.into_iter().rev() {
+            let (step, prices) = match record.mutation {
+                types::Mutation::Create => (Step::Deleted(record.after()?), None),
+                types::Mutation::Update => {
+                    (Step::Updated(record.before()?), record.prices_before()?)
+                }
+                types::Mutation::Delete => {
+                    (Step::Created(record.before()?), record.prices_before()?)
+                }
+            };
+            apply(&mut tx, self.store, &step, prices.as_deref()).await?;
+            steps.push(step);
+        }
+
+        mark(&mut tx, operation, true).await?;
+
+        // Later mutations of a batch must not join a replayed operation
+        if let super::Transaction::Shared(shared) = &mut tx {
+            shared.operation = None;
+        }
+
+        tx.commit().await?;
+        Ok(steps)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn redo(&self) -> Result<Vec<Step>> {
+        let mut tx = self.store.begin().await?;
+
+        let operation = sqlx::query_scalar!(
+            r#"
+            SELECT
+                id AS "id: Operation"
+            FROM
+                operations
+            WHERE
+                undone
+            ORDER BY
+                id
+            LIMIT
+                1
+            "#
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(Error::EmptyJournal("redo"))?;
+
+        let mut steps = Vec::new();
+        for record in records(&mut tx, operation).await? {
+            let (step, prices) = match record.mutation {
+                types::Mutation::Create => (Step::Created(record.after()?), record.prices_after()?),
+                types::Mutation::Update => (Step::Updated(record.after()?), record.prices_after()?),
+                types::Mutation::Delete => (Step::Deleted(record.before()?), None),
+            };
+            apply(&mut tx, self.store, &step, prices.as_deref()).await?;
+            steps.push(step);
+        }
+
+        mark(&mut tx, operation, false).await?;
+
+        // Later mutations of a batch must not join a replayed operation
+        if let super::Transaction::Shared(shared) = &mut tx {
+            shared.operation = None;
+        }
+
+        tx.commit().await?;
+        Ok(steps)
+    }
+}
+
+/// Opens a new operation to group the audits of a mutation, discarding anything that could be redone
+///
+/// Every mutation of a [`Batch`](super::Batch) joins the same operation
+pub(super) async fn begin(tx: &mut super::Transaction) -> Result<Operation> {
+    if let super::Transaction::Shared(shared) = tx
+        && let Some(operation) = shared.operation
+    {
+        return Ok(operation);
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            operations
+        WHERE
+            undone
+        "#
+    )
+    .execute(tx.as_mut())
+    .await?;
+
+    let operation = sqlx::query_scalar!(
+        r#"
+        INSERT INTO operations
+        DEFAULT VALUES
+        RETURNING
+            id AS "id: Operation"
+        "#
+    )
+    .fetch_one(tx.as_mut())
+    .await?;
+
+    if let super::Transaction::Shared(shared) = tx {
+        shared.operation = Some(operation);
+    }
+
+    Ok(operation)
+}
+
+struct Record {
+    entity: types::Entity,
+    mutation: types::Mutation,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl Record {
+    fn before(&self) -> Result<Entry> {
+        parse(self.entity, self.before.as_deref())
+    }
+
+    fn after(&self) -> Result<Entry> {
+        parse(self.entity, self.after.as_deref())
+    }
+
+    fn prices_before(&self) -> Result<Option<Vec<super::skulls::Price>>> {
+        prices(self.entity, self.before.as_deref())
+    }
+
+    fn prices_after(&self) -> Result<Option<Vec<super::skulls::Price>>> {
+        prices(self.entity, self.after.as_deref())
+    }
+}
+
+fn parse(entity: types::Entity, json: Option<&str>) -> Result<Entry> {
+    // A missing snapshot fails to parse as any entity
+    let json = json.unwrap_or_default();
+    Ok(match entity {
+        types::Entity::Skull => Entry::Skull(serde_json::from_str(json)?),
+        types::Entity::Occurrence => Entry::Occurrence(serde_json::from_str(json)?),
+        types::Entity::Tag => Entry::Tag(serde_json::from_str(json)?),
+    })
+}
+
+fn prices(entity: types::Entity, json: Option<&str>) -> Result<Option<Vec<super::skulls::Price>>> {
+    match (entity, json) {
+        (types::Entity::Skull, Some(json)) => {
+            Ok(serde_json::from_str::<super::skulls::Snapshot>(json)?.prices)
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn records(tx: &mut sqlx::SqliteConnection, operation: Operation) -> Result<Vec<Record>> {
+    sqlx::query_as!(
+        Record,
+        r#"
+        SELECT
+            entity AS "entity: types::Entity",
+            mutation AS "mutation: types::Mutation",
+            before,
+            after
+        FROM
+            audits
+        WHERE
+            operation = $1
+        ORDER BY
+            id
+        "#,
+        operation,
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(Into::into)
+}
+
+async fn mark(tx: &mut sqlx::SqliteConnection, operation: Operation, undone: bool) -> Result {
+    sqlx::query!(
+        r#"
+        UPDATE
+            operations
+        SET
+            undone = $2
+        WHERE
+            id = $1
+        "#,
+        operation,
+        undone,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Replays `step`, restoring the price history of a skull from `prices` when it was snapshotted
+async fn apply(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    step: &Step,
+    prices: Option<&[super::skulls::Price]>,
+) -> Result {
+    match step {
+        Step::Created(Entry::Skull(skull)) => {
+            insert_skull(tx, skull).await?;
+            restore_prices(tx, skull, prices).await?;
+            let after = super::skulls::snapshot(tx, skull.clone()).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Skull,
+                types::Mutation::Create,
+                None,
+                Some(&after),
+            )
+            .await
+        }
+        Step::Updated(Entry::Skull(skull)) => {
+            let before = super::skulls::fetch(tx, skull.id).await?;
+            let before = super::skulls::snapshot(tx, before).await?;
+            update_skull(tx, skull).await?;
+            restore_prices(tx, skull, prices).await?;
+            let after = super::skulls::snapshot(tx, skull.clone()).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Skull,
+                types::Mutation::Update,
+                Some(&before),
+                Some(&after),
+            )
+            .await
+        }
+        Step::Deleted(Entry::Skull(skull)) => {
+            let before = super::skulls::fetch(tx, skull.id).await?;
+            let before = super::skulls::snapshot(tx, before).await?;
+            delete_skull(tx, skull.id).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Skull,
+                types::Mutation::Delete,
+                Some(&before),
+                None,
+            )
+            .await
+        }
+        Step::Created(Entry::Occurrence(occurrence)) => {
+            insert_occurrence(tx, occurrence).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Occurrence,
+                types::Mutation::Create,
+                None,
+                Some(occurrence),
+            )
+            .await
+        }
+        Step::Updated(Entry::Occurrence(occurrence)) => {
+            let before = super::occurrences::fetch(tx, occurrence.id).await?;
+            update_occurrence(tx, occurrence).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Occurrence,
+                types::Mutation::Update,
+                Some(&before),
+                Some(occurrence),
+            )
+            .await
+        }
+        Step::Deleted(Entry::Occurrence(occurrence)) => {
+            let before = super::occurrences::fetch(tx, occurrence.id).await?;
+            delete_occurrence(tx, occurrence.id).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Occurrence,
+                types::Mutation::Delete,
+                Some(&before),
+                None,
+            )
+            .await
+        }
+        Step::Created(Entry::Tag(tag)) => {
+            insert_tag(tx, tag).await?;
+            attach_skulls(tx, tag).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Tag,
+                types::Mutation::Create,
+                None,
+                Some(tag),
+            )
+            .await
+        }
+        Step::Updated(Entry::Tag(tag)) => {
+            let before = super::tags::fetch(tx, tag.id).await?;
+            update_tag(tx, tag).await?;
+            attach_skulls(tx, tag).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Tag,
+                types::Mutation::Update,
+                Some(&before),
+                Some(tag),
+            )
+            .await
+        }
+        Step::Deleted(Entry::Tag(tag)) => {
+            let before = super::tags::fetch(tx, tag.id).await?;
+            delete_tag(tx, tag.id).await?;
+            audit(
+                tx,
+                store,
+                types::Entity::Tag,
+                types::Mutation::Delete,
+                Some(&before),
+                None,
+            )
+            .await
+        }
+    }
+}
+
+async fn audit<T: serde::Serialize>(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    entity: types::Entity,
+    mutation: types::Mutation,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result {
+    // Replayed steps are audited, but not journaled, so they cannot be undone themselves
+    super::audits::record(tx, store, None, entity, mutation, before, after).await
+}
+
+async fn restore_prices(
+    tx: &mut sqlx::SqliteConnection,
+    skull: &types::Skull,
+    prices: Option<&[super::skulls::Price]>,
+) -> Result {
+    match prices {
+        Some(prices) => super::skulls::restore_prices(tx, skull.id, prices).await,
+        // Audits without a price history can only start a new price from now
+        None => super::skulls::record_price(tx, skull.id, skull.price).await,
+    }
+}
+
+async fn insert_skull(tx: &mut sqlx::SqliteConnection, skull: &types::Skull) -> Result {
+    sqlx::query!(
+        r#"
+        INSERT INTO skulls (
+            "id",
+            "name",
+            "color",
+            "icon",
+            "price",
+            "limit",
+            "period",
+            "archived"
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5,
+            $6,
+            $7,
+            $8
+        )
+        "#,
+        skull.id,
+        skull.name,
+        skull.color,
+        skull.icon,
+        skull.price,
+        skull.limit,
+        skull.period,
+        skull.archived,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_skull(tx: &mut sqlx::SqliteConnection, skull: &types::Skull) -> Result {
+    sqlx::query!(
+        r#"
+        UPDATE
+            skulls
+        SET
+            "name" = $2,
+            "color" = $3,
+            "icon" = $4,
+            "price" = $5,
+            "limit" = $6,
+            "period" = $7,
+            "archived" = $8
+        WHERE
+            id = $1
+        "#,
+        skull.id,
+        skull.name,
+        skull.color,
+        skull.icon,
+        skull.price,
+        skull.limit,
+        skull.period,
+        skull.archived,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_skull(tx: &mut sqlx::SqliteConnection, id: types::SkullId) -> Result {
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            skulls
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_occurrence(
+    tx: &mut sqlx::SqliteConnection,
+    occurrence: &types::Occurrence,
+) -> Result {
+    sqlx::query!(
+        r#"
+        INSERT INTO occurrences (
+            id,
+            skull,
+            amount,
+            millis,
+            note
+        ) VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            $5
+        )
+        "#,
+        occurrence.id,
+        occurrence.skull,
+        occurrence.amount,
+        occurrence.millis,
+        occurrence.note,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_occurrence(
+    tx: &mut sqlx::SqliteConnection,
+    occurrence: &types::Occurrence,
+) -> Result {
+    sqlx::query!(
+        r#"
+        UPDATE
+            occurrences
+        SET
+            skull = $2,
+            amount = $3,
+            millis = $4,
+            note = $5
+        WHERE
+            id = $1
+        "#,
+        occurrence.id,
+        occurrence.skull,
+        occurrence.amount,
+        occurrence.millis,
+        occurrence.note,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn delete_occurrence(tx: &mut sqlx::SqliteConnection, id: types::OccurrenceId) -> Result {
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            occurrences
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_tag(tx: &mut sqlx::SqliteConnection, tag: &types::Tag) -> Result {
+    sqlx::query!(
+        r#"
+        INSERT INTO tags (
+            id,
+            name
+        ) VALUES (
+            $1,
+            $2
+        )
+        "#,
+        tag.id,
+        tag.name,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn update_tag(tx: &mut sqlx::SqliteConnection, tag: &types::Tag) -> Result {
+    sqlx::query!(
+        r#"
+        UPDATE
+            tags
+        SET
+            name = $2
+        WHERE
+            id = $1
+        "#,
+        tag.id,
+        tag.name,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn attach_skulls(tx: &mut sqlx::SqliteConnection, tag: &types::Tag) -> Result {
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            skull_tags
+        WHERE
+            tag = $1
+        "#,
+        tag.id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for skull in &tag.skulls {
+        sqlx::query!(
+            r#"
+            INSERT INTO skull_tags (
+                skull,
+                tag
+            ) VALUES (
+                $1,
+                $2
+            )
+            "#,
+            skull,
+            tag.id,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn delete_tag(tx: &mut sqlx::SqliteConnection, id: types::TagId) -> Result {
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            tags
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn skulled_store() -> (Store, types::Skull) {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skull = store
+            .skulls()
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        (store, skull)
+    }
+
+    fn millis(value: i64) -> types::Millis {
+        types::Millis::from(value)
+    }
+
+    async fn prices(store: &Store, skull: &types::Skull) -> Vec<super::super::skulls::Price> {
+        let mut connection = store.acquire().await.unwrap();
+        super::super::skulls::snapshot(&mut connection, skull.clone())
+            .await
+            .unwrap()
+            .prices
+            .unwrap()
+    }
+
+    // Prices are effective from the millisecond they are set
+    fn tick() {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    #[tokio::test]
+    async fn undo_create() {
+        let (store, skull) = skulled_store().await;
+
+        let created = store
+            .occurrences()
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+            ])
+            .await
+            .unwrap();
+
+        let steps = store.journal().undo().await.unwrap();
+        assert_eq!(
+            steps,
+            created
+                .into_iter()
+                .rev()
+                .map(|o| Step::Deleted(Entry::Occurrence(o)))
+                .collect::<Vec<_>>()
+        );
+        assert!(store.occurrences().list().await.unwrap().is_empty());
+
+        let steps = store.journal().undo().await.unwrap();
+        assert_eq!(steps, vec![Step::Deleted(Entry::Skull(skull))]);
+        assert!(store.skulls().list(true).await.unwrap().is_empty());
+
+        let err = store.journal().undo().await.unwrap_err();
+        assert_eq!(err.to_string(), "nothing to undo");
+    }
+
+    #[tokio::test]
+    async fn undo_update() {
+        let (store, skull) = skulled_store().await;
+
+        let created = store
+            .occurrences()
+            .create([(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap()
+            .remove(0);
+        store
+            .occurrences()
+            .update(
+                created.id,
+                None,
+                Some(10.0),
+                None,
+                Some(Some(String::from("oops"))),
+            )
+            .await
+            .unwrap();
+
+        let steps = store.journal().undo().await.unwrap();
+        assert_eq!(
+            steps,
+            vec![Step::Updated(Entry::Occurrence(created.clone()))]
+        );
+        assert_eq!(store.occurrences().list().await.unwrap(), vec![created]);
+    }
+
+    #[tokio::test]
+    async fn undo_delete() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skull = store
+            .skulls()
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let tag = store.tags().create("tag").await.unwrap();
+        let tag = store.tags().attach(tag.id, skull.id).await.unwrap();
+        store.tags().delete(tag.id).await.unwrap();
+
+        let steps = store.journal().undo().await.unwrap();
+        assert_eq!(steps, vec![Step::Created(Entry::Tag(tag.clone()))]);
+        assert_eq!(store.tags().list().await.unwrap(), vec![tag]);
+    }
+
+    #[tokio::test]
+    async fn undo_delete_skull() {
+        let (store, skull) = skulled_store().await;
+        tick();
+        let skull = store
+            .skulls()
+            .update(
+                skull.id,
+                None::<&str>,
+                None,
+                None::<&str>,
+                Some(2.0),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let tag = store.tags().create("tag").await.unwrap();
+        let tag = store.tags().attach(tag.id, skull.id).await.unwrap();
+        let history = prices(&store, &skull).await;
+        assert_eq!(history.len(), 2);
+
+        store.skulls().delete(skull.id).await.unwrap();
+        assert!(store.tags().list().await.unwrap()[0].skulls.is_empty());
+
+        store.journal().undo().await.unwrap();
+        assert_eq!(
+            store.skulls().list(true).await.unwrap(),
+            vec![skull.clone()]
+        );
+        assert_eq!(prices(&store, &skull).await, history);
+        assert_eq!(store.tags().list().await.unwrap(), vec![tag]);
+    }
+
+    #[tokio::test]
+    async fn undo_price() {
+        let (store, skull) = skulled_store().await;
+        let history = prices(&store, &skull).await;
+
+        tick();
+        let updated = store
+            .skulls()
+            .update(
+                skull.id,
+                None::<&str>,
+                None,
+                None::<&str>,
+                Some(5.0),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let updated_history = prices(&store, &updated).await;
+        assert_eq!(updated_history.len(), 2);
+
+        store.journal().undo().await.unwrap();
+        assert_eq!(
+            store.skulls().list(true).await.unwrap(),
+            vec![skull.clone()]
+        );
+        assert_eq!(prices(&store, &skull).await, history);
+        assert_eq!(
+            store
+                .skulls()
+                .price(skull.id, millis(i64::MAX))
+                .await
+                .unwrap(),
+            1.0
+        );
+
+        store.journal().redo().await.unwrap();
+        assert_eq!(prices(&store, &updated).await, updated_history);
+    }
+
+    #[tokio::test]
+    async fn undo_merge_tags() {
+        let (store, skull) = skulled_store().await;
+        let other = store
+            .skulls()
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let both = store.tags().create("both").await.unwrap();
+        store.tags().attach(both.id, skull.id).await.unwrap();
+        let both = store.tags().attach(both.id, other.id).await.unwrap();
+        let tag = store.tags().create("tag").await.unwrap();
+        let tag = store.tags().attach(tag.id, other.id).await.unwrap();
+        let history = prices(&store, &other).await;
+
+        store.skulls().merge(other.id, skull.id).await.unwrap();
+        assert_eq!(
+            store
+                .tags()
+                .list()
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|tag| tag.skulls)
+                .collect::<Vec<_>>(),
+            vec![vec![skull.id], vec![skull.id]]
+        );
+
+        store.journal().undo().await.unwrap();
+        assert_eq!(store.tags().list().await.unwrap(), vec![both, tag]);
+        assert_eq!(prices(&store, &other).await, history);
+    }
+
+    #[tokio::test]
+    async fn undo_merge() {
+        let (store, skull) = skulled_store().await;
+        let other = store
+            .skulls()
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let occurrences = store
+            .occurrences()
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (other.id, 2.0, millis(2), None),
+            ])
+            .await
+            .unwrap();
+
+        store.skulls().merge(other.id, skull.id).await.unwrap();
+        store.journal().undo().await.unwrap();
+
+        assert_eq!(store.skulls().list(true).await.unwrap(), vec![skull, other]);
+        assert_eq!(store.occurrences().list().await.unwrap().len(), 2);
+        for occurrence in occurrences {
+            assert!(
+                store
+                    .occurrences()
+                    .list()
+                    .await
+                    .unwrap()
+                    .contains(&occurrence)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn redo() {
+        let (store, skull) = skulled_store().await;
+
+        let updated = store
+            .skulls()
+            .update(skull.id, Some("two"), None, None::<&str>, None, None, None)
+            .await
+            .unwrap();
+
+        store.journal().undo().await.unwrap();
+        assert_eq!(store.skulls().list(true).await.unwrap(), vec![skull]);
+
+        let steps = store.journal().redo().await.unwrap();
+        assert_eq!(steps, vec![Step::Updated(Entry::Skull(updated.clone()))]);
+        assert_eq!(store.skulls().list(true).await.unwrap(), vec![updated]);
+
+        let err = store.journal().redo().await.unwrap_err();
+        assert_eq!(err.to_string(), "nothing to redo");
+    }
+
+    #[tokio::test]
+    async fn redo_discarded() {
+        let (store, skull) = skulled_store().await;
+
+        store
+            .skulls()
+            .update(skull.id, Some("two"), None, None::<&str>, None, None, None)
+            .await
+            .unwrap();
+        store.journal().undo().await.unwrap();
+
+        store
+            .skulls()
+            .update(
+                skull.id,
+                None::<&str>,
+                Some(3),
+                None::<&str>,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let err = store.journal().redo().await.unwrap_err();
+        assert_eq!(err.to_string(), "nothing to redo");
+    }
+
+    #[tokio::test]
+    async fn undo_audited() {
+        let (store, skull) = skulled_store().await;
+        let before = super::super::skulls::Snapshot {
+            prices: Some(prices(&store, &skull).await),
+            skull,
+        };
+
+        store.journal().undo().await.unwrap();
+
+        let audits = store.audits().list(None, None).await.unwrap();
+        assert_eq!(audits.len(), 2);
+        assert_eq!(audits[0].mutation, types::Mutation::Delete);
+        assert_eq!(
+            audits[0].before,
+            Some(serde_json::to_string(&before).unwrap())
+        );
+
+        // Undoing the replay is not possible, as it is not journaled
+        let err = store.journal().undo().await.unwrap_err();
+        assert_eq!(err.to_string(), "nothing to undo");
+    }
+}
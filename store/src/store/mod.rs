@@ -1,11 +1,43 @@
+pub mod audits;
+pub mod journal;
 pub mod occurrences;
+pub mod settings;
 pub mod skulls;
+pub mod tags;
 
 use crate::{Error, Result};
 
 #[derive(Debug, Clone)]
 pub struct Store {
     pool: sqlx::sqlite::SqlitePool,
+    actor: Option<std::sync::Arc<str>>,
+    batch: Option<std::sync::Arc<tokio::sync::Mutex<Shared>>>,
+}
+
+/// A store whose operations all run in a single transaction, until it is committed
+///
+/// Dropping the batch without committing rolls back every operation done through it
+#[derive(Debug)]
+pub struct Batch {
+    store: Store,
+}
+
+#[derive(Debug)]
+struct Shared {
+    tx: sqlx::Transaction<'static, sqlx::Sqlite>,
+    operation: Option<journal::Operation>,
+}
+
+/// A transaction that is either owned by a single operation or shared by a [`Batch`]
+enum Transaction {
+    Owned(sqlx::Transaction<'static, sqlx::Sqlite>),
+    Shared(tokio::sync::OwnedMutexGuard<Shared>),
+}
+
+/// A connection that is either taken from the pool or shared by a [`Batch`]
+enum Connection {
+    Pooled(sqlx::pool::PoolConnection<sqlx::Sqlite>),
+    Shared(tokio::sync::OwnedMutexGuard<Shared>),
 }
 
 impl Store {
@@ -20,7 +52,11 @@ impl Store {
             .connect_with(options)
             .await?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            actor: None,
+            batch: None,
+        })
     }
 
     #[cfg(test)]
@@ -36,7 +72,39 @@ impl Store {
 
         sqlx::migrate!().run(&pool).await?;
 
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            actor: None,
+            batch: None,
+        })
+    }
+
+    /// Attributes the mutations done through this store to `actor` in the audit log
+    #[must_use]
+    pub fn with_actor<A: Into<std::sync::Arc<str>>>(mut self, actor: A) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Starts a [`Batch`], running every operation done through it in a single transaction
+    pub async fn batch(&self) -> Result<Batch> {
+        if self.batch.is_some() {
+            return Err(Error::InvalidParameter("batch"));
+        }
+
+        let tx = self.pool.begin().await?;
+        let shared = Shared {
+            tx,
+            operation: None,
+        };
+
+        Ok(Batch {
+            store: Self {
+                pool: self.pool.clone(),
+                actor: self.actor.clone(),
+                batch: Some(std::sync::Arc::new(tokio::sync::Mutex::new(shared))),
+            },
+        })
     }
 
     pub async fn migrate(&self) -> Result {
@@ -52,6 +120,121 @@ impl Store {
     pub fn occurrences(&self) -> occurrences::Occurrences<'_> {
         occurrences::Occurrences::new(self)
     }
+
+    #[must_use]
+    pub fn audits(&self) -> audits::Audits<'_> {
+        audits::Audits::new(self)
+    }
+
+    #[must_use]
+    pub fn journal(&self) -> journal::Journal<'_> {
+        journal::Journal::new(self)
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> tags::Tags<'_> {
+        tags::Tags::new(self)
+    }
+
+    #[must_use]
+    pub fn settings(&self) -> settings::Settings<'_> {
+        settings::Settings::new(self)
+    }
+}
+
+impl Store {
+    async fn begin(&self) -> Result<Transaction> {
+        match &self.batch {
+            Some(shared) => Ok(Transaction::Shared(shared.clone().lock_owned().await)),
+            None => Ok(Transaction::Owned(self.pool.begin().await?)),
+        }
+    }
+
+    async fn acquire(&self) -> Result<Connection> {
+        match &self.batch {
+            Some(shared) => Ok(Connection::Shared(shared.clone().lock_owned().await)),
+            None => Ok(Connection::Pooled(self.pool.acquire().await?)),
+        }
+    }
+}
+
+impl Batch {
+    #[must_use]
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    pub async fn commit(self) -> Result {
+        let shared = self
+            .store
+            .batch
+            .and_then(|shared| std::sync::Arc::try_unwrap(shared).ok())
+            .ok_or(Error::BatchInUse)?;
+
+        shared.into_inner().tx.commit().await.map_err(Into::into)
+    }
+}
+
+impl Transaction {
+    async fn commit(self) -> Result {
+        match self {
+            Self::Owned(tx) => tx.commit().await.map_err(Into::into),
+            // The batch commits once all of its operations are done
+            Self::Shared(_) => Ok(()),
+        }
+    }
+}
+
+impl std::ops::Deref for Transaction {
+    type Target = sqlx::SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Owned(tx) => tx,
+            Self::Shared(shared) => &shared.tx,
+        }
+    }
+}
+
+impl std::ops::DerefMut for Transaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Owned(tx) => tx,
+            Self::Shared(shared) => &mut shared.tx,
+        }
+    }
+}
+
+impl AsMut<sqlx::SqliteConnection> for Transaction {
+    fn as_mut(&mut self) -> &mut sqlx::SqliteConnection {
+        self
+    }
+}
+
+impl std::ops::Deref for Connection {
+    type Target = sqlx::SqliteConnection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Shared(shared) => &shared.tx,
+        }
+    }
+}
+
+impl std::ops::DerefMut for Connection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Pooled(connection) => connection,
+            Self::Shared(shared) => &mut shared.tx,
+        }
+    }
+}
+
+impl AsMut<sqlx::SqliteConnection> for Connection {
+    fn as_mut(&mut self) -> &mut sqlx::SqliteConnection {
+        self
+    }
 }
 
 fn check_non_empty<'a>(value: &'a str, field: &'static str) -> Result<&'a str> {
@@ -62,3 +245,99 @@ fn check_non_empty<'a>(value: &'a str, field: &'static str) -> Result<&'a str> {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn fill(batch: &Batch) -> (types::Skull, types::Occurrence) {
+        let skull = batch
+            .store()
+            .skulls()
+            .create("one", 1, "icon", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let occurrence = batch
+            .store()
+            .occurrences()
+            .create([(skull.id, 1.0, types::Millis::from(1), None)])
+            .await
+            .unwrap()
+            .remove(0);
+
+        (skull, occurrence)
+    }
+
+    #[tokio::test]
+    async fn batch_commit() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let batch = store.batch().await.unwrap();
+        let (skull, occurrence) = fill(&batch).await;
+        assert_eq!(
+            batch.store().occurrences().list().await.unwrap(),
+            vec![occurrence.clone()]
+        );
+        batch.commit().await.unwrap();
+
+        assert_eq!(store.skulls().list(true).await.unwrap(), vec![skull]);
+        assert_eq!(store.occurrences().list().await.unwrap(), vec![occurrence]);
+    }
+
+    #[tokio::test]
+    async fn batch_rollback() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let batch = store.batch().await.unwrap();
+        let (skull, _) = fill(&batch).await;
+        batch
+            .store()
+            .occurrences()
+            .create([(skull.id, -1.0, types::Millis::from(2), None)])
+            .await
+            .unwrap_err();
+        drop(batch);
+
+        assert!(store.skulls().list(true).await.unwrap().is_empty());
+        assert!(store.occurrences().list().await.unwrap().is_empty());
+        assert!(store.audits().list(None, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_undo() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let batch = store.batch().await.unwrap();
+        fill(&batch).await;
+        batch.commit().await.unwrap();
+
+        store.journal().undo().await.unwrap();
+
+        assert!(store.skulls().list(true).await.unwrap().is_empty());
+        assert!(store.occurrences().list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn batch_err_nested() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let batch = store.batch().await.unwrap();
+        let err = batch.store().batch().await.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("batch").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_err_in_use() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let batch = store.batch().await.unwrap();
+        let _store = batch.store().clone();
+        let err = batch.commit().await.unwrap_err();
+
+        assert_eq!(err.to_string(), Error::BatchInUse.to_string());
+    }
+}
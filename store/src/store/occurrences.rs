@@ -1,5 +1,28 @@
 use crate::{Error, Result, Store};
 
+pub use types::request::occurrence::KEY_RETENTION;
+
+/// How long, in millis, it takes for an occurrence to weigh half as much in the quick amounts by
+/// default
+pub const HALF_LIFE: i64 = 10 * 24 * 60 * 60 * 1000;
+
+/// The occurrences of a creation, with how it moved the consumption of the limited skulls
+#[derive(Debug, Clone, PartialEq)]
+pub struct Created {
+    pub occurrences: Vec<types::Occurrence>,
+    pub consumptions: Vec<Consumption>,
+}
+
+/// The consumption of a limited skull right before and after a creation, at the latest millis
+/// created for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consumption {
+    pub skull: types::SkullId,
+    pub limit: f32,
+    pub before: f32,
+    pub after: f32,
+}
+
 pub struct Occurrences<'a> {
     store: &'a Store,
 }
@@ -20,7 +43,8 @@ impl Occurrences<'_> {
                 id AS "id: types::OccurrenceId",
                 skull AS "skull: types::SkullId",
                 amount AS "amount: f32",
-                millis AS "millis: types::Millis"
+                millis AS "millis: types::Millis",
+                note
             FROM
                 occurrences
             ORDER BY
@@ -28,7 +52,7 @@ impl Occurrences<'_> {
                 skull DESC
             "#
         )
-        .fetch_all(&self.store.pool)
+        .fetch_all(self.store.acquire().await?.as_mut())
         .await
         .map_err(Into::into)
     }
@@ -37,9 +61,11 @@ impl Occurrences<'_> {
     pub async fn search(
         &self,
         skulls: Option<&std::collections::HashSet<types::SkullId>>,
+        tags: Option<&std::collections::HashSet<String>>,
         start: Option<types::Millis>,
         end: Option<types::Millis>,
         limit: Option<usize>,
+        cursor: Option<types::Cursor>,
     ) -> Result<Vec<types::Occurrence>> {
         let mut builder = sqlx::QueryBuilder::new(
             r"
@@ -47,9 +73,84 @@ impl Occurrences<'_> {
                 id,
                 skull,
                 amount,
-                millis
-            FROM
-                occurrences
+                millis,
+                note
+            ",
+        );
+
+        push_search(&mut builder, skulls, tags, start, end, limit, cursor);
+
+        builder
+            .build_query_as()
+            .fetch_all(self.store.acquire().await?.as_mut())
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn aggregate(
+        &self,
+        skulls: Option<&std::collections::HashSet<types::SkullId>>,
+        start: Option<types::Millis>,
+        end: Option<types::Millis>,
+        bucket: types::Bucket,
+    ) -> Result<Vec<types::Aggregate>> {
+        let mut connection = self.store.acquire().await?;
+        let settings = super::settings::fetch(&mut connection).await?;
+
+        // Buckets are computed over the millis shifted into the timezone of the user, with days
+        // starting at `day_start`, and are then shifted back
+        let offset = i64::from(settings.timezone) * 60_000;
+        let shift = match bucket {
+            types::Bucket::Hour => offset,
+            types::Bucket::Day | types::Bucket::Week | types::Bucket::Month => {
+                offset - i64::from(settings.day_start) * 3_600_000
+            }
+        };
+
+        let mut builder = sqlx::QueryBuilder::new(
+            r"
+            SELECT
+                skull,
+                bucket AS millis,
+                COUNT(*) AS count,
+                SUM(amount) AS sum,
+                MIN(amount) AS min,
+                MAX(amount) AS max
+            FROM (
+                SELECT
+                    skull,
+                    amount,
+            ",
+        );
+
+        // The seconds are kept fractional, so that times before the epoch floor rather than
+        // truncate towards it
+        builder.push("(");
+        builder.push(match bucket {
+            types::Bucket::Hour => "CAST(strftime('%s', strftime('%Y-%m-%d %H:00:00', (millis + ",
+            types::Bucket::Day | types::Bucket::Week | types::Bucket::Month => {
+                "CAST(strftime('%s', (millis + "
+            }
+        });
+        builder.push_bind(shift);
+        builder.push(match bucket {
+            types::Bucket::Hour => ") / 1000.0, 'unixepoch')) AS INTEGER) * 1000",
+            types::Bucket::Day => ") / 1000.0, 'unixepoch', 'start of day') AS INTEGER) * 1000",
+            types::Bucket::Week => {
+                ") / 1000.0, 'unixepoch', 'start of day', '-6 days', 'weekday 1') AS INTEGER) * 1000"
+            }
+            types::Bucket::Month => {
+                ") / 1000.0, 'unixepoch', 'start of month') AS INTEGER) * 1000"
+            }
+        });
+        builder.push(") - ").push_bind(shift);
+
+        builder.push(
+            r"
+                    AS bucket
+                FROM
+                    occurrences
             ",
         );
 
@@ -88,22 +189,142 @@ impl Occurrences<'_> {
             builder.push_bind(end);
         }
 
-        builder.push(" ORDER BY millis DESC, skull DESC ");
+        builder.push(" ) GROUP BY skull, bucket ORDER BY bucket, skull ");
+
+        builder
+            .build_query_as()
+            .fetch_all(connection.as_mut())
+            .await
+            .map_err(Into::into)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn spending(
+        &self,
+        skulls: Option<&std::collections::HashSet<types::SkullId>>,
+        start: Option<types::Millis>,
+        end: Option<types::Millis>,
+    ) -> Result<Vec<types::Spending>> {
+        let mut builder = sqlx::QueryBuilder::new(
+            r"
+            SELECT
+                occurrences.skull AS skull,
+                SUM(occurrences.amount) AS amount,
+                SUM(
+                    occurrences.amount * COALESCE(
+                        (
+                            SELECT
+                                price
+                            FROM
+                                prices
+                            WHERE
+                                prices.skull = occurrences.skull
+                                AND prices.effective_from <= occurrences.millis
+                            ORDER BY
+                                prices.effective_from DESC
+                            LIMIT
+                                1
+                        ),
+                        (
+                            SELECT
+                                price
+                            FROM
+                                prices
+                            WHERE
+                                prices.skull = occurrences.skull
+                            ORDER BY
+                                prices.effective_from
+                            LIMIT
+                                1
+                        ),
+                        skulls.price
+                    )
+                ) AS cost
+            FROM
+                occurrences
+            JOIN
+                skulls
+            ON
+                skulls.id = occurrences.skull
+            ",
+        );
+
+        let mut nowhere = true;
+
+        if let Some(skulls) = skulls {
+            if nowhere {
+                builder.push(" WHERE occurrences.skull IN (");
+                nowhere = false;
+            }
+            let mut separated = builder.separated(',');
+
+            for skull in skulls {
+                separated.push_bind(skull);
+            }
+
+            separated.push_unseparated(')');
+        }
+
+        if let Some(start) = start {
+            if nowhere {
+                builder.push(" WHERE occurrences.millis >= ");
+                nowhere = false;
+            } else {
+                builder.push(" AND occurrences.millis >= ");
+            }
+            builder.push_bind(start);
+        }
 
-        if let Some(limit) = limit {
-            builder.push(" LIMIT ");
-            builder.push(limit);
+        if let Some(end) = end {
+            if nowhere {
+                builder.push(" WHERE occurrences.millis <= ");
+            } else {
+                builder.push(" AND occurrences.millis <= ");
+            }
+            builder.push_bind(end);
         }
 
+        builder.push(" GROUP BY occurrences.skull ORDER BY occurrences.skull ");
+
         builder
             .build_query_as()
-            .fetch_all(&self.store.pool)
+            .fetch_all(self.store.acquire().await?.as_mut())
             .await
             .map_err(Into::into)
     }
 
+    /// Scores every skull and amount pair by how recently and often it was registered
+    ///
+    /// Each occurrence weighs half as much every `half_life` millis before the latest one, and,
+    /// if an `hour` is given, half as much for every hour it is away from it in the timezone of
+    /// the settings
     #[tracing::instrument(skip(self), err)]
-    pub async fn quick(&self) -> Result<Vec<types::Quick>> {
+    pub async fn quick(
+        &self,
+        half_life: Option<u64>,
+        limit: Option<usize>,
+        hour: Option<u32>,
+    ) -> Result<Vec<types::Quick>> {
+        let half_life = half_life.map_or(Ok(HALF_LIFE), i64::try_from);
+        let half_life = match half_life {
+            Ok(half_life) if half_life > 0 => half_life,
+            _ => return Err(Error::InvalidParameter("halfLife")),
+        };
+        if limit == Some(0) {
+            return Err(Error::InvalidParameter("limit"));
+        }
+        if hour.is_some_and(|hour| hour >= 24) {
+            return Err(Error::InvalidParameter("hour"));
+        }
+
+        let mut connection = self.store.acquire().await?;
+        let settings = super::settings::fetch(&mut connection).await?;
+
+        let limit = limit
+            .map_or(Ok(i64::from(settings.quick_count)), i64::try_from)
+            .map_err(|_| Error::InvalidParameter("limit"))?;
+        let offset = i64::from(settings.timezone) * 60_000;
+
         sqlx::query_as!(
             types::Quick,
             r#"
@@ -114,14 +335,111 @@ impl Occurrences<'_> {
                 FROM
                   occurrences
               ),
+              distances AS (
+                SELECT
+                  skull,
+                  amount,
+                  millis,
+                  ABS(CAST(strftime('%H', (millis + $4) / 1000, 'unixepoch') AS INTEGER) - $3) AS distance
+                FROM
+                  occurrences
+              ),
               scored AS (
                 SELECT
                   skull,
                   amount,
                   COUNT(*) AS count,
-                  SUM(POWER(0.5, (max - millis) / 864000000.0)) AS score
+                  SUM(
+                    POWER(0.5, (max - millis) / CAST($1 AS REAL))
+                    * CASE
+                      WHEN $3 IS NULL THEN 1.0
+                      ELSE POWER(0.5, MIN(distance, 24 - distance))
+                    END
+                  ) AS score
+                FROM
+                  distances
+                CROSS JOIN
+                  last
+                GROUP BY
+                  skull,
+                  amount
+                ORDER BY
+                  score DESC
+                LIMIT
+                  $2
+              )
+            SELECT
+              skull AS "skull: types::SkullId",
+              amount AS "amount: f32"
+            FROM
+              scored
+            "#,
+            half_life,
+            limit,
+            hour,
+            offset,
+        )
+        .fetch_all(connection.as_mut())
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Finds the skull and amount most likely to be registered at `millis`
+    ///
+    /// Each occurrence weighs half as much every [`HALF_LIFE`] before the latest one, for every
+    /// hour it is away from the hour of `millis`, and if it was not on the same weekday, all in
+    /// the timezone of the settings
+    #[tracing::instrument(skip(self), err)]
+    pub async fn predict(&self, millis: types::Millis) -> Result<Option<types::Prediction>> {
+        let mut connection = self.store.acquire().await?;
+        let settings = super::settings::fetch(&mut connection).await?;
+
+        let offset = i64::from(settings.timezone) * 60_000;
+
+        sqlx::query_as!(
+            types::Prediction,
+            r#"
+            WITH
+              last AS (
+                SELECT
+                  MAX(millis) AS max
+                FROM
+                  occurrences
+              ),
+              target AS (
+                SELECT
+                  CAST(strftime('%H', ($1 + $2) / 1000, 'unixepoch') AS INTEGER) AS hour,
+                  strftime('%w', ($1 + $2) / 1000, 'unixepoch') AS weekday
+              ),
+              distances AS (
+                SELECT
+                  occurrences.skull AS skull,
+                  occurrences.amount AS amount,
+                  occurrences.millis AS millis,
+                  ABS(CAST(strftime('%H', (occurrences.millis + $2) / 1000, 'unixepoch') AS INTEGER) - target.hour) AS distance,
+                  strftime('%w', (occurrences.millis + $2) / 1000, 'unixepoch') = target.weekday AS same_weekday
                 FROM
                   occurrences
+                JOIN
+                  skulls
+                ON
+                  skulls.id = occurrences.skull
+                CROSS JOIN
+                  target
+                WHERE
+                  NOT skulls.archived
+              ),
+              scored AS (
+                SELECT
+                  skull,
+                  amount,
+                  SUM(
+                    POWER(0.5, (max - millis) / CAST($3 AS REAL))
+                    * POWER(0.5, MIN(distance, 24 - distance))
+                    * CASE WHEN same_weekday THEN 1.0 ELSE 0.5 END
+                  ) AS score
+                FROM
+                  distances
                 CROSS JOIN
                   last
                 GROUP BY
@@ -130,72 +448,171 @@ impl Occurrences<'_> {
                 ORDER BY
                   score DESC
                 LIMIT
-                  12
+                  1
               )
             SELECT
               skull AS "skull: types::SkullId",
               amount AS "amount: f32"
             FROM
               scored
-            "#
+            "#,
+            millis,
+            offset,
+            HALF_LIFE,
         )
-        .fetch_all(&self.store.pool)
+        .fetch_optional(connection.as_mut())
         .await
         .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip(self), err)]
+    pub async fn consumption(&self, skull: types::SkullId, at: types::Millis) -> Result<f32> {
+        let mut connection = self.store.acquire().await?;
+        let settings = super::settings::fetch(&mut connection).await?;
+
+        let period = sqlx::query!(
+            r#"
+            SELECT
+                "period" AS "period: types::Period"
+            FROM
+                skulls
+            WHERE
+                id = $1
+            "#,
+            skull,
+        )
+        .fetch_optional(connection.as_mut())
+        .await?
+        .ok_or(Error::NotFound(skull.into()))?
+        .period;
+
+        consumed(&mut connection, &settings, skull, period, at).await
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn get(&self, id: types::OccurrenceId) -> Result<types::Occurrence> {
+        let mut connection = self.store.acquire().await?;
+        fetch(&mut connection, id).await
+    }
+
     #[tracing::instrument(skip(self), err)]
     pub async fn create<
-        I: IntoIterator<Item = (types::SkullId, f32, types::Millis)> + std::fmt::Debug,
+        I: IntoIterator<Item = (types::SkullId, f32, types::Millis, Option<String>)> + std::fmt::Debug,
     >(
         &self,
         items: I,
     ) -> Result<Vec<types::Occurrence>> {
-        let mut tx = self.store.pool.begin().await?;
-        let mut occurrences = Vec::new();
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
 
-        for (skull, amount, millis) in items {
-            if amount <= 0.0 {
-                return Err(Error::InvalidParameter("amount"));
-            }
+        let occurrences = create(&mut tx, self.store, operation, items).await?;
 
-            let occurrence = sqlx::query_as!(
-                types::Occurrence,
-                r#"
-                INSERT INTO occurrences (
-                    skull,
-                    amount,
-                    millis
-                ) VALUES (
-                    $1,
-                    $2,
-                    $3
-                ) RETURNING
-                    id AS "id: types::OccurrenceId",
-                    skull AS "skull: types::SkullId",
-                    amount AS "amount: f32",
-                    millis AS "millis: types::Millis"
-                "#,
-                skull,
-                amount,
-                millis,
-            )
-            .fetch_one(tx.as_mut())
-            .await?;
+        tx.commit().await?;
+        Ok(occurrences)
+    }
+
+    /// Creates the occurrences only if `key` was not used in the last [`KEY_RETENTION`] millis
+    ///
+    /// Returns `None` when the key is replayed, in which case nothing is created. Replaying a key
+    /// with different items is rejected
+    #[tracing::instrument(skip(self), err)]
+    pub async fn create_once<
+        I: IntoIterator<Item = (types::SkullId, f32, types::Millis, Option<String>)> + std::fmt::Debug,
+    >(
+        &self,
+        key: &str,
+        items: I,
+    ) -> Result<Option<Vec<types::Occurrence>>> {
+        let key = super::check_non_empty(key, "key")?;
+        let items = items.into_iter().collect::<Vec<_>>();
 
-            occurrences.push(occurrence);
+        let mut tx = self.store.begin().await?;
+
+        if !claim_key(&mut tx, key, &items).await? {
+            return Ok(None);
         }
 
-        if occurrences.is_empty() {
-            return Err(Error::NoChanges);
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let occurrences = create(&mut tx, self.store, operation, items).await?;
+
+        tx.commit().await?;
+        Ok(Some(occurrences))
+    }
+
+    /// Creates the occurrences, once per `key` if given, measuring the consumption of the limited
+    /// skulls in the same transaction
+    ///
+    /// Returns `None` when the key is replayed, as in [`create_once`](Self::create_once)
+    #[tracing::instrument(skip(self), err)]
+    pub async fn create_metered<
+        I: IntoIterator<Item = (types::SkullId, f32, types::Millis, Option<String>)> + std::fmt::Debug,
+    >(
+        &self,
+        key: Option<&str>,
+        items: I,
+    ) -> Result<Option<Created>> {
+        let key = key
+            .map(|key| super::check_non_empty(key, "key"))
+            .transpose()?;
+        let items = items.into_iter().collect::<Vec<_>>();
+
+        let mut tx = self.store.begin().await?;
+
+        if let Some(key) = key
+            && !claim_key(&mut tx, key, &items).await?
+        {
+            return Ok(None);
+        }
+
+        let mut latest = std::collections::HashMap::<types::SkullId, types::Millis>::new();
+        for (skull, _, millis, _) in &items {
+            latest
+                .entry(*skull)
+                .and_modify(|latest| *latest = (*millis).max(*latest))
+                .or_insert(*millis);
+        }
+
+        let settings = super::settings::fetch(&mut tx).await?;
+        let mut limited = Vec::new();
+        for (skull, at) in latest {
+            // Unknown skulls are left for the creation to reject
+            let Ok(skull) = super::skulls::fetch(&mut tx, skull).await else {
+                continue;
+            };
+            let Some(limit) = skull.limit.filter(|_| !skull.archived) else {
+                continue;
+            };
+
+            let before = consumed(&mut tx, &settings, skull.id, skull.period, at).await?;
+            limited.push((skull, limit, at, before));
+        }
+
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let occurrences = create(&mut tx, self.store, operation, items).await?;
+
+        let mut consumptions = Vec::with_capacity(limited.len());
+        for (skull, limit, at, before) in limited {
+            let after = consumed(&mut tx, &settings, skull.id, skull.period, at).await?;
+            consumptions.push(Consumption {
+                skull: skull.id,
+                limit,
+                before,
+                after,
+            });
         }
+        consumptions.sort_unstable_by_key(|consumption| consumption.skull);
 
         tx.commit().await?;
-        Ok(occurrences)
+        Ok(Some(Created {
+            occurrences,
+            consumptions,
+        }))
     }
 
-    // allow(clippy::too_many_lines): So that we can have static type checking
-    #[allow(clippy::too_many_lines)]
+    // allow(clippy::option_option): This is necessary to convey a change into None
+    #[allow(clippy::option_option)]
     #[tracing::instrument(skip(self), err)]
     pub async fn update(
         &self,
@@ -203,205 +620,590 @@ impl Occurrences<'_> {
         skull: Option<types::SkullId>,
         amount: Option<f32>,
         millis: Option<types::Millis>,
+        note: Option<Option<String>>,
     ) -> Result<types::Occurrence> {
-        if let Some(amount) = amount
-            && amount <= 0.0
-        {
-            return Err(Error::InvalidParameter("amount"));
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let occurrence = update(
+            &mut tx,
+            self.store,
+            operation,
+            (id, skull, amount, millis, note),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(occurrence)
+    }
+
+    // allow(clippy::option_option): This is necessary to convey a change into None
+    // allow(clippy::type_complexity): Each field is individually optional
+    #[allow(clippy::option_option, clippy::type_complexity)]
+    #[tracing::instrument(skip(self), err)]
+    pub async fn update_batch<
+        I: IntoIterator<
+                Item = (
+                    types::OccurrenceId,
+                    Option<types::SkullId>,
+                    Option<f32>,
+                    Option<types::Millis>,
+                    Option<Option<String>>,
+                ),
+            > + std::fmt::Debug,
+    >(
+        &self,
+        items: I,
+    ) -> Result<Vec<types::Occurrence>> {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+        let mut occurrences = Vec::new();
+
+        for item in items {
+            occurrences.push(update(&mut tx, self.store, operation, item).await?);
         }
 
-        match (skull, amount, millis) {
-            (Some(skull), Some(amount), Some(millis)) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        skull = $2,
-                        amount = $3,
-                        millis = $4
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    skull,
-                    amount,
-                    millis,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (None, Some(amount), Some(millis)) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        amount = $2,
-                        millis = $3
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    amount,
-                    millis,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (Some(skull), None, Some(millis)) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        skull = $2,
-                        millis = $3
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    skull,
-                    millis,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (Some(skull), Some(amount), None) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        skull = $2,
-                        amount = $3
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    skull,
-                    amount,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (Some(skull), None, None) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        skull = $2
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    skull,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (None, Some(amount), None) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        amount = $2
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    amount,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (None, None, Some(millis)) => {
-                sqlx::query_as!(
-                    types::Occurrence,
-                    r#"
-                    UPDATE
-                        occurrences
-                    SET
-                        millis = $2
-                    WHERE
-                        id = $1
-                    RETURNING
-                        id AS "id!: types::OccurrenceId",
-                        skull AS "skull: types::SkullId",
-                        amount AS "amount: f32",
-                        millis AS "millis: types::Millis"
-                    "#,
-                    id,
-                    millis,
-                )
-                .fetch_optional(&self.store.pool)
-                .await
-            }
-            (None, None, None) => {
-                return Err(Error::NoChanges);
-            }
+        if occurrences.is_empty() {
+            return Err(Error::NoChanges);
         }
-        .map_err(Into::into)
-        .and_then(|r| r.ok_or(Error::NotFound(id.into())))
+
+        tx.commit().await?;
+        Ok(occurrences)
     }
 
     #[tracing::instrument(skip(self), err)]
     pub async fn delete(&self, id: types::OccurrenceId) -> Result {
-        sqlx::query!(
-            r#"
-            DELETE FROM
-                occurrences
-            WHERE
-                id = $1
-            RETURNING
-                id
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        delete(&mut tx, self.store, operation, id).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn delete_batch<I: IntoIterator<Item = types::OccurrenceId> + std::fmt::Debug>(
+        &self,
+        ids: I,
+    ) -> Result<Vec<types::OccurrenceId>> {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+        let mut deleted = Vec::new();
+
+        for id in ids {
+            delete(&mut tx, self.store, operation, id).await?;
+            deleted.push(id);
+        }
+
+        if deleted.is_empty() {
+            return Err(Error::NoChanges);
+        }
+
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn delete_search(
+        &self,
+        skulls: Option<&std::collections::HashSet<types::SkullId>>,
+        tags: Option<&std::collections::HashSet<String>>,
+        start: Option<types::Millis>,
+        end: Option<types::Millis>,
+        limit: Option<usize>,
+        cursor: Option<types::Cursor>,
+    ) -> Result<Vec<types::OccurrenceId>> {
+        // Refuse to wipe the whole table
+        if skulls.is_none()
+            && tags.is_none()
+            && start.is_none()
+            && end.is_none()
+            && limit.is_none()
+            && cursor.is_none()
+        {
+            return Err(Error::InvalidParameter("search"));
+        }
+
+        let mut builder =
+            sqlx::QueryBuilder::new("DELETE FROM occurrences WHERE id IN (SELECT id ");
+        push_search(&mut builder, skulls, tags, start, end, limit, cursor);
+        builder.push(
+            r#"
+            ) RETURNING
+                "id",
+                "skull",
+                "amount",
+                "millis",
+                "note"
+            "#,
+        );
+
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let mut occurrences = builder
+            .build_query_as::<types::Occurrence>()
+            .fetch_all(tx.as_mut())
+            .await?;
+
+        // RETURNING does not honor the ordering of the subquery
+        occurrences.sort_unstable_by_key(|o| std::cmp::Reverse((o.millis, o.id)));
+
+        for occurrence in &occurrences {
+            audit(
+                &mut tx,
+                self.store,
+                operation,
+                types::Mutation::Delete,
+                Some(occurrence),
+                None,
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(occurrences.into_iter().map(|o| o.id).collect())
+    }
+}
+
+/// Claims `key` for `items`, returning whether it was free
+async fn claim_key(
+    tx: &mut sqlx::SqliteConnection,
+    key: &str,
+    items: &[(types::SkullId, f32, types::Millis, Option<String>)],
+) -> Result<bool> {
+    let hash = hash(items)?;
+    let now = types::Millis::from(chrono::Utc::now());
+    let expired = types::Millis::from(i64::from(now) - KEY_RETENTION);
+
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            idempotency_keys
+        WHERE
+            millis < $1
+        "#,
+        expired,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let inserted = sqlx::query_scalar!(
+        r#"
+        INSERT INTO idempotency_keys (
+            key,
+            millis,
+            hash
+        ) VALUES (
+            $1,
+            $2,
+            $3
+        )
+        ON CONFLICT DO NOTHING
+        RETURNING
+            key
+        "#,
+        key,
+        now,
+        hash,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if inserted.is_some() {
+        return Ok(true);
+    }
+
+    let replayed = sqlx::query_scalar!(
+        r#"
+        SELECT
+            hash
+        FROM
+            idempotency_keys
+        WHERE
+            key = $1
+        "#,
+        key,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if replayed != hash {
+        Err(Error::InvalidParameter("key"))
+    } else {
+        Ok(false)
+    }
+}
+
+/// How much of `skull` was consumed within its `period` up to `at`
+async fn consumed(
+    connection: &mut sqlx::SqliteConnection,
+    settings: &types::Settings,
+    skull: types::SkullId,
+    period: types::Period,
+    at: types::Millis,
+) -> Result<f32> {
+    let start =
+        types::Millis::from(settings.start(period, &chrono::DateTime::<chrono::Utc>::from(at)));
+
+    sqlx::query_scalar!(
+        r#"
+        SELECT
+            COALESCE(SUM(amount), 0.0) AS "amount!: f32"
+        FROM
+            occurrences
+        WHERE
+            skull = $1
+            AND millis >= $2
+            AND millis <= $3
+        "#,
+        skull,
+        start,
+        at,
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(Into::into)
+}
+
+async fn create<
+    I: IntoIterator<Item = (types::SkullId, f32, types::Millis, Option<String>)> + std::fmt::Debug,
+>(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    items: I,
+) -> Result<Vec<types::Occurrence>> {
+    let mut occurrences = Vec::new();
+
+    for (skull, amount, millis, note) in items {
+        if amount <= 0.0 {
+            return Err(Error::InvalidParameter("amount"));
+        }
+        let note = check_note(note)?;
+
+        let occurrence = sqlx::query_as!(
+            types::Occurrence,
+            r#"
+            INSERT INTO occurrences (
+                skull,
+                amount,
+                millis,
+                note
+            ) VALUES (
+                $1,
+                $2,
+                $3,
+                $4
+            ) RETURNING
+                id AS "id: types::OccurrenceId",
+                skull AS "skull: types::SkullId",
+                amount AS "amount: f32",
+                millis AS "millis: types::Millis",
+                note
             "#,
-            id,
+            skull,
+            amount,
+            millis,
+            note,
         )
-        .map(|_| ())
-        .fetch_optional(&self.store.pool)
-        .await
-        .map_err(Into::into)
-        .and_then(|r| r.ok_or(Error::NotFound(id.into())))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        audit(
+            tx,
+            store,
+            operation,
+            types::Mutation::Create,
+            None,
+            Some(&occurrence),
+        )
+        .await?;
+
+        occurrences.push(occurrence);
     }
+
+    if occurrences.is_empty() {
+        return Err(Error::NoChanges);
+    }
+
+    Ok(occurrences)
+}
+
+// allow(clippy::option_option): This is necessary to convey a change into None
+// allow(clippy::type_complexity): Each field is individually optional
+#[allow(clippy::option_option, clippy::type_complexity)]
+async fn update(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    (id, skull, amount, millis, note): (
+        types::OccurrenceId,
+        Option<types::SkullId>,
+        Option<f32>,
+        Option<types::Millis>,
+        Option<Option<String>>,
+    ),
+) -> Result<types::Occurrence> {
+    let mut has_fields = false;
+    let mut builder = sqlx::QueryBuilder::new("UPDATE occurrences SET ");
+    let mut fields = builder.separated(',');
+
+    macro_rules! push_field {
+        ($name: ident) => {
+            push_field!($name, $name)
+        };
+        ($name: ident, $push: expr) => {
+            if let Some($name) = $name {
+                let $name = $push;
+                fields
+                    .push(concat!("\"", stringify!($name), "\"", " = "))
+                    .push_bind_unseparated($name);
+                has_fields = true;
+            }
+        };
+    }
+
+    push_field!(skull);
+    push_field!(
+        amount,
+        if amount <= 0.0 {
+            return Err(Error::InvalidParameter("amount"));
+        } else {
+            amount
+        }
+    );
+    push_field!(millis);
+    push_field!(note, check_note(note)?);
+
+    if !has_fields {
+        return Err(Error::NoChanges);
+    }
+
+    let before = fetch(tx, id).await?;
+
+    let occurrence = builder
+        .push(" WHERE id = ")
+        .push_bind(id)
+        .push(
+            r#"
+            RETURNING
+                "id",
+                "skull",
+                "amount",
+                "millis",
+                "note"
+            "#,
+        )
+        .build_query_as::<types::Occurrence>()
+        .fetch_one(&mut *tx)
+        .await?;
+
+    audit(
+        tx,
+        store,
+        operation,
+        types::Mutation::Update,
+        Some(&before),
+        Some(&occurrence),
+    )
+    .await?;
+
+    Ok(occurrence)
+}
+
+async fn delete(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    id: types::OccurrenceId,
+) -> Result {
+    let occurrence = sqlx::query_as!(
+        types::Occurrence,
+        r#"
+        DELETE FROM
+            occurrences
+        WHERE
+            id = $1
+        RETURNING
+            id AS "id: types::OccurrenceId",
+            skull AS "skull: types::SkullId",
+            amount AS "amount: f32",
+            millis AS "millis: types::Millis",
+            note
+        "#,
+        id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound(id.into()))?;
+
+    audit(
+        tx,
+        store,
+        operation,
+        types::Mutation::Delete,
+        Some(&occurrence),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub(super) async fn fetch(
+    connection: &mut sqlx::SqliteConnection,
+    id: types::OccurrenceId,
+) -> Result<types::Occurrence> {
+    sqlx::query_as!(
+        types::Occurrence,
+        r#"
+        SELECT
+            id AS "id: types::OccurrenceId",
+            skull AS "skull: types::SkullId",
+            amount AS "amount: f32",
+            millis AS "millis: types::Millis",
+            note
+        FROM
+            occurrences
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(connection)
+    .await?
+    .ok_or(Error::NotFound(id.into()))
+}
+
+async fn audit(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    mutation: types::Mutation,
+    before: Option<&types::Occurrence>,
+    after: Option<&types::Occurrence>,
+) -> Result {
+    super::audits::record(
+        tx,
+        store,
+        Some(operation),
+        types::Entity::Occurrence,
+        mutation,
+        before,
+        after,
+    )
+    .await
+}
+
+fn push_search<'a>(
+    builder: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>,
+    skulls: Option<&'a std::collections::HashSet<types::SkullId>>,
+    tags: Option<&'a std::collections::HashSet<String>>,
+    start: Option<types::Millis>,
+    end: Option<types::Millis>,
+    limit: Option<usize>,
+    cursor: Option<types::Cursor>,
+) {
+    builder.push(" FROM occurrences ");
+
+    let mut nowhere = true;
+
+    if skulls.is_some() || tags.is_some() {
+        builder.push(" WHERE (");
+        nowhere = false;
+    }
+
+    if let Some(skulls) = skulls {
+        builder.push("skull IN (");
+        let mut separated = builder.separated(',');
+
+        for skull in skulls {
+            separated.push_bind(skull);
+        }
+
+        separated.push_unseparated(')');
+    }
+
+    if let Some(tags) = tags {
+        if skulls.is_some() {
+            builder.push(" OR ");
+        }
+        builder.push(
+            "skull IN (SELECT skull_tags.skull FROM skull_tags JOIN tags ON tags.id = skull_tags.tag WHERE tags.name IN (",
+        );
+        let mut separated = builder.separated(',');
+
+        for tag in tags {
+            separated.push_bind(tag);
+        }
+
+        separated.push_unseparated("))");
+    }
+
+    if !nowhere {
+        builder.push(')');
+    }
+
+    if let Some(start) = start {
+        if nowhere {
+            builder.push(" WHERE millis >= ");
+            nowhere = false;
+        } else {
+            builder.push(" AND millis >= ");
+        }
+        builder.push_bind(start);
+    }
+
+    if let Some(end) = end {
+        if nowhere {
+            builder.push(" WHERE millis <= ");
+            nowhere = false;
+        } else {
+            builder.push(" AND millis <= ");
+        }
+        builder.push_bind(end);
+    }
+
+    if let Some(cursor) = cursor {
+        if nowhere {
+            builder.push(" WHERE (millis, id) < (");
+        } else {
+            builder.push(" AND (millis, id) < (");
+        }
+        builder
+            .push_bind(cursor.millis)
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(')');
+    }
+
+    builder.push(" ORDER BY millis DESC, id DESC ");
+
+    if let Some(limit) = limit {
+        builder.push(" LIMIT ");
+        builder.push(limit);
+    }
+}
+
+fn check_note(note: Option<String>) -> Result<Option<String>> {
+    note.map(|note| super::check_non_empty(&note, "note").map(String::from))
+        .transpose()
+}
+
+/// Hashes the items of an idempotent creation with FNV-1a, which is stable across builds
+fn hash(items: &[(types::SkullId, f32, types::Millis, Option<String>)]) -> Result<i64> {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = serde_json::to_vec(items)?
+        .into_iter()
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+
+    Ok(i64::from_ne_bytes(hash.to_ne_bytes()))
 }
 
 #[cfg(test)]
@@ -413,7 +1215,7 @@ mod tests {
 
         let skull = store
             .skulls()
-            .create("one", 1, "icon1", 1.0, None)
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
             .await
             .unwrap();
 
@@ -433,7 +1235,7 @@ mod tests {
         let skull_id = skull.id;
         let other_id = store
             .skulls()
-            .create("two", 2, "two", 2.0, None)
+            .create("two", 2, "two", 2.0, None, types::Period::default())
             .await
             .unwrap()
             .id;
@@ -441,20 +1243,22 @@ mod tests {
         let occurrences = store.occurrences();
 
         let mut new_occurrences = [
-            (other_id, 4.0, millis(4)),
-            (skull_id, 4.0, millis(4)),
-            (other_id, 3.0, millis(3)),
-            (skull_id, 3.0, millis(3)),
-            (skull_id, 2.0, millis(2)),
-            (skull_id, 1.0, millis(1)),
+            (other_id, 4.0, millis(4), None),
+            (skull_id, 4.0, millis(4), None),
+            (other_id, 3.0, millis(3), None),
+            (skull_id, 3.0, millis(3), None),
+            (skull_id, 2.0, millis(2), None),
+            (skull_id, 1.0, millis(1), None),
         ];
         sort(&mut new_occurrences);
 
+        // Created oldest first, so that the IDs also descend in the expected order
         let occurrences = occurrences
-            .create(new_occurrences)
+            .create(new_occurrences.into_iter().rev())
             .await
             .unwrap()
             .into_iter()
+            .rev()
             .collect::<Vec<_>>();
 
         (store, (skull_id, other_id), occurrences.try_into().unwrap())
@@ -463,7 +1267,7 @@ mod tests {
     async fn create_plain(store: &Store, skull: &types::Skull) -> types::Occurrence {
         store
             .occurrences()
-            .create([(skull.id, 1.0, millis(1))])
+            .create([(skull.id, 1.0, millis(1), None)])
             .await
             .unwrap()
             .into_iter()
@@ -486,7 +1290,7 @@ mod tests {
         }
     }
 
-    impl Sortable for (types::SkullId, f32, types::Millis) {
+    impl Sortable for (types::SkullId, f32, types::Millis, Option<String>) {
         fn millis(&self) -> &types::Millis {
             &self.2
         }
@@ -516,7 +1320,10 @@ mod tests {
 
         let occurrences = store.occurrences();
         let mut result = occurrences
-            .create([(skull.id, 1.0, millis(1)), (skull.id, 2.0, millis(2))])
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+            ])
             .await
             .unwrap();
         sort(&mut result);
@@ -534,28 +1341,60 @@ mod tests {
         assert_eq!(occurrences, Vec::new());
     }
 
+    #[tokio::test]
+    async fn get() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let created = occurrences
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), Some(String::from("note"))),
+            ])
+            .await
+            .unwrap();
+
+        let occurrence = occurrences.get(created[1].id).await.unwrap();
+        assert_eq!(occurrence, created[1]);
+    }
+
+    #[tokio::test]
+    async fn get_err_not_found() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let occurrence = create_plain(&store, &skull).await;
+        occurrences.delete(occurrence.id).await.unwrap();
+
+        let err = occurrences.get(occurrence.id).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(occurrence.id.into()).to_string()
+        );
+    }
+
     #[tokio::test]
     async fn quick() {
         let (store, skull) = skulled_store().await;
 
         let other_skull = store
             .skulls()
-            .create("two", 2, "icon2", 2.0, None)
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
             .await
             .unwrap();
 
         let occurrences = store.occurrences();
         occurrences
             .create([
-                (skull.id, 1.0, millis(1)),
-                (skull.id, 2.0, millis(2)),
-                (skull.id, 2.0, millis(3)),
-                (other_skull.id, 1.0, millis(4)),
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 2.0, millis(3), None),
+                (other_skull.id, 1.0, millis(4), None),
             ])
             .await
             .unwrap();
 
-        let occurrences = occurrences.quick().await.unwrap();
+        let occurrences = occurrences.quick(None, None, None).await.unwrap();
         let result = vec![
             types::Quick {
                 skull: skull.id,
@@ -579,22 +1418,22 @@ mod tests {
 
         let other_skull = store
             .skulls()
-            .create("two", 2, "icon2", 2.0, None)
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
             .await
             .unwrap();
 
         let occurrences = store.occurrences();
         occurrences
             .create([
-                (skull.id, 2.0, millis(2)),
-                (skull.id, 2.0, millis(3)),
-                (other_skull.id, 1.0, millis(4)),
-                (skull.id, 1.0, millis(100_000_000_000)),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 2.0, millis(3), None),
+                (other_skull.id, 1.0, millis(4), None),
+                (skull.id, 1.0, millis(100_000_000_000), None),
             ])
             .await
             .unwrap();
 
-        let occurrences = occurrences.quick().await.unwrap();
+        let occurrences = occurrences.quick(None, None, None).await.unwrap();
         let result = vec![
             types::Quick {
                 skull: skull.id,
@@ -613,79 +1452,857 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn quick_empty() {
-        let store = Store::in_memory(1).await.unwrap();
+    async fn quick_count() {
+        let (store, skull) = skulled_store().await;
+
+        store
+            .settings()
+            .set([types::Setting::QuickCount(1)])
+            .await
+            .unwrap();
 
         let occurrences = store.occurrences();
-        let occurrences = occurrences.quick().await.unwrap();
-        assert_eq!(occurrences, Vec::new());
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 2.0, millis(3), None),
+            ])
+            .await
+            .unwrap();
+
+        let occurrences = occurrences.quick(None, None, None).await.unwrap();
+        assert_eq!(
+            occurrences,
+            vec![types::Quick {
+                skull: skull.id,
+                amount: 2.0,
+            }]
+        );
     }
 
     #[tokio::test]
-    async fn search_no_filters() {
-        let (store, _, news) = prepare_search().await;
+    async fn quick_limit() {
+        let (store, skull) = skulled_store().await;
 
-        let occurrences = store
-            .occurrences()
-            .search(None, None, None, None)
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 2.0, millis(3), None),
+            ])
             .await
             .unwrap();
-        assert_eq!(occurrences, news);
+
+        let occurrences = occurrences.quick(None, Some(1), None).await.unwrap();
+        assert_eq!(
+            occurrences,
+            vec![types::Quick {
+                skull: skull.id,
+                amount: 2.0,
+            }]
+        );
     }
 
     #[tokio::test]
-    async fn search_all_skulls() {
-        let (store, (skull_one, skull_two), news) = prepare_search().await;
+    async fn quick_half_life() {
+        let (store, skull) = skulled_store().await;
 
-        let occurrences = store
-            .occurrences()
-            .search(
-                Some(&std::collections::HashSet::from([skull_one, skull_two])),
-                None,
-                None,
-                None,
-            )
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 2.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 1.0, millis(3_600_000), None),
+            ])
             .await
             .unwrap();
-        assert_eq!(occurrences, news);
+
+        let default = occurrences.quick(None, None, None).await.unwrap();
+        assert_eq!(
+            default,
+            vec![
+                types::Quick {
+                    skull: skull.id,
+                    amount: 2.0,
+                },
+                types::Quick {
+                    skull: skull.id,
+                    amount: 1.0,
+                },
+            ]
+        );
+
+        let short = occurrences.quick(Some(60_000), None, None).await.unwrap();
+        assert_eq!(
+            short,
+            vec![
+                types::Quick {
+                    skull: skull.id,
+                    amount: 1.0,
+                },
+                types::Quick {
+                    skull: skull.id,
+                    amount: 2.0,
+                },
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn search_no_skulls() {
-        let (store, _, _) = prepare_search().await;
+    async fn quick_hour() {
+        let (store, skull) = skulled_store().await;
 
-        let occurrences = store
-            .occurrences()
-            .search(Some(&std::collections::HashSet::new()), None, None, None)
+        let hour = 60 * 60 * 1000;
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 2.0, millis(8 * hour), None),
+                (skull.id, 2.0, millis(8 * hour + 1), None),
+                (skull.id, 1.0, millis(20 * hour), None),
+            ])
             .await
             .unwrap();
-        assert_eq!(occurrences.iter().map(|o| o.id).collect::<Vec<_>>(), []);
-    }
 
-    #[tokio::test]
-    async fn search_just_skulls() {
-        let (store, (_, skull_two), news) = prepare_search().await;
+        let morning = vec![
+            types::Quick {
+                skull: skull.id,
+                amount: 2.0,
+            },
+            types::Quick {
+                skull: skull.id,
+                amount: 1.0,
+            },
+        ];
+        let evening = vec![
+            types::Quick {
+                skull: skull.id,
+                amount: 1.0,
+            },
+            types::Quick {
+                skull: skull.id,
+                amount: 2.0,
+            },
+        ];
 
-        let occurrences = store
+        let quicks = occurrences.quick(None, None, Some(7)).await.unwrap();
+        assert_eq!(quicks, morning);
+
+        let quicks = occurrences.quick(None, None, Some(21)).await.unwrap();
+        assert_eq!(quicks, evening);
+
+        // The hours are in the timezone of the settings
+        store
+            .settings()
+            .set([types::Setting::Timezone(-12 * 60)])
+            .await
+            .unwrap();
+
+        let quicks = store
             .occurrences()
-            .search(
-                Some(&std::collections::HashSet::from([skull_two])),
-                None,
-                None,
-                None,
-            )
+            .quick(None, None, Some(8))
             .await
             .unwrap();
-        assert_eq!(occurrences, filter(news, |o| o.skull == skull_two));
+        assert_eq!(quicks, evening);
     }
 
     #[tokio::test]
-    async fn search_just_start() {
-        let (store, _, news) = prepare_search().await;
+    async fn quick_err_invalid() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let occurrences = store.occurrences();
+        for (half_life, limit, hour, field) in [
+            (Some(0), None, None, "halfLife"),
+            (Some(u64::MAX), None, None, "halfLife"),
+            (None, Some(0), None, "limit"),
+            (None, None, Some(24), "hour"),
+        ] {
+            let err = occurrences.quick(half_life, limit, hour).await.unwrap_err();
+            assert_eq!(err.to_string(), Error::InvalidParameter(field).to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn quick_empty() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let occurrences = store.occurrences();
+        let occurrences = occurrences.quick(None, None, None).await.unwrap();
+        assert_eq!(occurrences, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn predict() {
+        let (store, skull) = skulled_store().await;
+
+        let other_skull = store
+            .skulls()
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let hour = 60 * 60 * 1000;
+        let day = 24 * hour;
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(8 * hour), None),
+                (skull.id, 1.0, millis(day + 8 * hour), None),
+                (other_skull.id, 2.0, millis(20 * hour), None),
+                (other_skull.id, 2.0, millis(day + 20 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let prediction = occurrences
+            .predict(millis(7 * day + 9 * hour))
+            .await
+            .unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: skull.id,
+                amount: 1.0,
+            })
+        );
+
+        let prediction = occurrences
+            .predict(millis(7 * day + 19 * hour))
+            .await
+            .unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: other_skull.id,
+                amount: 2.0,
+            })
+        );
+
+        // Archived skulls are not predicted
+        store.skulls().archive(skull.id, true).await.unwrap();
+
+        let prediction = store
+            .occurrences()
+            .predict(millis(7 * day + 9 * hour))
+            .await
+            .unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: other_skull.id,
+                amount: 2.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn predict_weekday() {
+        let (store, skull) = skulled_store().await;
+
+        let hour = 60 * 60 * 1000;
+        let day = 24 * hour;
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(8 * hour), None),
+                (skull.id, 2.0, millis(day + 8 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let prediction = occurrences
+            .predict(millis(7 * day + 8 * hour))
+            .await
+            .unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: skull.id,
+                amount: 1.0,
+            })
+        );
+
+        let prediction = occurrences
+            .predict(millis(8 * day + 8 * hour))
+            .await
+            .unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: skull.id,
+                amount: 2.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn predict_timezone() {
+        let (store, skull) = skulled_store().await;
+
+        let hour = 60 * 60 * 1000;
+        let day = 24 * hour;
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(22 * hour), None),
+                (skull.id, 2.0, millis(day + 2 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let prediction = occurrences.predict(millis(7 * day)).await.unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: skull.id,
+                amount: 1.0,
+            })
+        );
+
+        // Neither occurrence falls on the weekday of the prediction anymore
+        store
+            .settings()
+            .set([types::Setting::Timezone(-3 * 60)])
+            .await
+            .unwrap();
+
+        let prediction = store.occurrences().predict(millis(7 * day)).await.unwrap();
+        assert_eq!(
+            prediction,
+            Some(types::Prediction {
+                skull: skull.id,
+                amount: 2.0,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn predict_empty() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let prediction = store.occurrences().predict(millis(0)).await.unwrap();
+        assert_eq!(prediction, None);
+    }
+
+    #[tokio::test]
+    async fn aggregate() {
+        let (store, (skull_one, skull_two), _) = prepare_search().await;
+
+        let aggregates = store
+            .occurrences()
+            .aggregate(None, None, None, types::Bucket::Hour)
+            .await
+            .unwrap();
+        assert_eq!(
+            aggregates,
+            vec![
+                types::Aggregate {
+                    skull: skull_one,
+                    millis: millis(0),
+                    count: 4,
+                    sum: 10.0,
+                    min: 1.0,
+                    max: 4.0,
+                },
+                types::Aggregate {
+                    skull: skull_two,
+                    millis: millis(0),
+                    count: 2,
+                    sum: 7.0,
+                    min: 3.0,
+                    max: 4.0,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregate_filtered() {
+        let (store, (skull_one, _), _) = prepare_search().await;
+
+        let aggregates = store
+            .occurrences()
+            .aggregate(
+                Some(&std::collections::HashSet::from([skull_one])),
+                Some(millis(2)),
+                Some(millis(3)),
+                types::Bucket::Day,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            aggregates,
+            vec![types::Aggregate {
+                skull: skull_one,
+                millis: millis(0),
+                count: 2,
+                sum: 5.0,
+                min: 2.0,
+                max: 3.0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregate_buckets() {
+        let (store, skull) = skulled_store().await;
+        let day = 24 * 60 * 60 * 1000;
+
+        // 1970-01-01 was a Thursday
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(day / 2), None),
+                (skull.id, 2.0, millis(day + day / 2), None),
+                (skull.id, 3.0, millis(4 * day + day / 2), None),
+                (skull.id, 4.0, millis(32 * day), None),
+            ])
+            .await
+            .unwrap();
+
+        let buckets = |aggregates: Vec<types::Aggregate>| {
+            aggregates
+                .into_iter()
+                .map(|a| (i64::from(a.millis) / day, a.count))
+                .collect::<Vec<_>>()
+        };
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Day)
+            .await
+            .unwrap();
+        assert_eq!(buckets(aggregates), [(0, 1), (1, 1), (4, 1), (32, 1)]);
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Week)
+            .await
+            .unwrap();
+        assert_eq!(buckets(aggregates), [(-3, 2), (4, 1), (32, 1)]);
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Month)
+            .await
+            .unwrap();
+        assert_eq!(buckets(aggregates), [(0, 3), (31, 1)]);
+    }
+
+    #[tokio::test]
+    async fn aggregate_buckets_before_epoch() {
+        let (store, skull) = skulled_store().await;
+        let hour = 60 * 60 * 1000;
+
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(-hour - 1), None),
+                (skull.id, 2.0, millis(-hour / 2), None),
+                (skull.id, 3.0, millis(-1), None),
+                (skull.id, 4.0, millis(hour / 2), None),
+            ])
+            .await
+            .unwrap();
+
+        let buckets = |aggregates: Vec<types::Aggregate>| {
+            aggregates
+                .into_iter()
+                .map(|a| (i64::from(a.millis), a.count))
+                .collect::<Vec<_>>()
+        };
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Hour)
+            .await
+            .unwrap();
+        assert_eq!(buckets(aggregates), [(-2 * hour, 1), (-hour, 2), (0, 1)]);
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Day)
+            .await
+            .unwrap();
+        assert_eq!(buckets(aggregates), [(-24 * hour, 3), (0, 1)]);
+    }
+
+    #[tokio::test]
+    async fn aggregate_settings() {
+        let (store, skull) = skulled_store().await;
+        let hour = 60 * 60 * 1000;
+
+        store
+            .settings()
+            .set([types::Setting::Timezone(60), types::Setting::DayStart(5)])
+            .await
+            .unwrap();
+
+        // Local days start at 05:00 in UTC+1, which is 04:00 UTC
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(3 * hour), None),
+                (skull.id, 2.0, millis(4 * hour), None),
+                (skull.id, 3.0, millis(27 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Day)
+            .await
+            .unwrap();
+        assert_eq!(
+            aggregates
+                .into_iter()
+                .map(|a| (i64::from(a.millis), a.count))
+                .collect::<Vec<_>>(),
+            [(-20 * hour, 1), (4 * hour, 2)]
+        );
+
+        store
+            .settings()
+            .set([types::Setting::Timezone(30)])
+            .await
+            .unwrap();
+
+        let aggregates = occurrences
+            .aggregate(None, None, None, types::Bucket::Hour)
+            .await
+            .unwrap();
+        assert_eq!(
+            aggregates
+                .into_iter()
+                .map(|a| (i64::from(a.millis), a.count))
+                .collect::<Vec<_>>(),
+            [(5 * hour / 2, 1), (7 * hour / 2, 1), (53 * hour / 2, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregate_empty() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let aggregates = store
+            .occurrences()
+            .aggregate(None, None, None, types::Bucket::Month)
+            .await
+            .unwrap();
+        assert_eq!(aggregates, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn spending() {
+        let (store, (skull_one, skull_two), _) = prepare_search().await;
+
+        let spending = store
+            .occurrences()
+            .spending(None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            spending,
+            vec![
+                types::Spending {
+                    skull: skull_one,
+                    amount: 10.0,
+                    cost: 10.0,
+                },
+                types::Spending {
+                    skull: skull_two,
+                    amount: 7.0,
+                    cost: 14.0,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn spending_filtered() {
+        let (store, (_, skull_two), _) = prepare_search().await;
+
+        let spending = store
+            .occurrences()
+            .spending(
+                Some(&std::collections::HashSet::from([skull_two])),
+                Some(millis(4)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            spending,
+            vec![types::Spending {
+                skull: skull_two,
+                amount: 4.0,
+                cost: 8.0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn spending_price_history() {
+        let (store, skull) = skulled_store().await;
+
+        sqlx::query("UPDATE prices SET effective_from = 0 WHERE skull = $1")
+            .bind(skull.id)
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        store
+            .skulls()
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                Some(3.0),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        store
+            .occurrences()
+            .create([
+                (skull.id, 1.0, millis(1), None),
+                (skull.id, 2.0, millis(2), None),
+                (skull.id, 1.0, millis(i64::MAX), None),
+            ])
+            .await
+            .unwrap();
+
+        let spending = store
+            .occurrences()
+            .spending(None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            spending,
+            vec![types::Spending {
+                skull: skull.id,
+                amount: 4.0,
+                cost: 6.0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn spending_empty() {
+        let (store, _) = skulled_store().await;
+
+        let spending = store
+            .occurrences()
+            .spending(None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(spending, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn consumption() {
+        let (store, skull) = skulled_store().await;
+        let hour = 60 * 60 * 1000;
+
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(hour), None),
+                (skull.id, 2.0, millis(20 * hour), None),
+                (skull.id, 3.0, millis(30 * hour), None),
+                (skull.id, 4.0, millis(50 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let consumption = occurrences
+            .consumption(skull.id, millis(30 * hour))
+            .await
+            .unwrap();
+        assert_eq!(consumption.to_string(), 5.0.to_string());
+    }
+
+    #[tokio::test]
+    async fn consumption_calendar() {
+        let (store, skull) = skulled_store().await;
+        let hour = 60 * 60 * 1000;
+
+        store
+            .skulls()
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                None,
+                None,
+                Some(types::Period::Day),
+            )
+            .await
+            .unwrap();
+
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(23 * hour), None),
+                (skull.id, 2.0, millis(24 * hour), None),
+                (skull.id, 3.0, millis(30 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let consumption = occurrences
+            .consumption(skull.id, millis(30 * hour))
+            .await
+            .unwrap();
+        assert_eq!(consumption.to_string(), 5.0.to_string());
+    }
+
+    #[tokio::test]
+    async fn consumption_settings() {
+        let (store, skull) = skulled_store().await;
+        let hour = 60 * 60 * 1000;
+
+        store
+            .skulls()
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                None,
+                None,
+                Some(types::Period::Day),
+            )
+            .await
+            .unwrap();
+        store
+            .settings()
+            .set([types::Setting::Timezone(60), types::Setting::DayStart(5)])
+            .await
+            .unwrap();
+
+        // The day started at 05:00 in UTC+1, which is 04:00 UTC
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (skull.id, 1.0, millis(27 * hour), None),
+                (skull.id, 2.0, millis(28 * hour), None),
+                (skull.id, 3.0, millis(30 * hour), None),
+            ])
+            .await
+            .unwrap();
+
+        let consumption = occurrences
+            .consumption(skull.id, millis(30 * hour))
+            .await
+            .unwrap();
+        assert_eq!(consumption.to_string(), 5.0.to_string());
+    }
+
+    #[tokio::test]
+    async fn consumption_empty() {
+        let (store, skull) = skulled_store().await;
+
+        let consumption = store
+            .occurrences()
+            .consumption(skull.id, millis(1))
+            .await
+            .unwrap();
+        assert_eq!(consumption.to_string(), 0.0.to_string());
+    }
+
+    #[tokio::test]
+    async fn consumption_err_not_found() {
+        let (store, skull) = skulled_store().await;
+        store.skulls().delete(skull.id).await.unwrap();
+
+        let err = store
+            .occurrences()
+            .consumption(skull.id, millis(1))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(skull.id.into()).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn search_no_filters() {
+        let (store, _, news) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(occurrences, news);
+    }
+
+    #[tokio::test]
+    async fn search_all_skulls() {
+        let (store, (skull_one, skull_two), news) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                Some(&std::collections::HashSet::from([skull_one, skull_two])),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, news);
+    }
+
+    #[tokio::test]
+    async fn search_no_skulls() {
+        let (store, _, _) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                Some(&std::collections::HashSet::new()),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences.iter().map(|o| o.id).collect::<Vec<_>>(), []);
+    }
+
+    #[tokio::test]
+    async fn search_just_skulls() {
+        let (store, (_, skull_two), news) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                Some(&std::collections::HashSet::from([skull_two])),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, filter(news, |o| o.skull == skull_two));
+    }
+
+    #[tokio::test]
+    async fn search_just_start() {
+        let (store, _, news) = prepare_search().await;
 
         let occurrences = store
             .occurrences()
-            .search(None, Some(millis(3)), None, None)
+            .search(None, None, Some(millis(3)), None, None, None)
             .await
             .unwrap();
         assert_eq!(occurrences, filter(news, |o| o.millis >= millis(3)));
@@ -697,7 +2314,7 @@ mod tests {
 
         let occurrences = store
             .occurrences()
-            .search(None, None, Some(millis(2)), None)
+            .search(None, None, None, Some(millis(2)), None, None)
             .await
             .unwrap();
         assert_eq!(occurrences, filter(news, |o| o.millis <= millis(2)));
@@ -709,7 +2326,7 @@ mod tests {
 
         let occurrences = store
             .occurrences()
-            .search(None, Some(millis(3)), Some(millis(3)), None)
+            .search(None, None, Some(millis(3)), Some(millis(3)), None, None)
             .await
             .unwrap();
         assert_eq!(
@@ -722,92 +2339,355 @@ mod tests {
     async fn search_just_limit() {
         let (store, _, news) = prepare_search().await;
 
-        let occurrences = store
-            .occurrences()
-            .search(None, None, None, Some(3))
+        let occurrences = store
+            .occurrences()
+            .search(None, None, None, None, Some(3), None)
+            .await
+            .unwrap();
+        assert_eq!(occurrences, news[..3]);
+    }
+
+    #[tokio::test]
+    async fn search_cursor() {
+        let (store, _, news) = prepare_search().await;
+
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = store
+                .occurrences()
+                .search(None, None, None, None, Some(4), cursor)
+                .await
+                .unwrap();
+            let Some(last) = page.last() else {
+                break;
+            };
+            cursor = Some(types::Cursor {
+                millis: last.millis,
+                id: last.id,
+            });
+            pages.push(page);
+        }
+
+        assert_eq!(pages, [news[..4].to_vec(), news[4..].to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn search_cursor_ties() {
+        let (store, _, news) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                None,
+                None,
+                Some(millis(3)),
+                None,
+                None,
+                Some(types::Cursor {
+                    millis: news[0].millis,
+                    id: news[0].id,
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, news[1..4]);
+    }
+
+    #[tokio::test]
+    async fn search_all_filters() {
+        let (store, (skull_one, _), news) = prepare_search().await;
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                Some(&std::collections::HashSet::from([skull_one])),
+                None,
+                Some(millis(3)),
+                Some(millis(4)),
+                Some(1),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            occurrences,
+            filter(news, |o| o.skull == skull_one
+                && o.millis >= millis(3)
+                && o.millis <= millis(4))[..1]
+        );
+    }
+
+    #[tokio::test]
+    async fn search_tags() {
+        let (store, (skull_one, skull_two), news) = prepare_search().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.attach(tag.id, skull_two).await.unwrap();
+        tags.create("other").await.unwrap();
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                None,
+                Some(&std::collections::HashSet::from([String::from("tag")])),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, filter(news.clone(), |o| o.skull == skull_two));
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                None,
+                Some(&std::collections::HashSet::from([String::from("other")])),
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, []);
+
+        let occurrences = store
+            .occurrences()
+            .search(
+                Some(&std::collections::HashSet::from([skull_one])),
+                Some(&std::collections::HashSet::from([String::from("tag")])),
+                Some(millis(4)),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrences, filter(news, |o| o.millis >= millis(4)));
+    }
+
+    #[tokio::test]
+    async fn create() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrence = create_plain(&store, &skull).await;
+
+        assert_eq!(types::Id::from(occurrence.id), 1);
+        assert_eq!(occurrence.skull, skull.id);
+        assert_eq!(occurrence.amount.to_string(), 1.0.to_string());
+        assert_eq!(occurrence.millis, millis(1));
+    }
+
+    #[tokio::test]
+    async fn create_err_no_skull() {
+        let (store, skull) = skulled_store().await;
+        store.skulls().delete(skull.id).await.unwrap();
+
+        let occurrences = store.occurrences();
+
+        let err = occurrences
+            .create([(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::ForeignKey.to_string());
+    }
+
+    #[tokio::test]
+    async fn create_err_amount_negative() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+
+        let err = occurrences
+            .create([(skull.id, -1.0, millis(1), None)])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("amount").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn create_err_amount_zero() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+
+        let err = occurrences
+            .create([(skull.id, 0.0, millis(1), None)])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("amount").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn create_once() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+
+        let created = occurrences
+            .create_once("key", [(skull.id, 1.0, millis(1), None)])
             .await
+            .unwrap()
             .unwrap();
-        assert_eq!(occurrences, news[..3]);
+        let replayed = occurrences
+            .create_once("key", [(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap();
+
+        assert!(replayed.is_none());
+        assert_eq!(occurrences.list().await.unwrap(), created);
     }
 
     #[tokio::test]
-    async fn search_all_filters() {
-        let (store, (skull_one, _), news) = prepare_search().await;
+    async fn create_metered() {
+        let (store, skull) = skulled_store().await;
+        let limited = store
+            .skulls()
+            .update(
+                skull.id,
+                None::<&str>,
+                None,
+                None::<&str>,
+                None,
+                Some(Some(2.0)),
+                Some(types::Period::Hours(1)),
+            )
+            .await
+            .unwrap();
+        let other = store
+            .skulls()
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
 
-        let occurrences = store
-            .occurrences()
-            .search(
-                Some(&std::collections::HashSet::from([skull_one])),
-                Some(millis(3)),
-                Some(millis(4)),
-                Some(1),
+        let occurrences = store.occurrences();
+        occurrences
+            .create([(limited.id, 1.0, millis(1), None)])
+            .await
+            .unwrap();
+
+        let created = occurrences
+            .create_metered(
+                None,
+                [
+                    (limited.id, 2.0, millis(3), None),
+                    (limited.id, 1.0, millis(2), None),
+                    (other.id, 1.0, millis(2), None),
+                ],
             )
             .await
+            .unwrap()
             .unwrap();
+
+        assert_eq!(created.occurrences.len(), 3);
         assert_eq!(
-            occurrences,
-            filter(news, |o| o.skull == skull_one
-                && o.millis >= millis(3)
-                && o.millis <= millis(4))[..1]
+            created.consumptions,
+            vec![Consumption {
+                skull: limited.id,
+                limit: 2.0,
+                before: 1.0,
+                after: 4.0,
+            }]
         );
+
+        let replayed = occurrences
+            .create_metered(Some("key"), [(other.id, 1.0, millis(4), None)])
+            .await
+            .unwrap();
+        assert!(replayed.is_some());
+        let replayed = occurrences
+            .create_metered(Some("key"), [(other.id, 1.0, millis(4), None)])
+            .await
+            .unwrap();
+        assert!(replayed.is_none());
     }
 
     #[tokio::test]
-    async fn create() {
+    async fn create_once_mismatch() {
         let (store, skull) = skulled_store().await;
 
-        let occurrence = create_plain(&store, &skull).await;
+        let occurrences = store.occurrences();
 
-        assert_eq!(types::Id::from(occurrence.id), 1);
-        assert_eq!(occurrence.skull, skull.id);
-        assert_eq!(occurrence.amount.to_string(), 1.0.to_string());
-        assert_eq!(occurrence.millis, millis(1));
+        let created = occurrences
+            .create_once("key", [(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap()
+            .unwrap();
+        let err = occurrences
+            .create_once("key", [(skull.id, 2.0, millis(1), None)])
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), Error::InvalidParameter("key").to_string());
+        assert_eq!(occurrences.list().await.unwrap(), created);
     }
 
     #[tokio::test]
-    async fn create_err_no_skull() {
+    async fn create_once_expired() {
         let (store, skull) = skulled_store().await;
-        store.skulls().delete(skull.id).await.unwrap();
 
         let occurrences = store.occurrences();
 
-        let err = occurrences
-            .create([(skull.id, 1.0, millis(1))])
+        occurrences
+            .create_once("key", [(skull.id, 1.0, millis(1), None)])
             .await
-            .unwrap_err();
-        assert_eq!(err.to_string(), Error::ForeignKey.to_string());
+            .unwrap()
+            .unwrap();
+
+        sqlx::query("UPDATE idempotency_keys SET millis = 0")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        let created = occurrences
+            .create_once("key", [(skull.id, 2.0, millis(2), None)])
+            .await
+            .unwrap();
+
+        assert!(created.is_some());
+        assert_eq!(occurrences.list().await.unwrap().len(), 2);
     }
 
     #[tokio::test]
-    async fn create_err_amount_negative() {
+    async fn create_once_failed() {
         let (store, skull) = skulled_store().await;
 
         let occurrences = store.occurrences();
 
-        let err = occurrences
-            .create([(skull.id, -1.0, millis(1))])
+        occurrences
+            .create_once("key", [(skull.id, 0.0, millis(1), None)])
             .await
             .unwrap_err();
-        assert_eq!(
-            err.to_string(),
-            Error::InvalidParameter("amount").to_string()
-        );
+
+        let created = occurrences
+            .create_once("key", [(skull.id, 1.0, millis(1), None)])
+            .await
+            .unwrap();
+
+        assert!(created.is_some());
     }
 
     #[tokio::test]
-    async fn create_err_amount_zero() {
+    async fn create_once_err_empty_key() {
         let (store, skull) = skulled_store().await;
 
-        let occurrences = store.occurrences();
-
-        let err = occurrences
-            .create([(skull.id, 0.0, millis(1))])
+        let err = store
+            .occurrences()
+            .create_once(" ", [(skull.id, 1.0, millis(1), None)])
             .await
             .unwrap_err();
-        assert_eq!(
-            err.to_string(),
-            Error::InvalidParameter("amount").to_string()
-        );
+
+        assert_eq!(err.to_string(), Error::InvalidParameter("key").to_string());
     }
 
     #[tokio::test]
@@ -815,7 +2695,7 @@ mod tests {
         let (store, skull) = skulled_store().await;
         let other_id = store
             .skulls()
-            .create("two", 2, "two", 2.0, None)
+            .create("two", 2, "two", 2.0, None, types::Period::default())
             .await
             .unwrap()
             .id;
@@ -823,7 +2703,13 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let occurrence = occurrences
-            .update(occurrence.id, Some(other_id), Some(2.0), Some(millis(2)))
+            .update(
+                occurrence.id,
+                Some(other_id),
+                Some(2.0),
+                Some(millis(2)),
+                None,
+            )
             .await
             .unwrap();
 
@@ -840,7 +2726,13 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let occurrence = occurrences
-            .update(occurrence.id, Some(skull.id), Some(1.0), Some(millis(1)))
+            .update(
+                occurrence.id,
+                Some(skull.id),
+                Some(1.0),
+                Some(millis(1)),
+                None,
+            )
             .await
             .unwrap();
 
@@ -855,7 +2747,7 @@ mod tests {
         let (store, skull) = skulled_store().await;
         let other_id = store
             .skulls()
-            .create("two", 2, "two", 2.0, None)
+            .create("two", 2, "two", 2.0, None, types::Period::default())
             .await
             .unwrap()
             .id;
@@ -863,7 +2755,7 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let occurrence = occurrences
-            .update(occurrence.id, Some(other_id), None, None)
+            .update(occurrence.id, Some(other_id), None, None, None)
             .await
             .unwrap();
 
@@ -873,7 +2765,7 @@ mod tests {
         assert_eq!(occurrence.millis, millis(1));
 
         let occurrence = occurrences
-            .update(occurrence.id, None, Some(2.0), Some(millis(2)))
+            .update(occurrence.id, None, Some(2.0), Some(millis(2)), None)
             .await
             .unwrap();
         assert_eq!(types::Id::from(occurrence.id), 1);
@@ -889,7 +2781,7 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let err = occurrences
-            .update(occurrence.id, None, None, None)
+            .update(occurrence.id, None, None, None, None)
             .await
             .unwrap_err();
 
@@ -904,7 +2796,7 @@ mod tests {
         let occurrence = create_plain(&store, &skull).await;
         occurrences.delete(occurrence.id).await.unwrap();
         let err = occurrences
-            .update(occurrence.id, None, Some(2.0), None)
+            .update(occurrence.id, None, Some(2.0), None, None)
             .await
             .unwrap_err();
 
@@ -919,7 +2811,7 @@ mod tests {
         let (store, skull) = skulled_store().await;
         let other_id = store
             .skulls()
-            .create("two", 2, "two", 2.0, None)
+            .create("two", 2, "two", 2.0, None, types::Period::default())
             .await
             .unwrap()
             .id;
@@ -928,7 +2820,7 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let err = occurrences
-            .update(occurrence.id, Some(other_id), None, None)
+            .update(occurrence.id, Some(other_id), None, None, None)
             .await
             .unwrap_err();
 
@@ -942,7 +2834,7 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let err = occurrences
-            .update(occurrence.id, None, Some(-1.0), None)
+            .update(occurrence.id, None, Some(-1.0), None, None)
             .await
             .unwrap_err();
         assert_eq!(
@@ -958,7 +2850,7 @@ mod tests {
         let occurrences = store.occurrences();
         let occurrence = create_plain(&store, &skull).await;
         let err = occurrences
-            .update(occurrence.id, None, Some(0.0), None)
+            .update(occurrence.id, None, Some(0.0), None, None)
             .await
             .unwrap_err();
         assert_eq!(
@@ -967,6 +2859,122 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn update_batch() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let first = create_plain(&store, &skull).await;
+        let second = create_plain(&store, &skull).await;
+
+        let updated = occurrences
+            .update_batch([
+                (first.id, None, Some(2.0), None, None),
+                (second.id, None, None, Some(millis(2)), None),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            updated,
+            vec![
+                types::Occurrence {
+                    amount: 2.0,
+                    ..first
+                },
+                types::Occurrence {
+                    millis: millis(2),
+                    ..second
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn update_batch_err_empty() {
+        let (store, _) = skulled_store().await;
+
+        let err = store.occurrences().update_batch([]).await.unwrap_err();
+
+        assert_eq!(err.to_string(), Error::NoChanges.to_string());
+    }
+
+    #[tokio::test]
+    async fn update_batch_err_rollback() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let occurrence = create_plain(&store, &skull).await;
+
+        occurrences
+            .update_batch([
+                (occurrence.id, None, Some(2.0), None, None),
+                (occurrence.id, None, Some(-1.0), None, None),
+            ])
+            .await
+            .unwrap_err();
+
+        assert_eq!(occurrences.list().await.unwrap(), vec![occurrence]);
+    }
+
+    #[tokio::test]
+    async fn note() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let occurrence = occurrences
+            .create([(skull.id, 1.0, millis(1), Some(String::from("note")))])
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(occurrence.note.as_deref(), Some("note"));
+
+        let occurrence = occurrences
+            .update(
+                occurrence.id,
+                None,
+                None,
+                None,
+                Some(Some(String::from("other"))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(occurrence.note.as_deref(), Some("other"));
+
+        let occurrence = occurrences
+            .update(occurrence.id, None, Some(2.0), None, None)
+            .await
+            .unwrap();
+        assert_eq!(occurrence.note.as_deref(), Some("other"));
+
+        let occurrence = occurrences
+            .update(occurrence.id, None, None, None, Some(None))
+            .await
+            .unwrap();
+        assert_eq!(occurrence.note, None);
+        assert_eq!(occurrences.list().await.unwrap(), vec![occurrence]);
+    }
+
+    #[tokio::test]
+    async fn note_err_empty() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let err = occurrences
+            .create([(skull.id, 1.0, millis(1), Some(String::from(" ")))])
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::InvalidParameter("note").to_string());
+
+        let occurrence = create_plain(&store, &skull).await;
+        let err = occurrences
+            .update(occurrence.id, None, None, None, Some(Some(String::new())))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::InvalidParameter("note").to_string());
+    }
+
     #[tokio::test]
     async fn delete() {
         let (store, skull) = skulled_store().await;
@@ -990,4 +2998,139 @@ mod tests {
             Error::NotFound(occurrence.id.into()).to_string()
         );
     }
+
+    #[tokio::test]
+    async fn delete_batch() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let first = create_plain(&store, &skull).await;
+        let second = create_plain(&store, &skull).await;
+        let third = create_plain(&store, &skull).await;
+
+        let deleted = occurrences
+            .delete_batch([first.id, third.id])
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, vec![first.id, third.id]);
+        assert_eq!(occurrences.list().await.unwrap(), vec![second]);
+    }
+
+    #[tokio::test]
+    async fn delete_batch_err_rollback() {
+        let (store, skull) = skulled_store().await;
+
+        let occurrences = store.occurrences();
+        let occurrence = create_plain(&store, &skull).await;
+
+        let err = occurrences
+            .delete_batch([occurrence.id, occurrence.id])
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(occurrence.id.into()).to_string()
+        );
+        assert_eq!(occurrences.list().await.unwrap(), vec![occurrence]);
+    }
+
+    #[tokio::test]
+    async fn delete_search() {
+        let (store, (skull, _), [_, a, _, b, c, d]) = prepare_search().await;
+
+        let occurrences = store.occurrences();
+        let deleted = occurrences
+            .delete_search(
+                Some(&std::collections::HashSet::from([skull])),
+                None,
+                None,
+                Some(millis(3)),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, vec![b.id, c.id, d.id]);
+
+        let remaining = occurrences
+            .search(
+                Some(&std::collections::HashSet::from([skull])),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec![a]);
+    }
+
+    #[tokio::test]
+    async fn delete_search_limit() {
+        let (store, _, [a, b, c, d, e, f]) = prepare_search().await;
+
+        let occurrences = store.occurrences();
+        let deleted = occurrences
+            .delete_search(None, None, None, None, Some(2), None)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, vec![a.id, b.id]);
+        assert_eq!(
+            occurrences
+                .search(None, None, None, None, None, None)
+                .await
+                .unwrap(),
+            vec![c, d, e, f]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_search_undo() {
+        let (store, _, occurrences) = prepare_search().await;
+
+        store
+            .occurrences()
+            .delete_search(None, None, Some(millis(3)), None, None, None)
+            .await
+            .unwrap();
+        store.journal().undo().await.unwrap();
+
+        assert_eq!(
+            store
+                .occurrences()
+                .search(None, None, None, None, None, None)
+                .await
+                .unwrap(),
+            occurrences
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_search_err_no_filters() {
+        let (store, _, occurrences) = prepare_search().await;
+
+        let err = store
+            .occurrences()
+            .delete_search(None, None, None, None, None, None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("search").to_string()
+        );
+        assert_eq!(
+            store
+                .occurrences()
+                .search(None, None, None, None, None, None)
+                .await
+                .unwrap(),
+            occurrences
+        );
+    }
 }
@@ -0,0 +1,289 @@
+use crate::{Error, Result, Store};
+
+pub struct Settings<'a> {
+    store: &'a Store,
+}
+
+impl<'a> Settings<'a> {
+    pub(super) fn new(store: &'a Store) -> Self {
+        Self { store }
+    }
+}
+
+impl Settings<'_> {
+    #[tracing::instrument(skip(self), err)]
+    pub async fn get(&self) -> Result<types::Settings> {
+        let mut connection = self.store.acquire().await?;
+        fetch(&mut connection).await
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn set<I: IntoIterator<Item = types::Setting> + std::fmt::Debug>(
+        &self,
+        settings: I,
+    ) -> Result<types::Settings> {
+        let settings = settings
+            .into_iter()
+            .map(check_setting)
+            .collect::<Result<Vec<_>>>()?;
+
+        if settings.is_empty() {
+            return Err(Error::NoChanges);
+        }
+
+        let mut tx = self.store.begin().await?;
+
+        for setting in settings {
+            let key = setting.key();
+
+            // Unsetting a value falls back to its default
+            if setting == types::Setting::Currency(None) {
+                sqlx::query!(
+                    r#"
+                    DELETE FROM
+                        settings
+                    WHERE
+                        key = $1
+                    "#,
+                    key,
+                )
+                .execute(tx.as_mut())
+                .await?;
+                continue;
+            }
+
+            let value = into_value(&setting)?;
+            sqlx::query!(
+                r#"
+                INSERT INTO settings (
+                    key,
+                    value
+                ) VALUES (
+                    $1,
+                    $2
+                )
+                ON CONFLICT (key) DO UPDATE SET
+                    value = excluded.value
+                "#,
+                key,
+                value,
+            )
+            .execute(tx.as_mut())
+            .await?;
+        }
+
+        let settings = fetch(&mut tx).await?;
+
+        tx.commit().await?;
+        Ok(settings)
+    }
+}
+
+pub(super) async fn fetch(connection: &mut sqlx::SqliteConnection) -> Result<types::Settings> {
+    let entries = sqlx::query!(
+        r#"
+        SELECT
+            key,
+            value
+        FROM
+            settings
+        "#
+    )
+    .fetch_all(connection)
+    .await?;
+
+    let mut settings = types::Settings::default();
+    for entry in entries {
+        match from_entry(&entry.key, &entry.value) {
+            Ok(setting) => settings.apply(setting),
+            // Entries are only ever written through `set`, so this is a key that is not known
+            Err(error) => tracing::warn!(key = %entry.key, %error, "Ignoring setting"),
+        }
+    }
+
+    Ok(settings)
+}
+
+/// Extracts the JSON value of `setting`, which is stored under its key
+fn into_value(setting: &types::Setting) -> Result<String> {
+    let mut entry = serde_json::to_value(setting)?;
+    let value = entry
+        .get_mut("value")
+        .map(serde_json::Value::take)
+        .unwrap_or_default();
+    serde_json::to_string(&value).map_err(Into::into)
+}
+
+fn from_entry(key: &str, value: &str) -> Result<types::Setting> {
+    let value = serde_json::from_str::<serde_json::Value>(value)?;
+    serde_json::from_value(serde_json::json!({ "key": key, "value": value })).map_err(Into::into)
+}
+
+fn check_setting(setting: types::Setting) -> Result<types::Setting> {
+    let valid = match &setting {
+        types::Setting::Timezone(timezone) => timezone.unsigned_abs() < 24 * 60,
+        types::Setting::DayStart(day_start) => *day_start < 24,
+        types::Setting::Window(window) => *window > 0,
+        types::Setting::QuickCount(quick_count) => *quick_count > 0,
+        types::Setting::Currency(Some(currency)) => {
+            return super::check_non_empty(currency, "currency")
+                .map(|currency| types::Setting::Currency(Some(String::from(currency))));
+        }
+        types::Setting::Currency(None) => true,
+    };
+
+    if valid {
+        Ok(setting)
+    } else {
+        Err(Error::InvalidParameter(setting.key()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn get_default() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let settings = store.settings().get().await.unwrap();
+        assert_eq!(settings, types::Settings::default());
+    }
+
+    #[tokio::test]
+    async fn set() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let settings = store
+            .settings()
+            .set([
+                types::Setting::Timezone(120),
+                types::Setting::DayStart(5),
+                types::Setting::Window(3),
+                types::Setting::QuickCount(6),
+                types::Setting::Currency(Some(String::from(" € "))),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(
+            settings,
+            types::Settings {
+                timezone: 120,
+                day_start: 5,
+                window: 3,
+                quick_count: 6,
+                currency: Some(String::from("€")),
+            }
+        );
+        assert_eq!(store.settings().get().await.unwrap(), settings);
+    }
+
+    #[tokio::test]
+    async fn set_partial() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        store
+            .settings()
+            .set([types::Setting::Timezone(-180), types::Setting::DayStart(4)])
+            .await
+            .unwrap();
+        let settings = store
+            .settings()
+            .set([types::Setting::DayStart(6)])
+            .await
+            .unwrap();
+        assert_eq!(
+            settings,
+            types::Settings {
+                timezone: -180,
+                day_start: 6,
+                ..types::Settings::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn set_unset() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        store
+            .settings()
+            .set([types::Setting::Currency(Some(String::from("USD")))])
+            .await
+            .unwrap();
+        let settings = store
+            .settings()
+            .set([types::Setting::Currency(None)])
+            .await
+            .unwrap();
+        assert_eq!(settings, types::Settings::default());
+    }
+
+    #[tokio::test]
+    async fn get_unknown_key() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('unknown', '1'), ('window', '4')")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+
+        let settings = store.settings().get().await.unwrap();
+        assert_eq!(
+            settings,
+            types::Settings {
+                window: 4,
+                ..types::Settings::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn set_err_no_changes() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let err = store
+            .settings()
+            .set(Vec::<types::Setting>::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), Error::NoChanges.to_string());
+    }
+
+    #[tokio::test]
+    async fn set_err_invalid() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        for (setting, key) in [
+            (types::Setting::Timezone(1440), "timezone"),
+            (types::Setting::Timezone(i32::MIN), "timezone"),
+            (types::Setting::DayStart(24), "dayStart"),
+            (types::Setting::Window(0), "window"),
+            (types::Setting::QuickCount(0), "quickCount"),
+            (
+                types::Setting::Currency(Some(String::from(" "))),
+                "currency",
+            ),
+        ] {
+            let err = store.settings().set([setting]).await.unwrap_err();
+            assert_eq!(err.to_string(), Error::InvalidParameter(key).to_string());
+        }
+
+        // Nothing is stored if any of the settings is invalid
+        let err = store
+            .settings()
+            .set([types::Setting::Window(3), types::Setting::DayStart(24)])
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("dayStart").to_string()
+        );
+
+        assert_eq!(
+            store.settings().get().await.unwrap(),
+            types::Settings::default()
+        );
+    }
+}
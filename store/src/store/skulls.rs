@@ -1,5 +1,25 @@
 use crate::{Error, Result, Store};
 
+/// The longest rolling limit period, in hours, which is a leap year
+pub const MAX_PERIOD_HOURS: u32 = 24 * 366;
+
+/// The audited state of a skull, carrying its price history so a replay can restore it exactly
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(super) struct Snapshot {
+    #[serde(flatten)]
+    pub(super) skull: types::Skull,
+    // Audits recorded before the price history was snapshotted have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) prices: Option<Vec<Price>>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Price {
+    price: f32,
+    effective_from: types::Millis,
+}
+
 pub struct Skulls<'a> {
     store: &'a Store,
 }
@@ -12,7 +32,7 @@ impl<'a> Skulls<'a> {
 
 impl Skulls<'_> {
     #[tracing::instrument(skip(self), err)]
-    pub async fn list(&self) -> Result<Vec<types::Skull>> {
+    pub async fn list(&self, include_archived: bool) -> Result<Vec<types::Skull>> {
         sqlx::query_as!(
             types::Skull,
             r#"
@@ -22,16 +42,28 @@ impl Skulls<'_> {
                 "color" AS "color: u32",
                 "icon",
                 "price" AS "price: f32",
-                "limit" as "limit: f32"
+                "limit" as "limit: f32",
+                "period" AS "period: types::Period",
+                "archived" AS "archived: bool"
             FROM
                 skulls
-            "#
+            WHERE
+                $1
+                OR NOT "archived"
+            "#,
+            include_archived,
         )
-        .fetch_all(&self.store.pool)
+        .fetch_all(self.store.acquire().await?.as_mut())
         .await
         .map_err(Into::into)
     }
 
+    #[tracing::instrument(skip(self), err)]
+    pub async fn get(&self, id: types::SkullId) -> Result<types::Skull> {
+        let mut connection = self.store.acquire().await?;
+        fetch(&mut connection, id).await
+    }
+
     #[tracing::instrument(skip(self), err)]
     pub async fn create<
         Name: AsRef<str> + std::fmt::Debug,
@@ -43,6 +75,7 @@ impl Skulls<'_> {
         icon: Icon,
         price: f32,
         limit: Option<f32>,
+        period: types::Period,
     ) -> Result<types::Skull> {
         let name = super::check_non_empty(name.as_ref(), "name")?;
         let icon = super::check_non_empty(icon.as_ref(), "icon")?;
@@ -54,8 +87,12 @@ impl Skulls<'_> {
         {
             return Err(Error::InvalidParameter("limit"));
         }
+        let period = check_period(period)?;
 
-        sqlx::query_as!(
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let skull = sqlx::query_as!(
             types::Skull,
             r#"
             INSERT INTO skulls (
@@ -63,34 +100,54 @@ impl Skulls<'_> {
                 "color",
                 "icon",
                 "price",
-                "limit"
+                "limit",
+                "period"
             ) VALUES (
                 $1,
                 $2,
                 $3,
                 $4,
-                $5
+                $5,
+                $6
             ) RETURNING
                 "id" AS "id: types::SkullId",
                 "name",
                 "color" AS "color: u32",
                 "icon",
                 "price" AS "price: f32",
-                "limit" as "limit: f32"
+                "limit" as "limit: f32",
+                "period" AS "period: types::Period",
+                "archived" AS "archived: bool"
             "#,
             name,
             color,
             icon,
             price,
             limit,
+            period,
         )
-        .fetch_one(&self.store.pool)
-        .await
-        .map_err(Into::into)
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        record_price(&mut tx, skull.id, skull.price).await?;
+        let after = snapshot(&mut tx, skull).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Create,
+            None,
+            Some(&after),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(after.skull)
     }
 
     // allow(clippy::option_option): This is necessary to convey a change into None
-    #[allow(clippy::option_option)]
+    // allow(clippy::too_many_arguments): Each field is individually optional
+    #[allow(clippy::option_option, clippy::too_many_arguments)]
     #[tracing::instrument(skip(self), err)]
     pub async fn update<
         Name: AsRef<str> + std::fmt::Debug,
@@ -103,6 +160,7 @@ impl Skulls<'_> {
         icon: Option<Icon>,
         price: Option<f32>,
         limit: Option<Option<f32>>,
+        period: Option<types::Period>,
     ) -> Result<types::Skull> {
         let mut has_fields = false;
         let mut builder = sqlx::QueryBuilder::new("UPDATE skulls SET ");
@@ -148,50 +206,523 @@ impl Skulls<'_> {
             }
             limit
         });
+        push_field!(period, check_period(period)?);
 
-        if has_fields {
-            builder
-                .push(" WHERE id = ")
-                .push_bind(id)
-                .push(
-                    r#"
-                    RETURNING
-                        "id",
-                        "name",
-                        "color",
-                        "icon",
-                        "price",
-                        "limit"
-                    "#,
-                )
-                .build_query_as::<types::Skull>()
-                .fetch_optional(&self.store.pool)
-                .await
-                .map_err(Into::into)
-                .and_then(|r| r.ok_or(Error::NotFound(id.into())))
-        } else {
-            Err(Error::NoChanges)
+        if !has_fields {
+            return Err(Error::NoChanges);
+        }
+
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let before = fetch(&mut tx, id).await?;
+        let before = snapshot(&mut tx, before).await?;
+
+        let skull = builder
+            .push(" WHERE id = ")
+            .push_bind(id)
+            .push(
+                r#"
+                RETURNING
+                    "id",
+                    "name",
+                    "color",
+                    "icon",
+                    "price",
+                    "limit",
+                    "period",
+                    "archived"
+                "#,
+            )
+            .build_query_as::<types::Skull>()
+            .fetch_optional(tx.as_mut())
+            .await?
+            .ok_or(Error::NotFound(id.into()))?;
+
+        if price.is_some() {
+            record_price(&mut tx, skull.id, skull.price).await?;
+        }
+        let after = snapshot(&mut tx, skull).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(after.skull)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn archive(&self, id: types::SkullId, archived: bool) -> Result<types::Skull> {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let before = fetch(&mut tx, id).await?;
+        let before = snapshot(&mut tx, before).await?;
+
+        let skull = sqlx::query_as!(
+            types::Skull,
+            r#"
+            UPDATE
+                skulls
+            SET
+                "archived" = $2
+            WHERE
+                id = $1
+            RETURNING
+                "id" AS "id: types::SkullId",
+                "name",
+                "color" AS "color: u32",
+                "icon",
+                "price" AS "price: f32",
+                "limit" as "limit: f32",
+                "period" AS "period: types::Period",
+                "archived" AS "archived: bool"
+            "#,
+            id,
+            archived,
+        )
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        let after = snapshot(&mut tx, skull).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(after.skull)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn merge(&self, from: types::SkullId, into: types::SkullId) -> Result {
+        if from == into {
+            return Err(Error::ConflictingField("from", "into"));
+        }
+
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        sqlx::query!(
+            r#"
+            SELECT
+                id
+            FROM
+                skulls
+            WHERE
+                id = $1
+            "#,
+            into,
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(Error::NotFound(into.into()))?;
+
+        // The price history cascades with the skull, so it is snapshotted beforehand
+        let before = fetch(&mut tx, from).await?;
+        let before = snapshot(&mut tx, before).await?;
+
+        let occurrences = sqlx::query_as!(
+            types::Occurrence,
+            r#"
+            UPDATE
+                occurrences
+            SET
+                skull = $2
+            WHERE
+                skull = $1
+            RETURNING
+                id AS "id: types::OccurrenceId",
+                skull AS "skull: types::SkullId",
+                amount AS "amount: f32",
+                millis AS "millis: types::Millis",
+                note
+            "#,
+            from,
+            into,
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        for after in occurrences {
+            let before = types::Occurrence {
+                skull: from,
+                ..after.clone()
+            };
+            super::audits::record(
+                &mut tx,
+                self.store,
+                Some(operation),
+                types::Entity::Occurrence,
+                types::Mutation::Update,
+                Some(&before),
+                Some(&after),
+            )
+            .await?;
         }
+
+        audit_tags(&mut tx, self.store, operation, from, Some(into)).await?;
+
+        sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO skull_tags (
+                skull,
+                tag
+            ) SELECT
+                $2,
+                tag
+            FROM
+                skull_tags
+            WHERE
+                skull = $1
+            "#,
+            from,
+            into,
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM
+                skulls
+            WHERE
+                id = $1
+            "#,
+            from,
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Delete,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn price(&self, id: types::SkullId, millis: types::Millis) -> Result<f32> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT
+                COALESCE(
+                    (
+                        SELECT
+                            price
+                        FROM
+                            prices
+                        WHERE
+                            skull = $1
+                            AND effective_from <= $2
+                        ORDER BY
+                            effective_from DESC
+                        LIMIT
+                            1
+                    ),
+                    (
+                        SELECT
+                            price
+                        FROM
+                            prices
+                        WHERE
+                            skull = $1
+                        ORDER BY
+                            effective_from
+                        LIMIT
+                            1
+                    ),
+                    (
+                        SELECT
+                            price
+                        FROM
+                            skulls
+                        WHERE
+                            id = $1
+                    )
+                ) AS "price: f32"
+            "#,
+            id,
+            millis,
+        )
+        .fetch_one(self.store.acquire().await?.as_mut())
+        .await?
+        .ok_or(Error::NotFound(id.into()))
     }
 
     #[tracing::instrument(skip(self), err)]
     pub async fn delete(&self, id: types::SkullId) -> Result {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        // The price history cascades with the skull, so it is snapshotted beforehand
+        let before = fetch(&mut tx, id).await?;
+        let before = snapshot(&mut tx, before).await?;
+
+        audit_tags(&mut tx, self.store, operation, id, None).await?;
+
         sqlx::query!(
             r#"
             DELETE FROM
                 skulls
             WHERE
                 id = $1
-            RETURNING
-                id
             "#,
             id,
         )
-        .map(|_| ())
-        .fetch_optional(&self.store.pool)
-        .await
-        .map_err(Into::into)
-        .and_then(|r| r.ok_or(Error::NotFound(id.into())))
+        .execute(tx.as_mut())
+        .await?;
+
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Delete,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+pub(super) async fn fetch(
+    connection: &mut sqlx::SqliteConnection,
+    id: types::SkullId,
+) -> Result<types::Skull> {
+    sqlx::query_as!(
+        types::Skull,
+        r#"
+        SELECT
+            "id" AS "id: types::SkullId",
+            "name",
+            "color" AS "color: u32",
+            "icon",
+            "price" AS "price: f32",
+            "limit" as "limit: f32",
+            "period" AS "period: types::Period",
+            "archived" AS "archived: bool"
+        FROM
+            skulls
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(connection)
+    .await?
+    .ok_or(Error::NotFound(id.into()))
+}
+
+async fn audit(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    mutation: types::Mutation,
+    before: Option<&Snapshot>,
+    after: Option<&Snapshot>,
+) -> Result {
+    super::audits::record(
+        tx,
+        store,
+        Some(operation),
+        types::Entity::Skull,
+        mutation,
+        before,
+        after,
+    )
+    .await
+}
+
+/// Audits the tags losing `skull`, and gaining `into` if given, so a replay can restore them
+async fn audit_tags(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    skull: types::SkullId,
+    into: Option<types::SkullId>,
+) -> Result {
+    let tags = sqlx::query_scalar!(
+        r#"
+        SELECT
+            tag AS "tag: types::TagId"
+        FROM
+            skull_tags
+        WHERE
+            skull = $1
+        ORDER BY
+            tag
+        "#,
+        skull,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for tag in tags {
+        let before = super::tags::fetch(tx, tag).await?;
+        let mut after = before.clone();
+        after.skulls.retain(|id| *id != skull);
+        if let Some(into) = into
+            && !after.skulls.contains(&into)
+        {
+            after.skulls.push(into);
+            after.skulls.sort();
+        }
+
+        super::audits::record(
+            tx,
+            store,
+            Some(operation),
+            types::Entity::Tag,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&after),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn snapshot(
+    connection: &mut sqlx::SqliteConnection,
+    skull: types::Skull,
+) -> Result<Snapshot> {
+    let prices = sqlx::query_as!(
+        Price,
+        r#"
+        SELECT
+            price AS "price: f32",
+            effective_from AS "effective_from: types::Millis"
+        FROM
+            prices
+        WHERE
+            skull = $1
+        ORDER BY
+            effective_from
+        "#,
+        skull.id,
+    )
+    .fetch_all(connection)
+    .await?;
+
+    Ok(Snapshot {
+        skull,
+        prices: Some(prices),
+    })
+}
+
+pub(super) async fn restore_prices(
+    tx: &mut sqlx::SqliteConnection,
+    skull: types::SkullId,
+    prices: &[Price],
+) -> Result {
+    sqlx::query!(
+        r#"
+        DELETE FROM
+            prices
+        WHERE
+            skull = $1
+        "#,
+        skull,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    for price in prices {
+        sqlx::query!(
+            r#"
+            INSERT INTO prices (
+                skull,
+                price,
+                effective_from
+            ) VALUES (
+                $1,
+                $2,
+                $3
+            )
+            "#,
+            skull,
+            price.price,
+            price.effective_from,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub(super) async fn record_price(
+    tx: &mut sqlx::SqliteConnection,
+    skull: types::SkullId,
+    price: f32,
+) -> Result {
+    let now = types::Millis::from(chrono::Utc::now());
+
+    sqlx::query!(
+        r#"
+        INSERT INTO prices (
+            skull,
+            price,
+            effective_from
+        )
+        SELECT
+            $1,
+            $2,
+            $3
+        WHERE
+            $2 IS NOT (
+                SELECT
+                    price
+                FROM
+                    prices
+                WHERE
+                    skull = $1
+                ORDER BY
+                    effective_from DESC
+                LIMIT
+                    1
+            )
+        ON CONFLICT (skull, effective_from) DO UPDATE SET
+            price = excluded.price
+        "#,
+        skull,
+        price,
+        now,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+fn check_period(period: types::Period) -> Result<types::Period> {
+    match period {
+        types::Period::Hours(hours) if hours == 0 || hours > MAX_PERIOD_HOURS => {
+            Err(Error::InvalidParameter("period"))
+        }
+        period => Ok(period),
     }
 }
 
@@ -204,13 +735,16 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let one = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let one = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         let two = skulls
-            .create("two", 2, "icon2", 2.0, Some(2.0))
+            .create("two", 2, "icon2", 2.0, Some(2.0), types::Period::default())
             .await
             .unwrap();
 
-        let skulls = skulls.list().await.unwrap();
+        let skulls = skulls.list(true).await.unwrap();
         assert_eq!(skulls, vec![one, two]);
     }
 
@@ -219,16 +753,77 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skulls = skulls.list().await.unwrap();
+        let skulls = skulls.list(true).await.unwrap();
         assert_eq!(skulls, Vec::new());
     }
 
+    #[tokio::test]
+    async fn get() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls
+            .create("two", 2, "icon2", 2.0, Some(2.0), types::Period::default())
+            .await
+            .unwrap();
+
+        let skull = skulls.get(two.id).await.unwrap();
+        assert_eq!(skull, two);
+    }
+
+    #[tokio::test]
+    async fn get_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls.delete(skull.id).await.unwrap();
+
+        let err = skulls.get(skull.id).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(skull.id.into()).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn list_archived() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let one = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls.archive(two.id, true).await.unwrap();
+
+        let all = skulls.list(true).await.unwrap();
+        assert_eq!(all, vec![one.clone(), two]);
+
+        let active = skulls.list(false).await.unwrap();
+        assert_eq!(active, vec![one]);
+    }
+
     #[tokio::test]
     async fn create() {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         assert_eq!(types::Id::from(skull.id), 1);
         assert_eq!(skull.name, "one");
@@ -244,11 +839,14 @@ mod tests {
 
         let skulls = store.skulls();
 
-        let err = skulls.create("", 1, "icon1", 1.0, None).await.unwrap_err();
+        let err = skulls
+            .create("", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("name").to_string());
 
         let err = skulls
-            .create(" 	 ", 1, "icon1", 1.0, None)
+            .create(" 	 ", 1, "icon1", 1.0, None, types::Period::default())
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("name").to_string());
@@ -259,10 +857,13 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .create("one", 2, "icon2", 2.0, None)
+            .create("one", 2, "icon2", 2.0, None, types::Period::default())
             .await
             .unwrap_err();
 
@@ -277,10 +878,13 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .create("two", 1, "icon2", 2.0, None)
+            .create("two", 1, "icon2", 2.0, None, types::Period::default())
             .await
             .unwrap_err();
 
@@ -296,10 +900,16 @@ mod tests {
 
         let skulls = store.skulls();
 
-        let err = skulls.create("one", 1, "", 1.0, None).await.unwrap_err();
+        let err = skulls
+            .create("one", 1, "", 1.0, None, types::Period::default())
+            .await
+            .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("icon").to_string());
 
-        let err = skulls.create("one", 1, " 	 ", 1.0, None).await.unwrap_err();
+        let err = skulls
+            .create("one", 1, " 	 ", 1.0, None, types::Period::default())
+            .await
+            .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("icon").to_string());
     }
 
@@ -308,10 +918,13 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .create("two", 2, "icon1", 2.0, None)
+            .create("two", 2, "icon1", 2.0, None, types::Period::default())
             .await
             .unwrap_err();
 
@@ -327,7 +940,7 @@ mod tests {
 
         let skulls = store.skulls();
         let err = skulls
-            .create("one", 1, "icon1", -1.0, None)
+            .create("one", 1, "icon1", -1.0, None, types::Period::default())
             .await
             .unwrap_err();
 
@@ -343,7 +956,7 @@ mod tests {
 
         let skulls = store.skulls();
         let err = skulls
-            .create("one", 1, "icon1", 1.0, Some(-1.0))
+            .create("one", 1, "icon1", 1.0, Some(-1.0), types::Period::default())
             .await
             .unwrap_err();
 
@@ -353,12 +966,64 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn create_err_period_too_long() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        skulls
+            .create(
+                "one",
+                1,
+                "icon1",
+                1.0,
+                None,
+                types::Period::Hours(MAX_PERIOD_HOURS),
+            )
+            .await
+            .unwrap();
+
+        let err = skulls
+            .create(
+                "two",
+                2,
+                "icon2",
+                1.0,
+                None,
+                types::Period::Hours(MAX_PERIOD_HOURS + 1),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("period").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn create_err_period_zero() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let err = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::Hours(0))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("period").to_string()
+        );
+    }
+
     #[tokio::test]
     async fn update() {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         let skull = skulls
             .update(
                 skull.id,
@@ -367,6 +1032,7 @@ mod tests {
                 Some("icon2"),
                 Some(2.0),
                 Some(Some(2.0)),
+                Some(types::Period::Week),
             )
             .await
             .unwrap();
@@ -377,6 +1043,7 @@ mod tests {
         assert_eq!(skull.icon, "icon2");
         assert_eq!(skull.price.to_string(), 2.0.to_string());
         assert_eq!(skull.limit, Some(2.0));
+        assert_eq!(skull.period, types::Period::Week);
     }
 
     #[tokio::test]
@@ -384,7 +1051,10 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         let skull = skulls
             .update(
                 skull.id,
@@ -393,6 +1063,7 @@ mod tests {
                 Some("icon1"),
                 Some(1.0),
                 Some(None),
+                None,
             )
             .await
             .unwrap();
@@ -410,10 +1081,21 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let skull = skulls
-            .update(skull.id, Some("two"), None, None::<String>, None, None)
+            .update(
+                skull.id,
+                Some("two"),
+                None,
+                None::<String>,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap();
 
@@ -425,10 +1107,18 @@ mod tests {
         assert_eq!(skull.limit, None);
 
         let skull = skulls
-            .update(skull.id, None::<String>, Some(2), Some("icon2"), None, None)
-            .await
-            .unwrap();
-
+            .update(
+                skull.id,
+                None::<String>,
+                Some(2),
+                Some("icon2"),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
         assert_eq!(types::Id::from(skull.id), 1);
         assert_eq!(skull.name, "two");
         assert_eq!(skull.color, 2);
@@ -444,6 +1134,7 @@ mod tests {
                 None::<String>,
                 Some(2.0),
                 Some(Some(1.0)),
+                None,
             )
             .await
             .unwrap();
@@ -463,6 +1154,7 @@ mod tests {
                 None::<String>,
                 None,
                 Some(None),
+                None,
             )
             .await
             .unwrap();
@@ -480,9 +1172,20 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         let err = skulls
-            .update(skull.id, None::<String>, None, None::<String>, None, None)
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap_err();
 
@@ -494,7 +1197,10 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         skulls.delete(skull.id).await.unwrap();
         let err = skulls
             .update(
@@ -504,6 +1210,7 @@ mod tests {
                 Some("icon2"),
                 Some(2.0),
                 Some(Some(2.0)),
+                None,
             )
             .await
             .unwrap_err();
@@ -519,16 +1226,27 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .update(skull.id, Some(""), None, None::<String>, None, None)
+            .update(skull.id, Some(""), None, None::<String>, None, None, None)
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("name").to_string());
 
         let err = skulls
-            .update(skull.id, Some(" 	 "), None, None::<String>, None, None)
+            .update(
+                skull.id,
+                Some(" 	 "),
+                None,
+                None::<String>,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("name").to_string());
@@ -539,11 +1257,25 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
-        skulls.create("two", 2, "icon2", 2.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .update(skull.id, Some("two"), None, None::<String>, None, None)
+            .update(
+                skull.id,
+                Some("two"),
+                None,
+                None::<String>,
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap_err();
 
@@ -558,8 +1290,14 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
-        skulls.create("two", 2, "icon2", 2.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
             .update(
@@ -569,6 +1307,7 @@ mod tests {
                 None::<String>,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap_err();
@@ -584,16 +1323,27 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .update(skull.id, None::<String>, None, Some(""), None, None)
+            .update(skull.id, None::<String>, None, Some(""), None, None, None)
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("icon").to_string());
 
         let err = skulls
-            .update(skull.id, None::<String>, None, Some(" 	 "), None, None)
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                Some(" 	 "),
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), Error::InvalidParameter("icon").to_string());
@@ -604,11 +1354,25 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
-        skulls.create("two", 2, "icon2", 2.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
-            .update(skull.id, None::<String>, None, Some("icon2"), None, None)
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                Some("icon2"),
+                None,
+                None,
+                None,
+            )
             .await
             .unwrap_err();
 
@@ -623,7 +1387,10 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
             .update(
@@ -633,6 +1400,7 @@ mod tests {
                 None::<String>,
                 Some(-1.0),
                 None,
+                None,
             )
             .await
             .unwrap_err();
@@ -647,7 +1415,10 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
 
         let err = skulls
             .update(
@@ -657,6 +1428,7 @@ mod tests {
                 None::<String>,
                 None,
                 Some(Some(-1.0)),
+                None,
             )
             .await
             .unwrap_err();
@@ -666,12 +1438,80 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn udpate_err_period_zero() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let err = skulls
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                None,
+                None,
+                Some(types::Period::Hours(0)),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidParameter("period").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn archive() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        assert!(!skull.archived);
+
+        let archived = skulls.archive(skull.id, true).await.unwrap();
+        assert!(archived.archived);
+        assert_eq!(archived.name, skull.name);
+
+        let unarchived = skulls.archive(skull.id, false).await.unwrap();
+        assert_eq!(unarchived, skull);
+    }
+
+    #[tokio::test]
+    async fn archive_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls.delete(skull.id).await.unwrap();
+
+        let err = skulls.archive(skull.id, true).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(skull.id.into()).to_string()
+        );
+    }
+
     #[tokio::test]
     async fn delete() {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         skulls.delete(skull.id).await.unwrap();
     }
 
@@ -680,7 +1520,10 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         skulls.delete(skull.id).await.unwrap();
 
         let err = skulls.delete(skull.id).await.unwrap_err();
@@ -695,10 +1538,13 @@ mod tests {
         let store = Store::in_memory(1).await.unwrap();
 
         let skulls = store.skulls();
-        let skull = skulls.create("one", 1, "icon1", 1.0, None).await.unwrap();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
         store
             .occurrences()
-            .create([(skull.id, 1.0, types::Millis::from(0))])
+            .create([(skull.id, 1.0, types::Millis::from(0), None)])
             .await
             .unwrap();
 
@@ -708,4 +1554,241 @@ mod tests {
             panic!("{err}");
         }
     }
+
+    #[tokio::test]
+    async fn merge() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let one = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let occurrences = store.occurrences();
+        occurrences
+            .create([
+                (one.id, 1.0, types::Millis::from(1), None),
+                (two.id, 2.0, types::Millis::from(2), None),
+            ])
+            .await
+            .unwrap();
+
+        skulls.merge(one.id, two.id).await.unwrap();
+
+        assert_eq!(skulls.list(true).await.unwrap(), vec![two.clone()]);
+        assert!(
+            occurrences
+                .list()
+                .await
+                .unwrap()
+                .iter()
+                .all(|o| o.skull == two.id)
+        );
+        assert_eq!(count_prices(&store, one.id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn merge_tags() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let one = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let tags = store.tags();
+        let shared = tags.create("shared").await.unwrap();
+        let single = tags.create("single").await.unwrap();
+        tags.attach(shared.id, one.id).await.unwrap();
+        tags.attach(shared.id, two.id).await.unwrap();
+        tags.attach(single.id, one.id).await.unwrap();
+
+        skulls.merge(one.id, two.id).await.unwrap();
+
+        let tags = tags.list().await.unwrap();
+        assert!(tags.iter().all(|t| t.skulls == vec![two.id]));
+    }
+
+    #[tokio::test]
+    async fn merge_err_same() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let err = skulls.merge(skull.id, skull.id).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::ConflictingField("from", "into").to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let one = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        let two = skulls
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+        store
+            .occurrences()
+            .create([(one.id, 1.0, types::Millis::from(1), None)])
+            .await
+            .unwrap();
+        skulls.delete(two.id).await.unwrap();
+
+        let err = skulls.merge(one.id, two.id).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NotFound(two.id.into()).to_string());
+
+        let err = skulls.merge(two.id, one.id).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NotFound(two.id.into()).to_string());
+
+        assert_eq!(skulls.list(true).await.unwrap(), vec![one]);
+    }
+
+    async fn backdate_prices(store: &Store, skull: types::SkullId) {
+        sqlx::query("UPDATE prices SET effective_from = 0 WHERE skull = $1")
+            .bind(skull)
+            .execute(&store.pool)
+            .await
+            .unwrap();
+    }
+
+    async fn count_prices(store: &Store, skull: types::SkullId) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM prices WHERE skull = $1")
+            .bind(skull)
+            .fetch_one(&store.pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn price() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let price = skulls
+            .price(skull.id, types::Millis::from(0))
+            .await
+            .unwrap();
+        assert_eq!(price.to_string(), 1.0.to_string());
+        assert_eq!(count_prices(&store, skull.id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn price_history() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        backdate_prices(&store, skull.id).await;
+
+        skulls
+            .update(
+                skull.id,
+                None::<String>,
+                None,
+                None::<String>,
+                Some(2.0),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let price = skulls
+            .price(skull.id, types::Millis::from(-1))
+            .await
+            .unwrap();
+        assert_eq!(price.to_string(), 1.0.to_string());
+
+        let price = skulls
+            .price(skull.id, types::Millis::from(1))
+            .await
+            .unwrap();
+        assert_eq!(price.to_string(), 1.0.to_string());
+
+        let price = skulls
+            .price(skull.id, types::Millis::from(i64::MAX))
+            .await
+            .unwrap();
+        assert_eq!(price.to_string(), 2.0.to_string());
+        assert_eq!(count_prices(&store, skull.id).await, 2);
+    }
+
+    #[tokio::test]
+    async fn price_history_unchanged() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        backdate_prices(&store, skull.id).await;
+
+        skulls
+            .update(
+                skull.id,
+                Some("two"),
+                None,
+                None::<String>,
+                Some(1.0),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(count_prices(&store, skull.id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn price_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skulls = store.skulls();
+        let skull = skulls
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+        skulls.delete(skull.id).await.unwrap();
+
+        let err = skulls
+            .price(skull.id, types::Millis::from(0))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(skull.id.into()).to_string()
+        );
+        assert_eq!(count_prices(&store, skull.id).await, 0);
+    }
 }
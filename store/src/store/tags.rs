@@ -0,0 +1,527 @@
+use crate::{Error, Result, Store};
+
+pub struct Tags<'a> {
+    store: &'a Store,
+}
+
+impl<'a> Tags<'a> {
+    pub(super) fn new(store: &'a Store) -> Self {
+        Self { store }
+    }
+}
+
+impl Tags<'_> {
+    #[tracing::instrument(skip(self), err)]
+    pub async fn list(&self) -> Result<Vec<types::Tag>> {
+        let mut tx = self.store.begin().await?;
+
+        let mut tags = sqlx::query!(
+            r#"
+            SELECT
+                id AS "id: types::TagId",
+                name
+            FROM
+                tags
+            ORDER BY
+                id
+            "#
+        )
+        .map(|r| types::Tag {
+            id: r.id,
+            name: r.name,
+            skulls: Vec::new(),
+        })
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let skull_tags = sqlx::query!(
+            r#"
+            SELECT
+                skull AS "skull: types::SkullId",
+                tag AS "tag: types::TagId"
+            FROM
+                skull_tags
+            ORDER BY
+                skull
+            "#
+        )
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        for skull_tag in skull_tags {
+            if let Ok(index) = tags.binary_search_by_key(&skull_tag.tag, |t| t.id) {
+                tags[index].skulls.push(skull_tag.skull);
+            }
+        }
+
+        Ok(tags)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn create<Name: AsRef<str> + std::fmt::Debug>(
+        &self,
+        name: Name,
+    ) -> Result<types::Tag> {
+        let name = check_name(name.as_ref())?;
+
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let tag = sqlx::query!(
+            r#"
+            INSERT INTO tags (
+                name
+            ) VALUES (
+                $1
+            ) RETURNING
+                id AS "id: types::TagId",
+                name
+            "#,
+            name,
+        )
+        .map(|r| types::Tag {
+            id: r.id,
+            name: r.name,
+            skulls: Vec::new(),
+        })
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Create,
+            None,
+            Some(&tag),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(tag)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn update<Name: AsRef<str> + std::fmt::Debug>(
+        &self,
+        id: types::TagId,
+        name: Option<Name>,
+    ) -> Result<types::Tag> {
+        let Some(name) = name else {
+            return Err(Error::NoChanges);
+        };
+        let name = check_name(name.as_ref())?;
+
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let before = fetch(&mut tx, id).await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE
+                tags
+            SET
+                name = $2
+            WHERE
+                id = $1
+            RETURNING
+                id
+            "#,
+            id,
+            name,
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(Error::NotFound(id.into()))?;
+
+        let tag = fetch(&mut tx, id).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&tag),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(tag)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn delete(&self, id: types::TagId) -> Result {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let before = fetch(&mut tx, id).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM
+                tags
+            WHERE
+                id = $1
+            "#,
+            id,
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Delete,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn attach(&self, id: types::TagId, skull: types::SkullId) -> Result<types::Tag> {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        // Checking before inserting, otherwise a missing tag is reported as a foreign key error
+        let before = fetch(&mut tx, id).await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO skull_tags (
+                skull,
+                tag
+            ) VALUES (
+                $1,
+                $2
+            )
+            "#,
+            skull,
+            id,
+        )
+        .execute(tx.as_mut())
+        .await?;
+
+        let tag = fetch(&mut tx, id).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&tag),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(tag)
+    }
+
+    #[tracing::instrument(skip(self), err)]
+    pub async fn detach(&self, id: types::TagId, skull: types::SkullId) -> Result<types::Tag> {
+        let mut tx = self.store.begin().await?;
+        let operation = super::journal::begin(&mut tx).await?;
+
+        let before = fetch(&mut tx, id).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM
+                skull_tags
+            WHERE
+                skull = $1
+                AND tag = $2
+            RETURNING
+                skull
+            "#,
+            skull,
+            id,
+        )
+        .fetch_optional(tx.as_mut())
+        .await?
+        .ok_or(Error::NotFound(skull.into()))?;
+
+        let tag = fetch(&mut tx, id).await?;
+        audit(
+            &mut tx,
+            self.store,
+            operation,
+            types::Mutation::Update,
+            Some(&before),
+            Some(&tag),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(tag)
+    }
+}
+
+async fn audit(
+    tx: &mut sqlx::SqliteConnection,
+    store: &Store,
+    operation: super::journal::Operation,
+    mutation: types::Mutation,
+    before: Option<&types::Tag>,
+    after: Option<&types::Tag>,
+) -> Result {
+    super::audits::record(
+        tx,
+        store,
+        Some(operation),
+        types::Entity::Tag,
+        mutation,
+        before,
+        after,
+    )
+    .await
+}
+
+pub(super) async fn fetch(tx: &mut sqlx::SqliteConnection, id: types::TagId) -> Result<types::Tag> {
+    let name = sqlx::query_scalar!(
+        r#"
+        SELECT
+            name
+        FROM
+            tags
+        WHERE
+            id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(Error::NotFound(id.into()))?;
+
+    let skulls = sqlx::query_scalar!(
+        r#"
+        SELECT
+            skull AS "skull: types::SkullId"
+        FROM
+            skull_tags
+        WHERE
+            tag = $1
+        ORDER BY
+            skull
+        "#,
+        id,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    Ok(types::Tag { id, name, skulls })
+}
+
+fn check_name(name: &str) -> Result<&str> {
+    let name = super::check_non_empty(name, "name")?;
+    if name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(name)
+    } else {
+        Err(Error::InvalidParameter("name"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn skulled_store() -> (Store, types::Skull) {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let skull = store
+            .skulls()
+            .create("one", 1, "icon1", 1.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        (store, skull)
+    }
+
+    #[tokio::test]
+    async fn list() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let one = tags.create("one").await.unwrap();
+        let two = tags.create("two").await.unwrap();
+        let one = tags.attach(one.id, skull.id).await.unwrap();
+
+        assert_eq!(tags.list().await.unwrap(), vec![one, two]);
+    }
+
+    #[tokio::test]
+    async fn list_empty() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags().list().await.unwrap();
+        assert_eq!(tags, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn create() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tag = store.tags().create("caffeine").await.unwrap();
+
+        assert_eq!(types::Id::from(tag.id), 1);
+        assert_eq!(tag.name, "caffeine");
+        assert_eq!(tag.skulls, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn create_err_name() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags();
+        for name in ["", " ", "with space", "with,comma"] {
+            let err = tags.create(name).await.unwrap_err();
+            assert_eq!(err.to_string(), Error::InvalidParameter("name").to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn create_err_duplicate() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags();
+        tags.create("tag").await.unwrap();
+        let err = tags.create("tag").await.unwrap_err();
+
+        if let Error::DuplicateEntry(_) = err {
+        } else {
+            panic!("{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn update() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.attach(tag.id, skull.id).await.unwrap();
+
+        let tag = tags.update(tag.id, Some("other")).await.unwrap();
+        assert_eq!(tag.name, "other");
+        assert_eq!(tag.skulls, vec![skull.id]);
+    }
+
+    #[tokio::test]
+    async fn update_err_no_changes() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        let err = tags.update(tag.id, None::<String>).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NoChanges.to_string());
+    }
+
+    #[tokio::test]
+    async fn update_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.delete(tag.id).await.unwrap();
+
+        let err = tags.update(tag.id, Some("other")).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NotFound(tag.id.into()).to_string());
+    }
+
+    #[tokio::test]
+    async fn delete() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.attach(tag.id, skull.id).await.unwrap();
+
+        tags.delete(tag.id).await.unwrap();
+        assert_eq!(tags.list().await.unwrap(), Vec::new());
+    }
+
+    #[tokio::test]
+    async fn delete_err_not_found() {
+        let store = Store::in_memory(1).await.unwrap();
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.delete(tag.id).await.unwrap();
+
+        let err = tags.delete(tag.id).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NotFound(tag.id.into()).to_string());
+    }
+
+    #[tokio::test]
+    async fn attach_and_detach() {
+        let (store, skull) = skulled_store().await;
+        let other = store
+            .skulls()
+            .create("two", 2, "icon2", 2.0, None, types::Period::default())
+            .await
+            .unwrap();
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+
+        let tag = tags.attach(tag.id, other.id).await.unwrap();
+        assert_eq!(tag.skulls, vec![other.id]);
+
+        let tag = tags.attach(tag.id, skull.id).await.unwrap();
+        assert_eq!(tag.skulls, vec![skull.id, other.id]);
+
+        let tag = tags.detach(tag.id, other.id).await.unwrap();
+        assert_eq!(tag.skulls, vec![skull.id]);
+
+        store.skulls().delete(skull.id).await.unwrap();
+        assert_eq!(tags.list().await.unwrap()[0].skulls, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn attach_err_not_found() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        tags.delete(tag.id).await.unwrap();
+
+        let err = tags.attach(tag.id, skull.id).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::NotFound(tag.id.into()).to_string());
+    }
+
+    #[tokio::test]
+    async fn attach_err_foreign_key() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+        store.skulls().delete(skull.id).await.unwrap();
+
+        let err = tags.attach(tag.id, skull.id).await.unwrap_err();
+        assert_eq!(err.to_string(), Error::ForeignKey.to_string());
+    }
+
+    #[tokio::test]
+    async fn detach_err_not_found() {
+        let (store, skull) = skulled_store().await;
+
+        let tags = store.tags();
+        let tag = tags.create("tag").await.unwrap();
+
+        let err = tags.detach(tag.id, skull.id).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::NotFound(skull.id.into()).to_string()
+        );
+    }
+}
@@ -36,6 +36,63 @@ pub struct Skull {
     pub icon: String,
     pub price: f32,
     pub limit: Option<f32>,
+    #[serde(default)]
+    pub period: Period,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Period {
+    Hours(u32),
+    Day,
+    Week,
+    Month,
+}
+
+impl Default for Period {
+    fn default() -> Self {
+        Self::Hours(18)
+    }
+}
+
+impl From<Period> for i64 {
+    fn from(value: Period) -> Self {
+        match value {
+            Period::Hours(hours) => Self::from(hours),
+            Period::Day => -1,
+            Period::Week => -2,
+            Period::Month => -3,
+        }
+    }
+}
+
+impl TryFrom<i64> for Period {
+    type Error = InvalidPeriod;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        match value {
+            -1 => Ok(Self::Day),
+            -2 => Ok(Self::Week),
+            -3 => Ok(Self::Month),
+            1.. => u32::try_from(value)
+                .map(Self::Hours)
+                .map_err(|_| InvalidPeriod(value)),
+            _ => Err(InvalidPeriod(value)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InvalidPeriod(i64);
+
+impl std::error::Error for InvalidPeriod {}
+
+impl std::fmt::Display for InvalidPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid period value: {}", self.0)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -45,6 +102,30 @@ pub struct Quick {
     pub amount: f32,
 }
 
+/// The skull and amount most likely to be registered at a given time
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Prediction {
+    pub skull: SkullId,
+    pub amount: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+pub struct TagId(Id);
+
+transparent::transparent!(readonly TagId, Id);
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Tag {
+    pub id: TagId,
+    pub name: String,
+    pub skulls: Vec<SkullId>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -61,6 +142,170 @@ pub struct Occurrence {
     pub skull: SkullId,
     pub amount: f32,
     pub millis: Millis,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Position of the last occurrence of a page, from which the next page continues
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct Cursor {
+    pub millis: Millis,
+    pub id: OccurrenceId,
+}
+
+impl Cursor {
+    /// The cursor continuing after `occurrences`, unless they fell short of `limit` and so were the last
+    #[must_use]
+    pub fn next(occurrences: &[Occurrence], limit: usize) -> Option<Self> {
+        occurrences
+            .last()
+            .filter(|_| occurrences.len() == limit)
+            .map(|last| Self {
+                millis: last.millis,
+                id: last.id,
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Page {
+    pub occurrences: Vec<Occurrence>,
+    pub next: Option<Cursor>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Aggregate {
+    pub skull: SkullId,
+    pub millis: Millis,
+    pub count: u32,
+    pub sum: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Spending {
+    pub skull: SkullId,
+    pub amount: f32,
+    pub cost: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+pub struct AuditId(Id);
+
+transparent::transparent!(readonly AuditId, Id);
+
+/// A recorded mutation, with `before` and `after` holding the JSON of the entity
+///
+/// Skulls also carry their `prices` history, so undoing and redoing can restore it
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct Audit {
+    pub id: AuditId,
+    pub actor: Option<String>,
+    pub millis: Millis,
+    pub entity: Entity,
+    pub mutation: Mutation,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(rename_all = "camelCase"))]
+pub enum Entity {
+    Skull,
+    Occurrence,
+    Tag,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(rename_all = "camelCase"))]
+pub enum Mutation {
+    Create,
+    Update,
+    Delete,
+}
+
+/// Preferences of a user, shared by every client
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    /// Offset from UTC, in minutes
+    pub timezone: i32,
+    /// Hour of the local day at which a new day starts
+    pub day_start: u32,
+    /// How many days are listed, counting the current one
+    pub window: u32,
+    /// How many amounts are suggested for quick registration
+    pub quick_count: u32,
+    /// Symbol shown alongside costs
+    pub currency: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            timezone: 0,
+            day_start: 0,
+            window: 2,
+            quick_count: 12,
+            currency: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Overrides the value held under the key of `setting`
+    pub fn apply(&mut self, setting: Setting) {
+        match setting {
+            Setting::Timezone(timezone) => self.timezone = timezone,
+            Setting::DayStart(day_start) => self.day_start = day_start,
+            Setting::Window(window) => self.window = window,
+            Setting::QuickCount(quick_count) => self.quick_count = quick_count,
+            Setting::Currency(currency) => self.currency = currency,
+        }
+    }
+}
+
+/// A single entry of the [`Settings`], stored as a key and a JSON value
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", tag = "key", content = "value")]
+pub enum Setting {
+    Timezone(i32),
+    DayStart(u32),
+    Window(u32),
+    QuickCount(u32),
+    Currency(Option<String>),
+}
+
+impl Setting {
+    #[must_use]
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Timezone(_) => "timezone",
+            Self::DayStart(_) => "dayStart",
+            Self::Window(_) => "window",
+            Self::QuickCount(_) => "quickCount",
+            Self::Currency(_) => "currency",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Bucket {
+    Hour,
+    Day,
+    Week,
+    Month,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -72,6 +317,127 @@ pub struct Millis(i64);
 
 transparent::transparent!(Millis, i64);
 
+#[cfg(feature = "chrono")]
+impl Period {
+    /// When the period containing `at` started, with calendar periods aligned to the timezone of `at`
+    #[must_use]
+    pub fn start<Tz: chrono::TimeZone>(self, at: &chrono::DateTime<Tz>) -> chrono::DateTime<Tz> {
+        use chrono::Datelike;
+
+        let date = at.date_naive();
+        let date = match self {
+            Self::Hours(hours) => {
+                // A period reaching past the representable range covers everything before `at`
+                return at
+                    .clone()
+                    .checked_sub_signed(chrono::Duration::hours(i64::from(hours)))
+                    .unwrap_or_else(|| {
+                        chrono::DateTime::<chrono::Utc>::MIN_UTC.with_timezone(&at.timezone())
+                    });
+            }
+            Self::Day => date,
+            Self::Week => {
+                date - chrono::Days::new(u64::from(date.weekday().num_days_from_monday()))
+            }
+            Self::Month => date
+                .with_day(1)
+                .expect("the first of the month is always valid"),
+        };
+
+        let midnight = date.and_time(chrono::NaiveTime::MIN);
+        let timezone = at.timezone();
+        timezone
+            .from_local_datetime(&midnight)
+            .earliest()
+            .unwrap_or_else(|| timezone.from_utc_datetime(&midnight))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Settings {
+    /// The timezone of the user, falling back to UTC if the offset is out of range
+    #[must_use]
+    pub fn offset(&self) -> chrono::FixedOffset {
+        use chrono::Offset;
+
+        chrono::FixedOffset::east_opt(self.timezone.saturating_mul(60))
+            .unwrap_or_else(|| chrono::Utc.fix())
+    }
+
+    /// The current time in the timezone of the user
+    #[must_use]
+    pub fn now(&self) -> chrono::DateTime<chrono::FixedOffset> {
+        chrono::Utc::now().with_timezone(&self.offset())
+    }
+
+    /// The day that `at` counts towards, which only rolls over at `day_start`
+    #[must_use]
+    pub fn day<Tz: chrono::TimeZone>(&self, at: &chrono::DateTime<Tz>) -> chrono::NaiveDate {
+        (at.with_timezone(&self.offset()) - self.rollover()).date_naive()
+    }
+
+    /// When the period containing `at` started, with calendar periods starting at `day_start` in
+    /// the timezone of the user
+    #[must_use]
+    pub fn start<Tz: chrono::TimeZone>(
+        &self,
+        period: Period,
+        at: &chrono::DateTime<Tz>,
+    ) -> chrono::DateTime<chrono::FixedOffset> {
+        let at = at.with_timezone(&self.offset());
+        match period {
+            Period::Hours(_) => period.start(&at),
+            Period::Day | Period::Week | Period::Month => {
+                period.start(&(at - self.rollover())) + self.rollover()
+            }
+        }
+    }
+
+    fn rollover(&self) -> chrono::Duration {
+        chrono::Duration::hours(i64::from(self.day_start))
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<DB: sqlx::Database> sqlx::Type<DB> for Period
+where
+    i64: sqlx::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <i64 as sqlx::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <i64 as sqlx::Type<DB>>::compatible(ty)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'q, DB: sqlx::Database> sqlx::Encode<'q, DB> for Period
+where
+    i64: sqlx::Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as sqlx::Database>::ArgumentBuffer<'q>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        <i64 as sqlx::Encode<'q, DB>>::encode(i64::from(*self), buf)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r, DB: sqlx::Database> sqlx::Decode<'r, DB> for Period
+where
+    i64: sqlx::Decode<'r, DB>,
+{
+    fn decode(
+        value: <DB as sqlx::Database>::ValueRef<'r>,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        let value = <i64 as sqlx::Decode<'r, DB>>::decode(value)?;
+        Self::try_from(value).map_err(Into::into)
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl<Tz> From<chrono::DateTime<Tz>> for Millis
 where
@@ -3,31 +3,85 @@
 pub enum Request {
     Skull(Skull),
     Occurrence(Occurrence),
+    Tag(Tag),
+    Audit(Audit),
+    Settings(Settings),
+    Undo,
+    Redo,
+    /// Runs every request in a single transaction, which is rolled back on the first error
+    Batch(Vec<Request>),
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Skull {
-    List,
+    List(skull::List),
+    Get(skull::Get),
     Create(skull::Create),
     Update(skull::Update),
     Delete(skull::Delete),
+    Archive(skull::Archive),
+    Unarchive(skull::Unarchive),
+    Merge(skull::Merge),
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Occurrence {
     List,
-    Quick,
+    Get(occurrence::Get),
+    Quick(occurrence::Quick),
+    Predict(occurrence::Predict),
     Search(occurrence::Search),
+    Aggregate(occurrence::Aggregate),
+    Spending(occurrence::Spending),
     Create(occurrence::Create),
     Update(occurrence::Update),
+    UpdateBatch(occurrence::UpdateBatch),
     Delete(occurrence::Delete),
+    DeleteBatch(occurrence::DeleteBatch),
+    DeleteSearch(occurrence::Search),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Tag {
+    List,
+    Create(tag::Create),
+    Update(tag::Update),
+    Delete(tag::Delete),
+    Attach(tag::Attach),
+    Detach(tag::Detach),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Audit {
+    List(audit::List),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Settings {
+    Get,
+    Set(settings::Set),
 }
 
 pub mod skull {
     use super::Setter;
-    use crate::SkullId;
+    use crate::{Period, SkullId};
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct List {
+        /// Archived skulls are listed too, unless this is `false`
+        pub include_archived: Option<bool>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Get {
+        pub id: SkullId,
+    }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
     pub struct Create {
@@ -36,6 +90,8 @@ pub mod skull {
         pub icon: String,
         pub price: f32,
         pub limit: Option<f32>,
+        #[serde(default)]
+        pub period: Period,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -46,29 +102,235 @@ pub mod skull {
         pub icon: Option<Setter<String>>,
         pub price: Option<Setter<f32>>,
         pub limit: Option<Setter<Option<f32>>>,
+        pub period: Option<Setter<Period>>,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
     pub struct Delete {
         pub id: SkullId,
     }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Archive {
+        pub id: SkullId,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Unarchive {
+        pub id: SkullId,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Merge {
+        pub from: SkullId,
+        pub into: SkullId,
+    }
+
+    #[cfg(feature = "query")]
+    pub mod query {
+        use super::List;
+        use crate::request::occurrence::query::{Error, bool_from_query, bool_to_query};
+
+        impl List {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                self.include_archived
+                    .map(|include_archived| bool_to_query("includeArchived", include_archived))
+                    .unwrap_or_default()
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut list = Self {
+                    include_archived: None,
+                };
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    if key == "includeArchived" {
+                        list.include_archived = Some(bool_from_query("includeArchived", value)?);
+                    }
+                }
+                Ok(list)
+            }
+        }
+    }
+}
+
+pub mod tag {
+    use super::Setter;
+    use crate::{SkullId, TagId};
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Create {
+        pub name: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Update {
+        pub id: TagId,
+        pub name: Option<Setter<String>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Delete {
+        pub id: TagId,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Attach {
+        pub id: TagId,
+        pub skull: SkullId,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Detach {
+        pub id: TagId,
+        pub skull: SkullId,
+    }
+}
+
+pub mod settings {
+    use super::Setter;
+    use crate::Setting;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Set {
+        pub timezone: Option<Setter<i32>>,
+        pub day_start: Option<Setter<u32>>,
+        pub window: Option<Setter<u32>>,
+        pub quick_count: Option<Setter<u32>>,
+        pub currency: Option<Setter<Option<String>>>,
+    }
+
+    impl Set {
+        /// The settings to change, each to be stored under its own key
+        #[must_use]
+        pub fn into_settings(self) -> Vec<Setting> {
+            [
+                self.timezone.map(|s| Setting::Timezone(s.set())),
+                self.day_start.map(|s| Setting::DayStart(s.set())),
+                self.window.map(|s| Setting::Window(s.set())),
+                self.quick_count.map(|s| Setting::QuickCount(s.set())),
+                self.currency.map(|s| Setting::Currency(s.set())),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        }
+    }
+}
+
+pub mod audit {
+    use crate::Millis;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct List {
+        pub since: Option<Millis>,
+        pub limit: Option<usize>,
+    }
+
+    #[cfg(feature = "query")]
+    pub mod query {
+        use super::List;
+        use crate::request::occurrence::query::{
+            Error, limit_from_query, limit_to_query, millis_from_query, millis_to_query,
+        };
+
+        impl List {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                [
+                    self.since.map(|since| millis_to_query("since", since)),
+                    self.limit.map(limit_to_query),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&")
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut list = Self {
+                    since: None,
+                    limit: None,
+                };
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    match key {
+                        "since" => list.since = Some(millis_from_query("since", value)?),
+                        "limit" => list.limit = Some(limit_from_query(value)?),
+                        _ => {}
+                    }
+                }
+                Ok(list)
+            }
+        }
+    }
 }
 
 pub mod occurrence {
     use super::Setter;
-    use crate::{Millis, OccurrenceId, SkullId};
+    use crate::{Bucket, Cursor, Millis, OccurrenceId, SkullId};
+
+    /// How long, in millis, the key of a [`Create`] is remembered after its first use
+    pub const KEY_RETENTION: i64 = 30 * 24 * 60 * 60 * 1000;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Get {
+        pub id: OccurrenceId,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Quick {
+        /// Time it takes for an occurrence to weigh half as much, in milliseconds
+        pub half_life: Option<u64>,
+        /// Defaults to the quick count of the settings
+        pub limit: Option<usize>,
+        /// Favors the amounts usually registered around this hour of the day, in the timezone of
+        /// the settings
+        pub hour: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Predict {
+        /// Matched against the hour and weekday of the occurrences, in the timezone of the
+        /// settings
+        pub millis: Millis,
+    }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
     pub struct Search {
         pub skulls: Option<std::collections::HashSet<SkullId>>,
+        /// Skulls carrying any of these tags are searched alongside `skulls`
+        pub tags: Option<std::collections::HashSet<String>>,
         pub start: Option<Millis>,
         pub end: Option<Millis>,
+        /// Pages the results, which are then returned as a [`Page`](crate::Page)
         pub limit: Option<usize>,
+        /// Continues after the `next` cursor of a previous page
+        pub cursor: Option<Cursor>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Aggregate {
+        pub skulls: Option<std::collections::HashSet<SkullId>>,
+        pub start: Option<Millis>,
+        pub end: Option<Millis>,
+        pub bucket: Bucket,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Spending {
+        pub skulls: Option<std::collections::HashSet<SkullId>>,
+        pub start: Option<Millis>,
+        pub end: Option<Millis>,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
     pub struct Create {
         pub items: Vec<Item>,
+        /// Replaying a key used within [`KEY_RETENTION`] creates nothing, as long as the items match
+        #[serde(default)]
+        pub key: Option<String>,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -76,6 +338,8 @@ pub mod occurrence {
         pub skull: SkullId,
         pub amount: f32,
         pub millis: Millis,
+        #[serde(default)]
+        pub note: Option<String>,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -84,6 +348,12 @@ pub mod occurrence {
         pub skull: Option<Setter<SkullId>>,
         pub amount: Option<Setter<f32>>,
         pub millis: Option<Setter<Millis>>,
+        pub note: Option<Setter<Option<String>>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct UpdateBatch {
+        pub items: Vec<Update>,
     }
 
     #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -91,29 +361,92 @@ pub mod occurrence {
         pub id: OccurrenceId,
     }
 
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct DeleteBatch {
+        pub ids: Vec<OccurrenceId>,
+    }
+
     #[cfg(feature = "query")]
     pub mod query {
-        use super::{Millis, Search, SkullId};
+        use super::{Aggregate, Bucket, Cursor, Millis, Predict, Quick, Search, SkullId, Spending};
 
-        impl Search {
+        impl Quick {
             #[must_use]
             pub fn to_query(&self) -> String {
                 [
-                    self.skulls.as_ref().map(|skulls| {
-                        let skulls = skulls
-                            .iter()
-                            .map(|id| String::from(itoa::Buffer::new().format(i64::from(*id))))
-                            .collect::<Vec<_>>()
-                            .join(",");
-                        format!("skulls={skulls}")
-                    }),
-                    self.start.map(|start| {
-                        format!("start={}", itoa::Buffer::new().format(i64::from(start)))
+                    self.half_life.map(|half_life| {
+                        format!("halfLife={}", itoa::Buffer::new().format(half_life))
                     }),
-                    self.end
-                        .map(|end| format!("end={}", itoa::Buffer::new().format(i64::from(end)))),
-                    self.limit
-                        .map(|limit| format!("limit={}", itoa::Buffer::new().format(limit))),
+                    self.limit.map(limit_to_query),
+                    self.hour
+                        .map(|hour| format!("hour={}", itoa::Buffer::new().format(hour))),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&")
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut quick = Self {
+                    half_life: None,
+                    limit: None,
+                    hour: None,
+                };
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    match key {
+                        "halfLife" => {
+                            quick.half_life = Some(value.parse().map_err(|error| Error {
+                                field: "halfLife",
+                                error: Cause::Parse(error),
+                            })?);
+                        }
+                        "limit" => quick.limit = Some(limit_from_query(value)?),
+                        "hour" => {
+                            quick.hour = Some(value.parse().map_err(|error| Error {
+                                field: "hour",
+                                error: Cause::Parse(error),
+                            })?);
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(quick)
+            }
+        }
+
+        impl Predict {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                millis_to_query("millis", self.millis)
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut millis = None;
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    if key == "millis" {
+                        millis = Some(millis_from_query("millis", value)?);
+                    }
+                }
+                Ok(Self {
+                    millis: millis.ok_or(Error {
+                        field: "millis",
+                        error: Cause::Missing,
+                    })?,
+                })
+            }
+        }
+
+        impl Search {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                [
+                    skulls_to_query(self.skulls.as_ref()),
+                    tags_to_query(self.tags.as_ref()),
+                    self.start.map(|start| millis_to_query("start", start)),
+                    self.end.map(|end| millis_to_query("end", end)),
+                    self.limit.map(limit_to_query),
+                    self.cursor.map(cursor_to_query),
                 ]
                 .into_iter()
                 .flatten()
@@ -124,48 +457,20 @@ pub mod occurrence {
             pub fn from_query(query: &str) -> Result<Self, Error> {
                 let mut search = Self {
                     skulls: None,
+                    tags: None,
                     start: None,
                     end: None,
                     limit: None,
+                    cursor: None,
                 };
                 for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
                     match key {
-                        "skulls" => {
-                            search.skulls = Some(
-                                value
-                                    .split(',')
-                                    .map(|id| id.parse::<i64>().map(SkullId))
-                                    .collect::<Result<_, _>>()
-                                    .map_err(|error| Error {
-                                        field: "skulls",
-                                        error,
-                                    })?,
-                            );
-                        }
-                        "start" => {
-                            search.start =
-                                Some(value.parse::<i64>().map(Millis::from).map_err(|error| {
-                                    Error {
-                                        field: "start",
-                                        error,
-                                    }
-                                })?);
-                        }
-                        "end" => {
-                            search.end =
-                                Some(value.parse::<i64>().map(Millis::from).map_err(|error| {
-                                    Error {
-                                        field: "end",
-                                        error,
-                                    }
-                                })?);
-                        }
-                        "limit" => {
-                            search.limit = Some(value.parse::<usize>().map_err(|error| Error {
-                                field: "limit",
-                                error,
-                            })?);
-                        }
+                        "skulls" => search.skulls = Some(skulls_from_query(value)?),
+                        "tags" => search.tags = Some(tags_from_query(value)?),
+                        "start" => search.start = Some(millis_from_query("start", value)?),
+                        "end" => search.end = Some(millis_from_query("end", value)?),
+                        "limit" => search.limit = Some(limit_from_query(value)?),
+                        "cursor" => search.cursor = Some(cursor_from_query(value)?),
                         _ => {}
                     }
                 }
@@ -173,17 +478,274 @@ pub mod occurrence {
             }
         }
 
+        impl Aggregate {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                [
+                    skulls_to_query(self.skulls.as_ref()),
+                    self.start.map(|start| millis_to_query("start", start)),
+                    self.end.map(|end| millis_to_query("end", end)),
+                    Some(format!("bucket={}", bucket_to_query(self.bucket))),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&")
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut skulls = None;
+                let mut start = None;
+                let mut end = None;
+                let mut bucket = None;
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    match key {
+                        "skulls" => skulls = Some(skulls_from_query(value)?),
+                        "start" => start = Some(millis_from_query("start", value)?),
+                        "end" => end = Some(millis_from_query("end", value)?),
+                        "bucket" => bucket = Some(bucket_from_query(value)?),
+                        _ => {}
+                    }
+                }
+                Ok(Self {
+                    skulls,
+                    start,
+                    end,
+                    bucket: bucket.ok_or(Error {
+                        field: "bucket",
+                        error: Cause::Missing,
+                    })?,
+                })
+            }
+        }
+
+        impl Spending {
+            #[must_use]
+            pub fn to_query(&self) -> String {
+                [
+                    skulls_to_query(self.skulls.as_ref()),
+                    self.start.map(|start| millis_to_query("start", start)),
+                    self.end.map(|end| millis_to_query("end", end)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("&")
+            }
+
+            pub fn from_query(query: &str) -> Result<Self, Error> {
+                let mut spending = Self {
+                    skulls: None,
+                    start: None,
+                    end: None,
+                };
+                for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+                    match key {
+                        "skulls" => spending.skulls = Some(skulls_from_query(value)?),
+                        "start" => spending.start = Some(millis_from_query("start", value)?),
+                        "end" => spending.end = Some(millis_from_query("end", value)?),
+                        _ => {}
+                    }
+                }
+                Ok(spending)
+            }
+        }
+
+        fn skulls_to_query(skulls: Option<&std::collections::HashSet<SkullId>>) -> Option<String> {
+            skulls.map(|skulls| {
+                let skulls = skulls
+                    .iter()
+                    .map(|id| String::from(itoa::Buffer::new().format(i64::from(*id))))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("skulls={skulls}")
+            })
+        }
+
+        fn skulls_from_query(value: &str) -> Result<std::collections::HashSet<SkullId>, Error> {
+            value
+                .split(',')
+                .map(|id| id.parse::<i64>().map(SkullId))
+                .collect::<Result<_, _>>()
+                .map_err(|error| Error {
+                    field: "skulls",
+                    error: Cause::Parse(error),
+                })
+        }
+
+        fn tags_to_query(tags: Option<&std::collections::HashSet<String>>) -> Option<String> {
+            tags.map(|tags| {
+                let tags = tags
+                    .iter()
+                    .map(|tag| percent_encode(tag))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("tags={tags}")
+            })
+        }
+
+        fn tags_from_query(value: &str) -> Result<std::collections::HashSet<String>, Error> {
+            value
+                .split(',')
+                .map(|tag| {
+                    percent_decode(tag)
+                        .filter(|tag| !tag.is_empty())
+                        .ok_or(Error {
+                            field: "tags",
+                            error: Cause::Invalid,
+                        })
+                })
+                .collect()
+        }
+
+        /// Encodes everything but the unreserved characters, as names may hold any alphanumeric
+        fn percent_encode(value: &str) -> String {
+            use std::fmt::Write;
+
+            value.bytes().fold(String::new(), |mut encoded, byte| {
+                if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                    encoded.push(char::from(byte));
+                } else {
+                    let _ = write!(encoded, "%{byte:02X}");
+                }
+                encoded
+            })
+        }
+
+        fn percent_decode(value: &str) -> Option<String> {
+            let mut bytes = Vec::with_capacity(value.len());
+            let mut iter = value.bytes();
+            while let Some(byte) = iter.next() {
+                if byte == b'%' {
+                    let high = char::from(iter.next()?).to_digit(16)?;
+                    let low = char::from(iter.next()?).to_digit(16)?;
+                    bytes.push(u8::try_from((high << 4) | low).ok()?);
+                } else {
+                    bytes.push(byte);
+                }
+            }
+            String::from_utf8(bytes).ok()
+        }
+
+        pub(in crate::request) fn millis_to_query(field: &'static str, millis: Millis) -> String {
+            format!("{field}={}", itoa::Buffer::new().format(i64::from(millis)))
+        }
+
+        pub(in crate::request) fn millis_from_query(
+            field: &'static str,
+            value: &str,
+        ) -> Result<Millis, Error> {
+            value
+                .parse::<i64>()
+                .map(Millis::from)
+                .map_err(|error| Error {
+                    field,
+                    error: Cause::Parse(error),
+                })
+        }
+
+        pub(in crate::request) fn bool_to_query(field: &'static str, value: bool) -> String {
+            format!("{field}={value}")
+        }
+
+        pub(in crate::request) fn bool_from_query(
+            field: &'static str,
+            value: &str,
+        ) -> Result<bool, Error> {
+            match value {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(Error {
+                    field,
+                    error: Cause::Invalid,
+                }),
+            }
+        }
+
+        pub(in crate::request) fn limit_to_query(limit: usize) -> String {
+            format!("limit={}", itoa::Buffer::new().format(limit))
+        }
+
+        pub(in crate::request) fn limit_from_query(value: &str) -> Result<usize, Error> {
+            value.parse::<usize>().map_err(|error| Error {
+                field: "limit",
+                error: Cause::Parse(error),
+            })
+        }
+
+        fn cursor_to_query(cursor: Cursor) -> String {
+            format!(
+                "cursor={},{}",
+                itoa::Buffer::new().format(i64::from(cursor.millis)),
+                itoa::Buffer::new().format(i64::from(cursor.id)),
+            )
+        }
+
+        fn cursor_from_query(value: &str) -> Result<Cursor, Error> {
+            let (millis, id) = value.split_once(',').ok_or(Error {
+                field: "cursor",
+                error: Cause::Invalid,
+            })?;
+
+            let parse = |value: &str| {
+                value.parse::<i64>().map_err(|error| Error {
+                    field: "cursor",
+                    error: Cause::Parse(error),
+                })
+            };
+
+            Ok(Cursor {
+                millis: Millis::from(parse(millis)?),
+                id: crate::OccurrenceId(parse(id)?),
+            })
+        }
+
+        fn bucket_to_query(bucket: Bucket) -> &'static str {
+            match bucket {
+                Bucket::Hour => "hour",
+                Bucket::Day => "day",
+                Bucket::Week => "week",
+                Bucket::Month => "month",
+            }
+        }
+
+        fn bucket_from_query(value: &str) -> Result<Bucket, Error> {
+            match value {
+                "hour" => Ok(Bucket::Hour),
+                "day" => Ok(Bucket::Day),
+                "week" => Ok(Bucket::Week),
+                "month" => Ok(Bucket::Month),
+                _ => Err(Error {
+                    field: "bucket",
+                    error: Cause::Invalid,
+                }),
+            }
+        }
+
         #[derive(Debug, Clone, Eq, PartialEq)]
         pub struct Error {
             field: &'static str,
-            error: std::num::ParseIntError,
+            error: Cause,
+        }
+
+        #[derive(Debug, Clone, Eq, PartialEq)]
+        enum Cause {
+            Parse(std::num::ParseIntError),
+            Invalid,
+            Missing,
         }
 
         impl std::error::Error for Error {}
 
         impl std::fmt::Display for Error {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                write!(f, "Failed to parse field `{}`: {}", self.field, self.error)
+                match &self.error {
+                    Cause::Parse(error) => {
+                        write!(f, "Failed to parse field `{}`: {error}", self.field)
+                    }
+                    Cause::Invalid => write!(f, "Invalid value for field `{}`", self.field),
+                    Cause::Missing => write!(f, "Missing field `{}`", self.field),
+                }
             }
         }
     }
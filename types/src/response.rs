@@ -1,4 +1,6 @@
-use crate::{Error, Occurrence, Quick, Skull};
+use crate::{
+    Aggregate, Audit, Error, Occurrence, Page, Prediction, Quick, Settings, Skull, Spending, Tag,
+};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,9 +14,19 @@ pub enum Response {
 #[serde(rename_all = "camelCase")]
 pub enum Payload {
     Change(Change),
+    Skull(Skull),
     Skulls(Vec<Skull>),
     Quicks(Vec<Quick>),
+    Prediction(Option<Prediction>),
+    Occurrence(Occurrence),
     Occurrences(Vec<Occurrence>),
+    Page(Page),
+    Aggregates(Vec<Aggregate>),
+    Spending(Vec<Spending>),
+    Tags(Vec<Tag>),
+    Audits(Vec<Audit>),
+    Settings(Settings),
+    Batch(Vec<Response>),
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
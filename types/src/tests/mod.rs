@@ -1,4 +1,4 @@
-use crate::{Millis, Occurrence, OccurrenceId, Quick, Skull, SkullId};
+use crate::{Cursor, Millis, Occurrence, OccurrenceId, Period, Quick, Skull, SkullId};
 
 mod error;
 mod request;
@@ -46,6 +46,8 @@ fn skull_none() {
         icon: String::from("icon"),
         price: 1.0,
         limit: None,
+        period: Period::default(),
+        archived: false,
     };
     let json = json(
         &t,
@@ -67,6 +69,8 @@ fn skull_limit() {
         icon: String::from("icon"),
         price: 1.0,
         limit: Some(1.0),
+        period: Period::default(),
+        archived: false,
     };
     let json = json(
         &t,
@@ -79,6 +83,166 @@ fn skull_limit() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn skull_period() {
+    let t = Skull {
+        id: SkullId(27),
+        name: String::from("name"),
+        color: 1,
+        icon: String::from("icon"),
+        price: 1.0,
+        limit: Some(1.0),
+        period: Period::Week,
+        archived: false,
+    };
+    let json = json(
+        &t,
+        r#"{"id":27,"name":"name","color":1,"icon":"icon","price":1,"limit":1,"period":"week"}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn skull_archived() {
+    let t = Skull {
+        id: SkullId(27),
+        name: String::from("name"),
+        color: 1,
+        icon: String::from("icon"),
+        price: 1.0,
+        limit: None,
+        period: Period::default(),
+        archived: true,
+    };
+    let json = json(
+        &t,
+        r#"{"id":27,"name":"name","color":1,"icon":"icon","price":1,"archived":true}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn period() {
+    for (t, expected) in [
+        (Period::Hours(18), r#"{"hours":18}"#),
+        (Period::Day, r#""day""#),
+        (Period::Week, r#""week""#),
+        (Period::Month, r#""month""#),
+    ] {
+        let json = json(&t, expected).unwrap();
+        let rmp = rmp(&t).unwrap();
+
+        assert_eq!(t, json);
+        assert_eq!(t, rmp);
+        assert_eq!(Period::try_from(i64::from(t)), Ok(t));
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn period_start() {
+    let at = chrono::DateTime::parse_from_rfc3339("2026-10-17T09:35:12Z")
+        .unwrap()
+        .to_utc();
+
+    for (period, expected) in [
+        (Period::Hours(18), "2026-10-16T15:35:12Z"),
+        (Period::Day, "2026-10-17T00:00:00Z"),
+        (Period::Week, "2026-10-12T00:00:00Z"),
+        (Period::Month, "2026-10-01T00:00:00Z"),
+    ] {
+        assert_eq!(
+            period.start(&at),
+            chrono::DateTime::parse_from_rfc3339(expected)
+                .unwrap()
+                .to_utc()
+        );
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn period_start_overflow() {
+    let at = chrono::DateTime::parse_from_rfc3339("2026-10-17T09:35:12Z")
+        .unwrap()
+        .to_utc();
+
+    assert_eq!(
+        Period::Hours(u32::MAX).start(&at),
+        chrono::DateTime::<chrono::Utc>::MIN_UTC
+    );
+}
+
+#[test]
+fn setting() {
+    for (t, expected) in [
+        (
+            crate::Setting::Timezone(-180),
+            r#"{"key":"timezone","value":-180}"#,
+        ),
+        (
+            crate::Setting::DayStart(5),
+            r#"{"key":"dayStart","value":5}"#,
+        ),
+        (crate::Setting::Window(3), r#"{"key":"window","value":3}"#),
+        (
+            crate::Setting::QuickCount(6),
+            r#"{"key":"quickCount","value":6}"#,
+        ),
+        (
+            crate::Setting::Currency(None),
+            r#"{"key":"currency","value":null}"#,
+        ),
+    ] {
+        let json = json(&t, expected).unwrap();
+        let rmp = rmp(&t).unwrap();
+
+        assert_eq!(t, json);
+        assert_eq!(t, rmp);
+        assert!(expected.contains(t.key()));
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn settings_start() {
+    let settings = crate::Settings {
+        timezone: 60,
+        day_start: 5,
+        ..crate::Settings::default()
+    };
+
+    // Still the 17th locally, but before the day started
+    let at = chrono::DateTime::parse_from_rfc3339("2026-10-17T03:35:12Z")
+        .unwrap()
+        .to_utc();
+
+    assert_eq!(
+        settings.day(&at),
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+    );
+
+    for (period, expected) in [
+        (Period::Hours(18), "2026-10-16T09:35:12Z"),
+        (Period::Day, "2026-10-16T04:00:00Z"),
+        (Period::Week, "2026-10-12T04:00:00Z"),
+        (Period::Month, "2026-10-01T04:00:00Z"),
+    ] {
+        assert_eq!(
+            settings.start(period, &at),
+            chrono::DateTime::parse_from_rfc3339(expected).unwrap()
+        );
+    }
+}
+
 #[test]
 fn quick() {
     let t = Quick {
@@ -109,6 +273,7 @@ fn occurrence() {
         skull: SkullId(72),
         amount: 2.7,
         millis: Millis(-27),
+        note: None,
     };
     let json = json(&t, r#"{"id":27,"skull":72,"amount":2.7,"millis":-27}"#).unwrap();
     let rmp = rmp(&t).unwrap();
@@ -117,6 +282,47 @@ fn occurrence() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn occurrence_note() {
+    let t = Occurrence {
+        id: OccurrenceId(27),
+        skull: SkullId(72),
+        amount: 2.7,
+        millis: Millis(-27),
+        note: Some(String::from("note")),
+    };
+    let json = json(
+        &t,
+        r#"{"id":27,"skull":72,"amount":2.7,"millis":-27,"note":"note"}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn cursor_next() {
+    let occurrences = [27, 72].map(|id| Occurrence {
+        id: OccurrenceId(id),
+        skull: SkullId(1),
+        amount: 1.0,
+        millis: Millis(id),
+        note: None,
+    });
+
+    assert_eq!(
+        Cursor::next(&occurrences, 2),
+        Some(Cursor {
+            millis: Millis(72),
+            id: OccurrenceId(72),
+        })
+    );
+    assert_eq!(Cursor::next(&occurrences, 3), None);
+    assert_eq!(Cursor::next(&[], 0), None);
+}
+
 #[test]
 fn millis() {
     let t = Millis(1);
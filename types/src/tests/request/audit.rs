@@ -0,0 +1,31 @@
+use crate::{
+    Millis, Request,
+    request::{Audit, audit::List},
+    tests::{json, rmp},
+};
+
+#[test]
+fn list_none() {
+    let t = Request::Audit(Audit::List(List {
+        since: None,
+        limit: None,
+    }));
+    let json = json(&t, r#"{"audit":{"list":{}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn list() {
+    let t = Request::Audit(Audit::List(List {
+        since: Some(Millis(27)),
+        limit: Some(72),
+    }));
+    let json = json(&t, r#"{"audit":{"list":{"since":27,"limit":72}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
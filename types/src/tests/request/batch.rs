@@ -0,0 +1,45 @@
+use crate::{
+    Millis, Request, SkullId,
+    request::{
+        Occurrence, Skull,
+        occurrence::{Create, Item},
+        skull::Delete,
+    },
+    tests::{json, rmp},
+};
+
+#[test]
+fn batch() {
+    let t = Request::Batch(vec![
+        Request::Occurrence(Occurrence::Create(Create {
+            items: vec![Item {
+                skull: SkullId(27),
+                amount: 1.0,
+                millis: Millis(72),
+                note: None,
+            }],
+            key: None,
+        })),
+        Request::Skull(Skull::Delete(Delete { id: SkullId(72) })),
+        Request::Undo,
+    ]);
+    let json = json(
+        &t,
+        r#"{"batch":[{"occurrence":{"create":{"items":[{"skull":27,"amount":1,"millis":72,"note":null}]}}},{"skull":{"delete":{"id":72}}},"undo"]}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn batch_empty() {
+    let t = Request::Batch(Vec::new());
+    let json = json(&t, r#"{"batch":[]}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -0,0 +1,24 @@
+use crate::{
+    Request,
+    tests::{json, rmp},
+};
+
+#[test]
+fn undo() {
+    let t = Request::Undo;
+    let json = json(&t, r#""undo""#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn redo() {
+    let t = Request::Redo;
+    let json = json(&t, r#""redo""#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -1,2 +1,7 @@
+mod audit;
+mod batch;
+mod journal;
 mod occurrence;
+mod settings;
 mod skull;
+mod tag;
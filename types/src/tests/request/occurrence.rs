@@ -1,8 +1,11 @@
 use crate::{
-    Millis, OccurrenceId, Request, Setter, SkullId,
+    Bucket, Cursor, Millis, OccurrenceId, Request, Setter, SkullId,
     request::{
         Occurrence,
-        occurrence::{Create, Delete, Item, Search, Update},
+        occurrence::{
+            Aggregate, Create, Delete, DeleteBatch, Get, Item, Predict, Quick, Search, Spending,
+            Update, UpdateBatch,
+        },
     },
     tests::{json, rmp},
 };
@@ -18,9 +21,53 @@ fn list() {
 }
 
 #[test]
-fn quick() {
-    let t = Request::Occurrence(Occurrence::Quick);
-    let json = json(&t, r#"{"occurrence":"quick"}"#).unwrap();
+fn get() {
+    let t = Request::Occurrence(Occurrence::Get(Get {
+        id: OccurrenceId(27),
+    }));
+    let json = json(&t, r#"{"occurrence":{"get":{"id":27}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn quick_none() {
+    let t = Request::Occurrence(Occurrence::Quick(Quick {
+        half_life: None,
+        limit: None,
+        hour: None,
+    }));
+    let json = json(&t, r#"{"occurrence":{"quick":{}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn quick_some() {
+    let t = Request::Occurrence(Occurrence::Quick(Quick {
+        half_life: Some(3_600_000),
+        limit: Some(4),
+        hour: Some(8),
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"quick":{"halfLife":3600000,"limit":4,"hour":8}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn predict() {
+    let t = Request::Occurrence(Occurrence::Predict(Predict { millis: Millis(2) }));
+    let json = json(&t, r#"{"occurrence":{"predict":{"millis":2}}}"#).unwrap();
     let rmp = rmp(&t).unwrap();
 
     assert_eq!(t, json);
@@ -31,9 +78,11 @@ fn quick() {
 fn search_none() {
     let t = Request::Occurrence(Occurrence::Search(Search {
         skulls: None,
+        tags: None,
         start: None,
         end: None,
         limit: None,
+        cursor: None,
     }));
     let json = json(&t, r#"{"occurrence":{"search":{}}}"#).unwrap();
     let rmp = rmp(&t).unwrap();
@@ -42,9 +91,118 @@ fn search_none() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn search_tags() {
+    let t = Request::Occurrence(Occurrence::Search(Search {
+        skulls: None,
+        tags: Some(std::collections::HashSet::from([String::from("tag")])),
+        start: None,
+        end: None,
+        limit: None,
+        cursor: None,
+    }));
+    let json = json(&t, r#"{"occurrence":{"search":{"tags":["tag"]}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[cfg(feature = "query")]
+#[test]
+fn search_tags_query() {
+    let t = Search {
+        skulls: None,
+        tags: Some(std::collections::HashSet::from([String::from("café")])),
+        start: None,
+        end: None,
+        limit: None,
+        cursor: None,
+    };
+    let query = t.to_query();
+
+    assert_eq!(query, "tags=caf%C3%A9");
+    assert_eq!(Search::from_query(&query), Ok(t));
+}
+
+#[test]
+fn search_cursor() {
+    let t = Request::Occurrence(Occurrence::Search(Search {
+        skulls: None,
+        tags: None,
+        start: None,
+        end: None,
+        limit: Some(2),
+        cursor: Some(Cursor {
+            millis: Millis(27),
+            id: OccurrenceId(72),
+        }),
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"search":{"limit":2,"cursor":{"millis":27,"id":72}}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn aggregate_none() {
+    let t = Request::Occurrence(Occurrence::Aggregate(Aggregate {
+        skulls: None,
+        start: None,
+        end: None,
+        bucket: Bucket::Day,
+    }));
+    let json = json(&t, r#"{"occurrence":{"aggregate":{"bucket":"day"}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn aggregate() {
+    let t = Request::Occurrence(Occurrence::Aggregate(Aggregate {
+        skulls: Some(std::collections::HashSet::from([SkullId(27)])),
+        start: Some(Millis(1)),
+        end: Some(Millis(2)),
+        bucket: Bucket::Month,
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"aggregate":{"skulls":[27],"start":1,"end":2,"bucket":"month"}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn spending() {
+    let t = Request::Occurrence(Occurrence::Spending(Spending {
+        skulls: None,
+        start: Some(Millis(1)),
+        end: None,
+    }));
+    let json = json(&t, r#"{"occurrence":{"spending":{"start":1}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn create_empty() {
-    let t = Request::Occurrence(Occurrence::Create(Create { items: Vec::new() }));
+    let t = Request::Occurrence(Occurrence::Create(Create {
+        items: Vec::new(),
+        key: None,
+    }));
     let json = json(&t, r#"{"occurrence":{"create":{"items":[]}}}"#).unwrap();
     let rmp = rmp(&t).unwrap();
 
@@ -59,7 +217,9 @@ fn create() {
             skull: SkullId(27),
             amount: 1.0,
             millis: Millis(72),
+            note: None,
         }],
+        key: None,
     }));
     let json = json(
         &t,
@@ -72,6 +232,50 @@ fn create() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn create_note() {
+    let t = Request::Occurrence(Occurrence::Create(Create {
+        items: vec![Item {
+            skull: SkullId(27),
+            amount: 1.0,
+            millis: Millis(72),
+            note: Some(String::from("note")),
+        }],
+        key: None,
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"create":{"items":[{"skull":27,"amount":1,"millis":72,"note":"note"}]}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn create_key() {
+    let t = Request::Occurrence(Occurrence::Create(Create {
+        items: vec![Item {
+            skull: SkullId(27),
+            amount: 1.0,
+            millis: Millis(72),
+            note: None,
+        }],
+        key: Some(String::from("key")),
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"create":{"items":[{"skull":27,"amount":1,"millis":72}],"key":"key"}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn update_none() {
     let t = Request::Occurrence(Occurrence::Update(Update {
@@ -79,6 +283,7 @@ fn update_none() {
         skull: None,
         amount: None,
         millis: None,
+        note: None,
     }));
     let json = json(&t, r#"{"occurrence":{"update":{"id":27}}}"#).unwrap();
     let rmp = rmp(&t).unwrap();
@@ -94,6 +299,7 @@ fn update_some() {
         skull: Some(Setter { set: SkullId(72) }),
         amount: Some(Setter { set: 1.0 }),
         millis: Some(Setter { set: Millis(-27) }),
+        note: None,
     }));
     let json = json(
         &t,
@@ -106,6 +312,75 @@ fn update_some() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn update_set_note() {
+    let t = Request::Occurrence(Occurrence::Update(Update {
+        id: OccurrenceId(27),
+        skull: None,
+        amount: None,
+        millis: None,
+        note: Some(Setter {
+            set: Some(String::from("note")),
+        }),
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"update":{"id":27,"note":{"set":"note"}}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn update_clear_note() {
+    let t = Request::Occurrence(Occurrence::Update(Update {
+        id: OccurrenceId(27),
+        skull: None,
+        amount: None,
+        millis: None,
+        note: Some(Setter { set: None }),
+    }));
+    let json = json(&t, r#"{"occurrence":{"update":{"id":27,"note":{}}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn update_batch() {
+    let t = Request::Occurrence(Occurrence::UpdateBatch(UpdateBatch {
+        items: vec![
+            Update {
+                id: OccurrenceId(27),
+                skull: None,
+                amount: Some(Setter { set: 1.0 }),
+                millis: None,
+                note: None,
+            },
+            Update {
+                id: OccurrenceId(72),
+                skull: Some(Setter { set: SkullId(27) }),
+                amount: None,
+                millis: None,
+                note: None,
+            },
+        ],
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"updateBatch":{"items":[{"id":27,"amount":{"set":1}},{"id":72,"skull":{"set":27}}]}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn delete() {
     let t = Request::Occurrence(Occurrence::Delete(Delete {
@@ -117,3 +392,36 @@ fn delete() {
     assert_eq!(t, json);
     assert_eq!(t, rmp);
 }
+
+#[test]
+fn delete_batch() {
+    let t = Request::Occurrence(Occurrence::DeleteBatch(DeleteBatch {
+        ids: vec![OccurrenceId(27), OccurrenceId(72)],
+    }));
+    let json = json(&t, r#"{"occurrence":{"deleteBatch":{"ids":[27,72]}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn delete_search() {
+    let t = Request::Occurrence(Occurrence::DeleteSearch(Search {
+        skulls: Some(std::collections::HashSet::from([SkullId(27)])),
+        tags: None,
+        start: Some(Millis(72)),
+        end: None,
+        limit: None,
+        cursor: None,
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"deleteSearch":{"skulls":[27],"start":72}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -0,0 +1,71 @@
+use crate::{
+    Request, Setter, Setting,
+    request::{Settings, settings::Set},
+    tests::{json, rmp},
+};
+
+#[test]
+fn get() {
+    let t = Request::Settings(Settings::Get);
+    let json = json(&t, r#"{"settings":"get"}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn set_none() {
+    let t = Request::Settings(Settings::Set(Set {
+        timezone: None,
+        day_start: None,
+        window: None,
+        quick_count: None,
+        currency: None,
+    }));
+    let json = json(&t, r#"{"settings":{"set":{}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn set_settings() {
+    let set = Set {
+        timezone: Some(Setter { set: -180 }),
+        day_start: None,
+        window: None,
+        quick_count: Some(Setter { set: 6 }),
+        currency: Some(Setter { set: None }),
+    };
+
+    assert_eq!(
+        set.into_settings(),
+        vec![
+            Setting::Timezone(-180),
+            Setting::QuickCount(6),
+            Setting::Currency(None)
+        ]
+    );
+}
+
+#[test]
+fn set_some() {
+    let t = Request::Settings(Settings::Set(Set {
+        timezone: Some(Setter { set: -180 }),
+        day_start: Some(Setter { set: 5 }),
+        window: Some(Setter { set: 3 }),
+        quick_count: Some(Setter { set: 6 }),
+        currency: Some(Setter { set: None }),
+    }));
+    let json = json(
+        &t,
+        r#"{"settings":{"set":{"timezone":{"set":-180},"dayStart":{"set":5},"window":{"set":3},"quickCount":{"set":6},"currency":{"set":null}}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
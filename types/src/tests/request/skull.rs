@@ -1,16 +1,40 @@
 use crate::{
-    Request, Setter, SkullId,
+    Period, Request, Setter, SkullId,
     request::{
         Skull,
-        skull::{Create, Delete, Update},
+        skull::{Archive, Create, Delete, Get, List, Merge, Unarchive, Update},
     },
     tests::{json, rmp},
 };
 
+#[test]
+fn list_none() {
+    let t = Request::Skull(Skull::List(List {
+        include_archived: None,
+    }));
+    let json = json(&t, r#"{"skull":{"list":{}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn list() {
-    let t = Request::Skull(Skull::List);
-    let json = json(&t, r#"{"skull":"list"}"#).unwrap();
+    let t = Request::Skull(Skull::List(List {
+        include_archived: Some(false),
+    }));
+    let json = json(&t, r#"{"skull":{"list":{"includeArchived":false}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn get() {
+    let t = Request::Skull(Skull::Get(Get { id: SkullId(27) }));
+    let json = json(&t, r#"{"skull":{"get":{"id":27}}}"#).unwrap();
     let rmp = rmp(&t).unwrap();
 
     assert_eq!(t, json);
@@ -25,6 +49,7 @@ fn create_none() {
         icon: String::from("icon"),
         price: 1.0,
         limit: None,
+        period: Period::default(),
     }));
     let json = json(
         &t,
@@ -45,6 +70,7 @@ fn create_limit() {
         icon: String::from("icon"),
         price: 1.0,
         limit: Some(1.0),
+        period: Period::default(),
     }));
     let json = json(
         &t,
@@ -57,6 +83,27 @@ fn create_limit() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn create_period() {
+    let t = Request::Skull(Skull::Create(Create {
+        name: String::from("name"),
+        color: 1,
+        icon: String::from("icon"),
+        price: 1.0,
+        limit: Some(1.0),
+        period: Period::Day,
+    }));
+    let json = json(
+        &t,
+        r#"{"skull":{"create":{"name":"name","color":1,"icon":"icon","price":1,"limit":1,"period":"day"}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn update_none() {
     let t = Request::Skull(Skull::Update(Update {
@@ -68,6 +115,7 @@ fn update_none() {
         icon: None,
         price: Some(Setter { set: 1.0 }),
         limit: None,
+        period: None,
     }));
     let json = json(
         &t,
@@ -91,6 +139,7 @@ fn update_set_none() {
         icon: None,
         price: Some(Setter { set: 1.0 }),
         limit: Some(Setter { set: None }),
+        period: None,
     }));
     let json = json(
         &t,
@@ -114,6 +163,7 @@ fn update_set_limit() {
         icon: None,
         price: Some(Setter { set: 1.0 }),
         limit: Some(Setter { set: Some(1.0) }),
+        period: None,
     }));
     let json = json(
         &t,
@@ -126,6 +176,30 @@ fn update_set_limit() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn update_set_period() {
+    let t = Request::Skull(Skull::Update(Update {
+        id: SkullId(27),
+        name: None,
+        color: None,
+        icon: None,
+        price: None,
+        limit: None,
+        period: Some(Setter {
+            set: Period::Hours(12),
+        }),
+    }));
+    let json = json(
+        &t,
+        r#"{"skull":{"update":{"id":27,"period":{"set":{"hours":12}}}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn delete() {
     let t = Request::Skull(Skull::Delete(Delete { id: SkullId(27) }));
@@ -135,3 +209,36 @@ fn delete() {
     assert_eq!(t, json);
     assert_eq!(t, rmp);
 }
+
+#[test]
+fn archive() {
+    let t = Request::Skull(Skull::Archive(Archive { id: SkullId(27) }));
+    let json = json(&t, r#"{"skull":{"archive":{"id":27}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn unarchive() {
+    let t = Request::Skull(Skull::Unarchive(Unarchive { id: SkullId(27) }));
+    let json = json(&t, r#"{"skull":{"unarchive":{"id":27}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn merge() {
+    let t = Request::Skull(Skull::Merge(Merge {
+        from: SkullId(27),
+        into: SkullId(72),
+    }));
+    let json = json(&t, r#"{"skull":{"merge":{"from":27,"into":72}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
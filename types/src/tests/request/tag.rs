@@ -0,0 +1,94 @@
+use crate::{
+    Request, Setter, SkullId, TagId,
+    request::{
+        Tag,
+        tag::{Attach, Create, Delete, Detach, Update},
+    },
+    tests::{json, rmp},
+};
+
+#[test]
+fn list() {
+    let t = Request::Tag(Tag::List);
+    let json = json(&t, r#"{"tag":"list"}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn create() {
+    let t = Request::Tag(Tag::Create(Create {
+        name: String::from("name"),
+    }));
+    let json = json(&t, r#"{"tag":{"create":{"name":"name"}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn update_none() {
+    let t = Request::Tag(Tag::Update(Update {
+        id: TagId(27),
+        name: None,
+    }));
+    let json = json(&t, r#"{"tag":{"update":{"id":27}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn update_some() {
+    let t = Request::Tag(Tag::Update(Update {
+        id: TagId(27),
+        name: Some(Setter {
+            set: String::from("name"),
+        }),
+    }));
+    let json = json(&t, r#"{"tag":{"update":{"id":27,"name":{"set":"name"}}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn delete() {
+    let t = Request::Tag(Tag::Delete(Delete { id: TagId(27) }));
+    let json = json(&t, r#"{"tag":{"delete":{"id":27}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn attach() {
+    let t = Request::Tag(Tag::Attach(Attach {
+        id: TagId(27),
+        skull: SkullId(72),
+    }));
+    let json = json(&t, r#"{"tag":{"attach":{"id":27,"skull":72}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn detach() {
+    let t = Request::Tag(Tag::Detach(Detach {
+        id: TagId(27),
+        skull: SkullId(72),
+    }));
+    let json = json(&t, r#"{"tag":{"detach":{"id":27,"skull":72}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -1,5 +1,7 @@
 use crate::{
-    Change, Error, Kind, Millis, Occurrence, OccurrenceId, Payload, Quick, Response, Skull, SkullId,
+    Aggregate, Audit, AuditId, Change, Cursor, Entity, Error, Kind, Millis, Mutation, Occurrence,
+    OccurrenceId, Page, Payload, Period, Prediction, Quick, Response, Settings, Skull, SkullId,
+    Spending, Tag, TagId,
 };
 
 use super::{json, rmp};
@@ -60,6 +62,29 @@ fn deleted() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn skull() {
+    let t = Response::Payload(Payload::Skull(Skull {
+        id: SkullId(27),
+        name: String::from("name"),
+        color: 1,
+        icon: String::from("icon"),
+        price: 1.0,
+        limit: None,
+        period: Period::Day,
+        archived: false,
+    }));
+    let json = json(
+        &t,
+        r#"{"skull":{"id":27,"name":"name","color":1,"icon":"icon","price":1.0,"limit":null,"period":"day","archived":false}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn skulls() {
     let t = Response::Payload(Payload::Skulls(vec![Skull {
@@ -69,6 +94,8 @@ fn skulls() {
         icon: String::from("icon"),
         price: 1.0,
         limit: None,
+        period: Period::default(),
+        archived: false,
     }]));
     let json = json(
         &t,
@@ -104,6 +131,49 @@ fn quicks() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn prediction() {
+    let t = Response::Payload(Payload::Prediction(Some(Prediction {
+        skull: SkullId(72),
+        amount: 1.0,
+    })));
+    let json = json(&t, r#"{"prediction":{"skull":72,"amount":1}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn prediction_none() {
+    let t = Response::Payload(Payload::Prediction(None));
+    let json = json(&t, r#"{"prediction":null}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn occurrence() {
+    let t = Response::Payload(Payload::Occurrence(Occurrence {
+        id: OccurrenceId(27),
+        skull: SkullId(72),
+        amount: 1.0,
+        millis: Millis(2),
+        note: Some(String::from("note")),
+    }));
+    let json = json(
+        &t,
+        r#"{"occurrence":{"id":27,"skull":72,"amount":1.0,"millis":2,"note":"note"}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn occurrences() {
     let t = Response::Payload(Payload::Occurrences(vec![Occurrence {
@@ -111,6 +181,7 @@ fn occurrences() {
         skull: SkullId(72),
         amount: 1.0,
         millis: Millis(-27),
+        note: None,
     }]));
     let json = json(
         &t,
@@ -132,3 +203,133 @@ fn occurrences_empty() {
     assert_eq!(t, json);
     assert_eq!(t, rmp);
 }
+
+#[test]
+fn page() {
+    let t = Response::Payload(Payload::Page(Page {
+        occurrences: vec![Occurrence {
+            id: OccurrenceId(27),
+            skull: SkullId(72),
+            amount: 1.0,
+            millis: Millis(2),
+            note: None,
+        }],
+        next: Some(Cursor {
+            millis: Millis(2),
+            id: OccurrenceId(27),
+        }),
+    }));
+    let json = json(
+        &t,
+        r#"{"page":{"occurrences":[{"id":27,"skull":72,"amount":1.0,"millis":2}],"next":{"millis":2,"id":27}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn aggregates() {
+    let t = Response::Payload(Payload::Aggregates(vec![Aggregate {
+        skull: SkullId(72),
+        millis: Millis(27),
+        count: 2,
+        sum: 3.0,
+        min: 1.0,
+        max: 2.0,
+    }]));
+    let json = json(
+        &t,
+        r#"{"aggregates":[{"skull":72,"millis":27,"count":2,"sum":3,"min":1,"max":2}]}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn spending() {
+    let t = Response::Payload(Payload::Spending(vec![Spending {
+        skull: SkullId(72),
+        amount: 2.0,
+        cost: 5.5,
+    }]));
+    let json = json(&t, r#"{"spending":[{"skull":72,"amount":2,"cost":5.5}]}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn tags() {
+    let t = Response::Payload(Payload::Tags(vec![Tag {
+        id: TagId(27),
+        name: String::from("name"),
+        skulls: vec![SkullId(72)],
+    }]));
+    let json = json(&t, r#"{"tags":[{"id":27,"name":"name","skulls":[72]}]}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn audits() {
+    let t = Response::Payload(Payload::Audits(vec![Audit {
+        id: AuditId(27),
+        actor: Some(String::from("actor")),
+        millis: Millis(72),
+        entity: Entity::Tag,
+        mutation: Mutation::Update,
+        before: Some(String::from(r#"{"id":1,"name":"before","skulls":[]}"#)),
+        after: None,
+    }]));
+    let json = json(
+        &t,
+        r#"{"audits":[{"id":27,"actor":"actor","millis":72,"entity":"tag","mutation":"update","before":"{\"id\":1,\"name\":\"before\",\"skulls\":[]}","after":null}]}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn settings() {
+    let t = Response::Payload(Payload::Settings(Settings {
+        timezone: -180,
+        day_start: 5,
+        window: 3,
+        quick_count: 6,
+        currency: Some(String::from("€")),
+    }));
+    let json = json(
+        &t,
+        r#"{"settings":{"timezone":-180,"dayStart":5,"window":3,"quickCount":6,"currency":"€"}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn batch() {
+    let t = Response::Payload(Payload::Batch(vec![
+        Response::Payload(Payload::Change(Change::Created)),
+        Response::Payload(Payload::Occurrences(Vec::new())),
+    ]));
+    let json = json(&t, r#"{"batch":[{"change":"created"},{"occurrences":[]}]}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -1,7 +1,7 @@
 use super::{json, rmp};
 use crate::{
-    Change, Error, Kind, Message, Millis, Occurrence, OccurrenceId, Payload, Push, Quick, Response,
-    Skull, SkullId, ws,
+    Change, Error, Kind, Message, Millis, Occurrence, OccurrenceId, Payload, Period, Push, Quick,
+    Response, Settings, Skull, SkullId, Tag, TagId, ws,
 };
 
 #[test]
@@ -106,6 +106,8 @@ fn skulls() {
             icon: String::from("icon"),
             price: 1.0,
             limit: None,
+            period: Period::default(),
+            archived: false,
         }])),
     });
     let json = json(
@@ -161,6 +163,7 @@ fn occurrences() {
             skull: SkullId(72),
             amount: 1.0,
             millis: Millis(-27),
+            note: None,
         }])),
     });
     let json = json(
@@ -196,6 +199,8 @@ fn skull_created() {
         icon: String::from("icon"),
         price: 1.0,
         limit: None,
+        period: Period::default(),
+        archived: false,
     }));
     let json = json(
         &t,
@@ -217,6 +222,8 @@ fn skull_updated() {
         icon: String::from("icon"),
         price: 1.0,
         limit: None,
+        period: Period::default(),
+        archived: false,
     }));
     let json = json(
         &t,
@@ -239,6 +246,39 @@ fn skull_deleted() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn skull_archived() {
+    let t = Message::Push(Push::SkullArchived(SkullId(27)));
+    let json = json(&t, r#"{"push":{"skullArchived":27}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn skull_unarchived() {
+    let t = Message::Push(Push::SkullUnarchived(SkullId(27)));
+    let json = json(&t, r#"{"push":{"skullUnarchived":27}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn skull_merged() {
+    let t = Message::Push(Push::SkullMerged(ws::Merge {
+        from: SkullId(27),
+        into: SkullId(72),
+    }));
+    let json = json(&t, r#"{"push":{"skullMerged":{"from":27,"into":72}}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn occurrence_created() {
     let t = Message::Push(Push::OccurrencesCreated(vec![Occurrence {
@@ -246,6 +286,7 @@ fn occurrence_created() {
         skull: SkullId(72),
         amount: 1.0,
         millis: Millis(-27),
+        note: None,
     }]));
     let json = json(
         &t,
@@ -275,6 +316,7 @@ fn occurrence_updated() {
         skull: SkullId(72),
         amount: 1.0,
         millis: Millis(-27),
+        note: None,
     }));
     let json = json(
         &t,
@@ -287,6 +329,26 @@ fn occurrence_updated() {
     assert_eq!(t, rmp);
 }
 
+#[test]
+fn occurrences_updated() {
+    let t = Message::Push(Push::OccurrencesUpdated(vec![Occurrence {
+        id: OccurrenceId(27),
+        skull: SkullId(72),
+        amount: 1.0,
+        millis: Millis(-27),
+        note: None,
+    }]));
+    let json = json(
+        &t,
+        r#"{"push":{"occurrencesUpdated":[{"id":27,"skull":72,"amount":1,"millis":-27}]}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
 #[test]
 fn occurrence_deleted() {
     let t = Message::Push(Push::OccurrenceDeleted(OccurrenceId(27)));
@@ -296,3 +358,115 @@ fn occurrence_deleted() {
     assert_eq!(t, json);
     assert_eq!(t, rmp);
 }
+
+#[test]
+fn occurrences_deleted() {
+    let t = Message::Push(Push::OccurrencesDeleted(vec![
+        OccurrenceId(27),
+        OccurrenceId(72),
+    ]));
+    let json = json(&t, r#"{"push":{"occurrencesDeleted":[27,72]}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn tag_created() {
+    let t = Message::Push(Push::TagCreated(Tag {
+        id: TagId(27),
+        name: String::from("name"),
+        skulls: Vec::new(),
+    }));
+    let json = json(
+        &t,
+        r#"{"push":{"tagCreated":{"id":27,"name":"name","skulls":[]}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn tag_updated() {
+    let t = Message::Push(Push::TagUpdated(Tag {
+        id: TagId(27),
+        name: String::from("name"),
+        skulls: vec![SkullId(72)],
+    }));
+    let json = json(
+        &t,
+        r#"{"push":{"tagUpdated":{"id":27,"name":"name","skulls":[72]}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn tag_deleted() {
+    let t = Message::Push(Push::TagDeleted(TagId(27)));
+    let json = json(&t, r#"{"push":{"tagDeleted":27}}"#).unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn limit_reached() {
+    let t = Message::Push(Push::LimitReached(ws::Consumption {
+        skull: SkullId(27),
+        consumed: 2.0,
+        limit: 2.0,
+    }));
+    let json = json(
+        &t,
+        r#"{"push":{"limitReached":{"skull":27,"consumed":2,"limit":2}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn limit_exceeded() {
+    let t = Message::Push(Push::LimitExceeded(ws::Consumption {
+        skull: SkullId(27),
+        consumed: 2.5,
+        limit: 2.0,
+    }));
+    let json = json(
+        &t,
+        r#"{"push":{"limitExceeded":{"skull":27,"consumed":2.5,"limit":2}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
+
+#[test]
+fn settings_changed() {
+    let t = Message::Push(Push::SettingsChanged(Settings {
+        timezone: 60,
+        ..Settings::default()
+    }));
+    let json = json(
+        &t,
+        r#"{"push":{"settingsChanged":{"timezone":60,"dayStart":0,"window":2,"quickCount":12,"currency":null}}}"#,
+    )
+    .unwrap();
+    let rmp = rmp(&t).unwrap();
+
+    assert_eq!(t, json);
+    assert_eq!(t, rmp);
+}
@@ -1,4 +1,4 @@
-use crate::{Occurrence, OccurrenceId, Skull, SkullId};
+use crate::{Occurrence, OccurrenceId, Settings, Skull, SkullId, Tag, TagId};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct WithId<T> {
@@ -24,7 +24,31 @@ pub enum Push {
     SkullCreated(Skull),
     SkullUpdated(Skull),
     SkullDeleted(SkullId),
+    SkullArchived(SkullId),
+    SkullUnarchived(SkullId),
+    SkullMerged(Merge),
     OccurrencesCreated(Vec<Occurrence>),
     OccurrenceUpdated(Occurrence),
+    OccurrencesUpdated(Vec<Occurrence>),
     OccurrenceDeleted(OccurrenceId),
+    OccurrencesDeleted(Vec<OccurrenceId>),
+    TagCreated(Tag),
+    TagUpdated(Tag),
+    TagDeleted(TagId),
+    LimitReached(Consumption),
+    LimitExceeded(Consumption),
+    SettingsChanged(Settings),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Merge {
+    pub from: SkullId,
+    pub into: SkullId,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Consumption {
+    pub skull: SkullId,
+    pub consumed: f32,
+    pub limit: f32,
 }
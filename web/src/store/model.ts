@@ -7,8 +7,18 @@ export interface Skull {
   icon: string;
   price: number;
   limit?: number;
+  period?: Period;
+  archived?: boolean;
 }
 
+export type Period = { hours: number } | 'day' | 'week' | 'month';
+
+const isPeriod = (v: unknown): v is Period =>
+  v === 'day' ||
+  v === 'week' ||
+  v === 'month' ||
+  (typeof v === 'object' && v !== null && typeof (v as { hours?: unknown }).hours === 'number');
+
 const opacityToHex = (opacity?: string | number) =>
   opacity === undefined
     ? ''
@@ -36,12 +46,14 @@ export interface Occurrence {
   skull: number;
   amount: number;
   millis: Date;
+  note?: string;
 }
 
 export interface ProtoOccurrence {
   skull: number;
   amount: number;
   millis: Date;
+  note?: string;
 }
 
 export interface Response<T> {
@@ -116,16 +128,21 @@ export class EpochDays {
 }
 
 export const sealed = {
-  isSkullTuple: (v: unknown): v is [number, string, number, string, number, number?] =>
+  isSkullTuple: (
+    v: unknown,
+  ): v is [number, string, number, string, number, number?, Period?, boolean?] =>
     v instanceof Array &&
-    (v.length === 5 || v.length === 6) &&
+    v.length >= 5 &&
+    v.length <= 8 &&
     typeof v[0] === 'number' &&
     typeof v[1] === 'string' &&
     typeof v[2] === 'number' &&
     typeof v[3] === 'string' &&
     typeof v[4] === 'number' &&
-    (v[5] === undefined || v[5] === null || typeof v[5] === 'number'),
-  makeSkull: (s: [number, string, number, string, number, number?]) => {
+    (v[5] === undefined || v[5] === null || typeof v[5] === 'number') &&
+    (v[6] === undefined || isPeriod(v[6])) &&
+    (v[7] === undefined || typeof v[7] === 'boolean'),
+  makeSkull: (s: [number, string, number, string, number, number?, Period?, boolean?]) => {
     return {
       id: s[0],
       name: s[1],
@@ -133,6 +150,8 @@ export const sealed = {
       icon: s[3],
       price: s[4],
       limit: s[5],
+      period: s[6],
+      archived: s[7],
     };
   },
 
@@ -145,19 +164,22 @@ export const sealed = {
     };
   },
 
-  isOccurrenceTuple: (v: unknown): v is [number, number, number, number] =>
+  isOccurrenceTuple: (v: unknown): v is [number, number, number, number, (string | null)?] =>
     v instanceof Array &&
-    v.length === 4 &&
+    v.length >= 4 &&
+    v.length <= 5 &&
     typeof v[0] === 'number' &&
     typeof v[1] === 'number' &&
     typeof v[2] === 'number' &&
-    typeof v[3] === 'number',
-  makeOccurrence: (o: [number, number, number, number]) => {
+    typeof v[3] === 'number' &&
+    (v[4] === undefined || v[4] === null || typeof v[4] === 'string'),
+  makeOccurrence: (o: [number, number, number, number, (string | null)?]) => {
     return {
       id: o[0],
       skull: o[1],
       amount: Number(o[2].toFixed(3)),
       millis: new Date(o[3]),
+      note: o[4] ?? undefined,
     };
   },
 } as const;
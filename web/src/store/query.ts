@@ -12,7 +12,7 @@ import { Socket } from '../socket';
 export const sealed = {
   getSkulls: (socket: Socket): Promise<Skull[]> => {
     const id = newRequestId();
-    return socket.request({ id, skull: 'list' }, (message: unknown) => {
+    return socket.request({ id, skull: { list: {} } }, (message: unknown) => {
       const response = validateMessage(message, id, 'skulls', r => r instanceof Array);
 
       if (response !== undefined) {
@@ -28,7 +28,7 @@ export const sealed = {
 
   getQuicks: (socket: Socket): Promise<RawQuick[]> => {
     const id = newRequestId();
-    return socket.request({ id, occurrence: 'quick' }, (message: unknown) => {
+    return socket.request({ id, occurrence: { quick: {} } }, (message: unknown) => {
       const response = validateMessage(message, id, 'quicks', r => r instanceof Array);
 
       if (response !== undefined) {
@@ -412,6 +412,42 @@ export class Store {
           return true;
         }
         console.error('Expected push.skullDeleted to be a number');
+      } else if ('skullArchived' in push) {
+        if (typeof push.skullArchived === 'number') {
+          const id = push.skullArchived;
+          const skull = this.skulls.find(s => s.id === id);
+          if (skull) {
+            this.setSkulls([{ ...skull, archived: true }]);
+          }
+          return true;
+        }
+        console.error('Expected push.skullArchived to be a number');
+      } else if ('skullUnarchived' in push) {
+        if (typeof push.skullUnarchived === 'number') {
+          const id = push.skullUnarchived;
+          const skull = this.skulls.find(s => s.id === id);
+          if (skull) {
+            this.setSkulls([{ ...skull, archived: false }]);
+          }
+          return true;
+        }
+        console.error('Expected push.skullUnarchived to be a number');
+      } else if ('skullMerged' in push) {
+        const merged = push.skullMerged;
+        if (
+          merged instanceof Array &&
+          merged.length === 2 &&
+          typeof merged[0] === 'number' &&
+          typeof merged[1] === 'number'
+        ) {
+          const [from, into] = merged as [number, number];
+          this.setOccurrences(
+            this.occurrences.filter(o => o.skull === from).map(o => ({ ...o, skull: into })),
+          );
+          this.removeSkull(from);
+          return true;
+        }
+        console.error('Expected push.skullMerged to be a merge tuple');
       } else if ('occurrencesCreated' in push) {
         if (
           push.occurrencesCreated instanceof Array &&
@@ -427,12 +463,30 @@ export class Store {
           return true;
         }
         console.error('Expected push.occurrenceUpdated to be an occurrence tuple');
+      } else if ('occurrencesUpdated' in push) {
+        if (
+          push.occurrencesUpdated instanceof Array &&
+          push.occurrencesUpdated.every(modelSealed.isOccurrenceTuple)
+        ) {
+          this.setOccurrences(push.occurrencesUpdated.map(modelSealed.makeOccurrence));
+          return true;
+        }
+        console.error('Expected push.occurrencesUpdated to be a list of occurrence tuples');
       } else if ('occurrenceDeleted' in push) {
         if (typeof push.occurrenceDeleted === 'number') {
           this.removeOccurrence(push.occurrenceDeleted);
           return true;
         }
         console.error('Expected push.occurrenceDeleted to be a number');
+      } else if ('occurrencesDeleted' in push) {
+        if (
+          push.occurrencesDeleted instanceof Array &&
+          push.occurrencesDeleted.every(id => typeof id === 'number')
+        ) {
+          push.occurrencesDeleted.forEach(id => this.removeOccurrence(id));
+          return true;
+        }
+        console.error('Expected push.occurrencesDeleted to be a list of numbers');
       }
     }
     return false;